@@ -0,0 +1,16 @@
+use ottershipper_db::Application;
+
+/// A change to an application, published after it has been committed
+#[derive(Debug, Clone)]
+pub enum AppEvent {
+    /// A new application was created
+    Created(Application),
+    /// An application's name changed
+    Renamed {
+        id: String,
+        old: String,
+        new: String,
+    },
+    /// The application with this ID was deleted
+    Deleted(String),
+}
@@ -1,3 +1,10 @@
+mod events;
+mod naming;
 mod services;
 
-pub use services::ApplicationService;
+pub use events::AppEvent;
+pub use naming::{AdjectiveAnimalGenerator, NameGenerator};
+pub use services::{
+    AppDefinition, ApplicationCursorPage, ApplicationDetails, ApplicationPage, ApplicationService,
+    CreatePreflight, FileImport, ImportMode, ImportOutcome, RecentActivity,
+};
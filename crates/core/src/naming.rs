@@ -0,0 +1,70 @@
+/// Source of names for applications created without one
+pub trait NameGenerator: Send + Sync {
+    /// Generate a candidate application name
+    ///
+    /// Candidates are still validated and checked for uniqueness by the caller,
+    /// so generators only need to make collisions unlikely, not impossible.
+    fn generate(&self) -> String;
+}
+
+const ADJECTIVES: &[&str] = &[
+    "amber", "brave", "calm", "clever", "cosmic", "crisp", "daring", "eager", "fuzzy", "gentle",
+    "golden", "happy", "humble", "jolly", "keen", "lively", "lucky", "mellow", "nimble", "plucky",
+    "quiet", "rapid", "rustic", "shiny", "silent", "snowy", "sunny", "swift", "tidy", "witty",
+];
+
+const ANIMALS: &[&str] = &[
+    "badger", "beaver", "bison", "crane", "falcon", "ferret", "gecko", "heron", "ibex", "koala",
+    "lemur", "lynx", "marmot", "moose", "narwhal", "ocelot", "otter", "panda", "puffin", "quokka",
+    "raven", "salmon", "seal", "sloth", "stoat", "tapir", "walrus", "weasel", "wombat", "yak",
+];
+
+/// Generates names like `plucky-otter-4821`
+#[derive(Debug, Clone, Copy, Default)]
+pub struct AdjectiveAnimalGenerator;
+
+impl NameGenerator for AdjectiveAnimalGenerator {
+    fn generate(&self) -> String {
+        // UUIDv4 is already a dependency and a fine source of non-cryptographic randomness
+        let mut bits = uuid::Uuid::new_v4().as_u128();
+        let mut pick = |n: usize| {
+            let n128 = n as u128;
+            // Truncation is fine: the value is always < n
+            #[allow(clippy::cast_possible_truncation)]
+            let i = (bits % n128) as usize;
+            bits /= n128;
+            i
+        };
+
+        let adjective = ADJECTIVES[pick(ADJECTIVES.len())];
+        let animal = ANIMALS[pick(ANIMALS.len())];
+        let number = pick(10_000);
+
+        format!("{adjective}-{animal}-{number:04}")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ottershipper_db::validate_app_name;
+    use std::collections::HashSet;
+
+    #[test]
+    fn test_generated_names_are_valid() {
+        let generator = AdjectiveAnimalGenerator;
+        for _ in 0..500 {
+            let name = generator.generate();
+            assert!(validate_app_name(&name).is_ok(), "invalid name: {name}");
+        }
+    }
+
+    #[test]
+    fn test_generated_names_are_mostly_unique() {
+        let generator = AdjectiveAnimalGenerator;
+        let names: HashSet<_> = (0..200).map(|_| generator.generate()).collect();
+
+        // 9,000,000 combinations; a couple of collisions would already be very unlikely
+        assert!(names.len() >= 198);
+    }
+}
@@ -1,4 +1,97 @@
-use ottershipper_db::{Application, Database, DbError};
+use super::import::{self, FileImport, ImportMode, ImportOutcome, ParsedDefinition};
+use super::name_cache::NameCache;
+use crate::events::AppEvent;
+use crate::naming::{AdjectiveAnimalGenerator, NameGenerator};
+use ottershipper_db::{
+    AppQuery, AppStatus, Application, ApplicationRepository, AuditEntry, Database, DbError,
+    MetadataEntry, NameFilter, NamePolicy,
+};
+use std::collections::HashSet;
+use std::path::Path;
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::sync::broadcast;
+
+/// How many generated names to try before giving up on autonaming
+const AUTONAME_MAX_ATTEMPTS: usize = 5;
+
+/// How many suffixed names `create_app_unique` tries before giving up
+const UNIQUE_SUFFIX_MAX_ATTEMPTS: usize = 20;
+
+/// How long an idempotency key dedupes creates by default
+const DEFAULT_IDEMPOTENCY_WINDOW: Duration = Duration::from_hours(24);
+
+/// Events buffered per subscriber before the oldest are dropped
+const EVENT_CAPACITY: usize = 256;
+
+/// One day in milliseconds, the unit of the `recent_activity` windows
+const DAY_MS: i64 = 24 * 60 * 60 * 1000;
+
+/// One page of applications plus the information needed to fetch the next
+#[derive(Debug, Clone)]
+pub struct ApplicationPage {
+    /// Applications on this page
+    pub applications: Vec<Application>,
+    /// Total number of applications across all pages
+    pub total: i64,
+    /// Requested page size (`None` means unlimited)
+    pub limit: Option<i64>,
+    /// Number of applications skipped before this page
+    pub offset: i64,
+}
+
+impl ApplicationPage {
+    /// Whether more applications exist after this page
+    #[must_use]
+    pub fn has_more(&self) -> bool {
+        let returned = i64::try_from(self.applications.len()).unwrap_or(i64::MAX);
+        self.offset.saturating_add(returned) < self.total
+    }
+}
+
+/// One page of applications from keyset pagination
+#[derive(Debug, Clone)]
+pub struct ApplicationCursorPage {
+    /// Applications on this page
+    pub applications: Vec<Application>,
+    /// `(created_at, id)` to pass for the next page, or `None` on the last page
+    pub next_cursor: Option<(i64, String)>,
+}
+
+/// Outcome of checking whether a create would succeed, without creating
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CreatePreflight {
+    /// Whether the name passes the service's [`NamePolicy`]
+    pub valid: bool,
+    /// Whether creating an application with this name would succeed now
+    pub would_create: bool,
+    /// Why the create would fail, if it would
+    pub reason: Option<String>,
+}
+
+/// Applications created within the last day, week, and month, newest first
+///
+/// The windows overlap: an application created an hour ago is in all three.
+#[derive(Debug, Clone)]
+pub struct RecentActivity {
+    /// Created within the last 24 hours
+    pub last_24h: Vec<Application>,
+    /// Created within the last 7 days
+    pub last_7d: Vec<Application>,
+    /// Created within the last 30 days
+    pub last_30d: Vec<Application>,
+}
+
+/// An application together with its metadata and audit trail
+#[derive(Debug, Clone)]
+pub struct ApplicationDetails {
+    /// The application itself
+    pub application: Application,
+    /// Metadata, ordered by key
+    pub metadata: Vec<MetadataEntry>,
+    /// Audit trail, oldest first
+    pub audit_trail: Vec<AuditEntry>,
+}
 
 /// Service for application-related business logic
 ///
@@ -8,24 +101,160 @@ use ottershipper_db::{Application, Database, DbError};
 #[derive(Clone)]
 pub struct ApplicationService {
     db: Database,
+    policy: NamePolicy,
+    name_generator: Arc<dyn NameGenerator>,
+    idempotency_window: Duration,
+    max_applications: Option<u64>,
+    events: broadcast::Sender<AppEvent>,
+    name_cache: Option<Arc<NameCache>>,
+    /// Owner this service acts for, see [`Self::for_owner`]
+    owner: String,
 }
 
 impl ApplicationService {
     /// Create a new `ApplicationService`
     #[must_use]
     pub fn new(db: Database) -> Self {
-        Self { db }
+        Self {
+            db,
+            policy: NamePolicy::default(),
+            name_generator: Arc::new(AdjectiveAnimalGenerator),
+            idempotency_window: DEFAULT_IDEMPOTENCY_WINDOW,
+            max_applications: None,
+            events: broadcast::channel(EVENT_CAPACITY).0,
+            name_cache: None,
+            owner: String::new(),
+        }
+    }
+
+    /// Receive an [`AppEvent`] after each successful change
+    ///
+    /// Publishing never waits on subscribers: one that falls more than a few
+    /// hundred events behind gets `RecvError::Lagged` and misses them.
+    #[must_use]
+    pub fn subscribe(&self) -> broadcast::Receiver<AppEvent> {
+        self.events.subscribe()
+    }
+
+    fn publish(&self, event: AppEvent) {
+        // No subscribers is not an error
+        let _ = self.events.send(event);
+    }
+
+    /// Cap the total number of applications that may exist
+    #[must_use]
+    pub fn with_max_applications(mut self, max: Option<u64>) -> Self {
+        self.max_applications = max;
+        self
+    }
+
+    /// Set how long an idempotency key dedupes repeated creates
+    #[must_use]
+    pub fn with_idempotency_window(mut self, window: Duration) -> Self {
+        self.idempotency_window = window;
+        self
+    }
+
+    /// Use a custom generator for [`ApplicationService::create_app_autoname`]
+    #[must_use]
+    pub fn with_name_generator(mut self, generator: impl NameGenerator + 'static) -> Self {
+        self.name_generator = Arc::new(generator);
+        self
+    }
+
+    /// Use a custom name policy when creating applications
+    #[must_use]
+    pub fn with_name_policy(mut self, policy: NamePolicy) -> Self {
+        self.policy = policy;
+        self
+    }
+
+    /// Cache up to `capacity` lookups by name for `ttl`
+    ///
+    /// Entries are dropped when this service creates, renames, archives, or
+    /// deletes the application; changes made by other processes show up once
+    /// the entry expires.
+    #[must_use]
+    pub fn with_name_cache(mut self, capacity: usize, ttl: Duration) -> Self {
+        self.name_cache = Some(Arc::new(NameCache::new(capacity, ttl)));
+        self
+    }
+
+    /// This service, acting for `owner`
+    ///
+    /// Applications it creates belong to `owner`. Once names are scoped by
+    /// owner (see [`Database::scope_names_by_owner`]), it also looks names up,
+    /// checks them, and deletes by name among `owner`'s applications only.
+    /// Shares the database, cache, and event channel with `self`.
+    #[must_use]
+    pub fn for_owner(&self, owner: impl Into<String>) -> Self {
+        Self {
+            owner: owner.into(),
+            ..self.clone()
+        }
+    }
+
+    /// Owner this service acts for; empty for applications nobody owns
+    #[must_use]
+    pub fn owner(&self) -> &str {
+        &self.owner
+    }
+
+    /// Repository creating and looking names up for this service's owner
+    fn apps(&self) -> ApplicationRepository<'_> {
+        self.db.applications().with_owner(&self.owner)
+    }
+
+    /// Drop every cached name lookup, e.g. after the database file was replaced
+    pub fn clear_cache(&self) {
+        if let Some(cache) = &self.name_cache {
+            cache.clear();
+        }
+    }
+
+    /// Reopen the database (see [`Database::reconnect`]) and drop cached
+    /// lookups, which may describe the file that was replaced
+    pub async fn reconnect(&self) -> Result<(), DbError> {
+        self.db.reconnect().await?;
+        self.clear_cache();
+        Ok(())
+    }
+
+    fn uncache_name(&self, name: &str) {
+        if let Some(cache) = &self.name_cache {
+            cache.remove_name(name);
+        }
+    }
+
+    fn uncache_id(&self, id: &str) {
+        if let Some(cache) = &self.name_cache {
+            cache.remove_id(id);
+        }
+    }
+
+    /// Underlying database, for maintenance operations
+    #[must_use]
+    pub fn database(&self) -> &Database {
+        &self.db
+    }
+
+    /// Name policy applied by this service
+    #[must_use]
+    pub fn name_policy(&self) -> &NamePolicy {
+        &self.policy
     }
 
     /// Create a new application
     ///
     /// # Arguments
-    /// * `name` - Application name (alphanumeric, hyphens, underscores, max 255 chars)
+    /// * `name` - Application name, normalized with [`NamePolicy::normalize`] and
+    ///   then validated against the service's [`NamePolicy`]
     ///
     /// # Returns
     /// * `Ok(Application)` - Successfully created application with id and timestamp
     /// * `Err(DbError::InvalidName)` - Name validation failed
     /// * `Err(DbError::DuplicateName)` - Application with this name already exists
+    /// * `Err(DbError::LimitExceeded)` - The configured application cap is reached
     ///
     /// # Examples
     /// ```ignore
@@ -35,7 +264,291 @@ impl ApplicationService {
     /// ```
     pub async fn create_app(&self, name: String) -> Result<Application, DbError> {
         // Validation and creation is handled by the repository
-        self.db.applications().create(&name).await
+        self.insert_app(&self.policy.normalize(&name), None, None)
+            .await
+    }
+
+    /// Check whether `create_app(name)` would succeed, without side effects
+    ///
+    /// Runs the same name validation as create, then checks that the database
+    /// is writable, the name is free, and `max_applications` isn't reached.
+    pub async fn preflight_create(&self, name: &str) -> Result<CreatePreflight, DbError> {
+        let name = self.policy.normalize(name);
+        if let Err(e) = self.policy.validate(&name) {
+            return Ok(CreatePreflight {
+                valid: false,
+                would_create: false,
+                reason: Some(e.to_string()),
+            });
+        }
+
+        let reason = if self.db.is_read_only() {
+            Some(DbError::ReadOnly)
+        } else if self.apps().exists_by_name(&name).await? {
+            Some(DbError::DuplicateName(name.to_string()))
+        } else {
+            self.limit_reached().await?.map(DbError::LimitExceeded)
+        };
+
+        Ok(CreatePreflight {
+            valid: true,
+            would_create: reason.is_none(),
+            reason: reason.map(|e| e.to_string()),
+        })
+    }
+
+    /// The `max_applications` cap, if one is set and already reached
+    async fn limit_reached(&self) -> Result<Option<u64>, DbError> {
+        let Some(max) = self.max_applications else {
+            return Ok(None);
+        };
+        let count = self.db.applications().count().await?;
+        Ok((u64::try_from(count).unwrap_or(0) >= max).then_some(max))
+    }
+
+    /// Create a new application with a generated name
+    ///
+    /// Generated names that collide with an existing application are retried
+    /// a few times before the `DuplicateName` error is returned.
+    pub async fn create_app_autoname(&self) -> Result<Application, DbError> {
+        self.insert_autonamed_app(None).await
+    }
+
+    /// Create an application named `base`, or `base-2`, `base-3`, ... if taken
+    ///
+    /// The base is shortened when needed so suffixed names still fit the policy's
+    /// length limit. Fails with `DuplicateName` after a bounded number of attempts.
+    pub async fn create_app_unique(&self, base: String) -> Result<Application, DbError> {
+        let base = self.policy.normalize(&base);
+        // Reject bad bases up front instead of retrying every suffix
+        self.policy.validate(&base)?;
+
+        let mut last_err = None;
+        for n in 1..=UNIQUE_SUFFIX_MAX_ATTEMPTS {
+            let name = if n == 1 {
+                base.to_string()
+            } else {
+                self.suffixed_name(&base, n)
+            };
+            match self.insert_app(&name, None, None).await {
+                Err(e @ DbError::DuplicateName(_)) => last_err = Some(e),
+                result => return result,
+            }
+        }
+
+        Err(last_err.unwrap_or_else(|| DbError::DuplicateName(base.to_string())))
+    }
+
+    /// `base-n`, with `base` shortened so the result fits the policy's length limit
+    fn suffixed_name(&self, base: &str, n: usize) -> String {
+        let suffix = format!("-{n}");
+        let room = self.policy.max_len.saturating_sub(suffix.len());
+        let truncated: String = base.chars().take(room).collect();
+        // Don't leave a separator dangling before the suffix
+        format!("{}{suffix}", truncated.trim_end_matches(['-', '_', '.']))
+    }
+
+    /// Create an application unless `idempotency_key` was already used
+    ///
+    /// If an application was created with the same key within the idempotency
+    /// window it is returned instead of creating a new one. A `name` of `None`
+    /// generates one as in [`Self::create_app_autoname`].
+    ///
+    /// # Returns
+    /// * `Ok((Application, true))` - A new application was created
+    /// * `Ok((Application, false))` - The key was seen before; the original application
+    pub async fn create_app_idempotent(
+        &self,
+        name: Option<String>,
+        idempotency_key: &str,
+    ) -> Result<(Application, bool), DbError> {
+        if let Some(app) = self.find_by_idempotency_key(idempotency_key).await? {
+            return Ok((app, false));
+        }
+
+        // Keys older than the window are forgotten so they can be reused
+        self.db
+            .applications()
+            .release_idempotency_key(idempotency_key, self.idempotency_cutoff())
+            .await?;
+
+        let result = match name {
+            Some(name) => {
+                self.insert_app(&self.policy.normalize(&name), Some(idempotency_key), None)
+                    .await
+            }
+            None => self.insert_autonamed_app(Some(idempotency_key)).await,
+        };
+
+        match result {
+            Ok(app) => Ok((app, true)),
+            Err(DbError::DuplicateName(name)) => {
+                // A concurrent retry with the same key may have won the race
+                match self.find_by_idempotency_key(idempotency_key).await? {
+                    Some(app) => Ok((app, false)),
+                    None => Err(DbError::DuplicateName(name)),
+                }
+            }
+            Err(e) => Err(e),
+        }
+    }
+
+    async fn find_by_idempotency_key(&self, key: &str) -> Result<Option<Application>, DbError> {
+        self.db
+            .applications()
+            .find_by_idempotency_key(key, self.idempotency_cutoff())
+            .await
+    }
+
+    /// Epoch millis before which idempotency keys are ignored
+    fn idempotency_cutoff(&self) -> i64 {
+        let window_ms = i64::try_from(self.idempotency_window.as_millis()).unwrap_or(i64::MAX);
+        self.db.clock().now_millis().saturating_sub(window_ms)
+    }
+
+    /// Insert an application, enforcing `max_applications`, and publish it
+    ///
+    /// `description` is only stored for creates without an idempotency key.
+    async fn insert_app(
+        &self,
+        name: &str,
+        idempotency_key: Option<&str>,
+        description: Option<&str>,
+    ) -> Result<Application, DbError> {
+        let app = self
+            .insert_app_unpublished(name, idempotency_key, description)
+            .await?;
+        self.uncache_name(&app.name);
+        self.publish(AppEvent::Created(app.clone()));
+        Ok(app)
+    }
+
+    async fn insert_app_unpublished(
+        &self,
+        name: &str,
+        idempotency_key: Option<&str>,
+        description: Option<&str>,
+    ) -> Result<Application, DbError> {
+        let Some(max) = self.max_applications else {
+            let repo = self.apps().with_policy(&self.policy);
+            return match idempotency_key {
+                Some(key) => repo.create_with_idempotency_key(name, key).await,
+                None => repo.create_with_description(name, description).await,
+            };
+        };
+
+        // Count and insert under the write lock so concurrent creates can't overshoot
+        let mut tx = self.db.begin_immediate().await?;
+        let count = tx.applications().count().await?;
+        if u64::try_from(count).unwrap_or(0) >= max {
+            return Err(DbError::LimitExceeded(max));
+        }
+
+        let repo = tx
+            .applications()
+            .with_owner(&self.owner)
+            .with_policy(&self.policy);
+        let app = match idempotency_key {
+            Some(key) => repo.create_with_idempotency_key(name, key).await?,
+            None => repo.create_with_description(name, description).await?,
+        };
+        tx.commit().await?;
+
+        Ok(app)
+    }
+
+    /// Create a copy of the application `source_id` called `new_name`
+    ///
+    /// The copy gets a fresh ID and `created_at` but keeps the source's
+    /// description. Its status is not copied: like every new application the
+    /// copy starts out active, so archived templates can be cloned into use.
+    /// Fails with `NotFound` if the source doesn't exist.
+    pub async fn clone_app(
+        &self,
+        source_id: &str,
+        new_name: String,
+    ) -> Result<Application, DbError> {
+        let source = self.get_app_or_err(source_id).await?;
+        self.insert_app(
+            &self.policy.normalize(&new_name),
+            None,
+            source.description.as_deref(),
+        )
+        .await
+    }
+
+    /// Ensure an application called `name` exists with the given description
+    ///
+    /// Creates it if missing (subject to `max_applications`) or updates it in
+    /// place. Returns the application and whether it was created.
+    pub async fn apply_app(
+        &self,
+        name: &str,
+        description: Option<&str>,
+    ) -> Result<(Application, bool), DbError> {
+        let (app, created) = self
+            .upsert_app(&self.policy.normalize(name), description)
+            .await?;
+        self.uncache_name(&app.name);
+        if created {
+            self.publish(AppEvent::Created(app.clone()));
+        }
+        Ok((app, created))
+    }
+
+    async fn upsert_app(
+        &self,
+        name: &str,
+        description: Option<&str>,
+    ) -> Result<(Application, bool), DbError> {
+        let Some(max) = self.max_applications else {
+            return self
+                .db
+                .applications()
+                .with_owner(&self.owner)
+                .with_policy(&self.policy)
+                .upsert_by_name(name, description)
+                .await;
+        };
+
+        // Only a create counts against the cap; check it under the write lock
+        let mut tx = self.db.begin_immediate().await?;
+        if !tx
+            .applications()
+            .with_owner(&self.owner)
+            .exists_by_name(name)
+            .await?
+        {
+            let count = tx.applications().count().await?;
+            if u64::try_from(count).unwrap_or(0) >= max {
+                return Err(DbError::LimitExceeded(max));
+            }
+        }
+
+        let result = tx
+            .applications()
+            .with_owner(&self.owner)
+            .with_policy(&self.policy)
+            .upsert_by_name(name, description)
+            .await?;
+        tx.commit().await?;
+
+        Ok(result)
+    }
+
+    async fn insert_autonamed_app(
+        &self,
+        idempotency_key: Option<&str>,
+    ) -> Result<Application, DbError> {
+        let mut attempt = 1;
+        loop {
+            let name = self.name_generator.generate();
+            let name = self.policy.normalize(&name);
+            match self.insert_app(&name, idempotency_key, None).await {
+                Err(DbError::DuplicateName(_)) if attempt < AUTONAME_MAX_ATTEMPTS => attempt += 1,
+                result => return result,
+            }
+        }
     }
 
     /// Get application by ID
@@ -43,9 +556,59 @@ impl ApplicationService {
         self.db.applications().get(id).await
     }
 
-    /// Get application by name
+    /// Get application by name, from the name cache if one is configured
+    ///
+    /// `name` is normalized with [`NamePolicy::normalize`] first.
     pub async fn get_app_by_name(&self, name: &str) -> Result<Option<Application>, DbError> {
-        self.db.applications().get_by_name(name).await
+        let name = self.policy.normalize(name);
+        let Some(cache) = &self.name_cache else {
+            return self.apps().get_by_name(&name).await;
+        };
+
+        let now = self.db.clock().now_millis();
+        if let Some(app) = cache.get(&self.owner, &name, now) {
+            return Ok(Some(app));
+        }
+        let app = self.apps().get_by_name(&name).await?;
+        if let Some(app) = &app {
+            cache.insert(&self.owner, app.clone(), now);
+        }
+        Ok(app)
+    }
+
+    /// Get the application with this ID along with its metadata and audit trail
+    ///
+    /// Everything is read in one transaction, so the parts are consistent with
+    /// each other. Fails with `NotFound` if the application doesn't exist.
+    pub async fn describe_app(&self, id: &str) -> Result<ApplicationDetails, DbError> {
+        let mut tx = self.db.begin().await?;
+        let application = tx.applications().get_or_err(id).await?;
+        let metadata = tx.applications().list_meta(id).await?;
+        let audit_trail = tx.applications().audit_trail(id).await?;
+        tx.commit().await?;
+
+        Ok(ApplicationDetails {
+            application,
+            metadata,
+            audit_trail,
+        })
+    }
+
+    /// Get application by ID, failing with `NotFound` if it doesn't exist
+    pub async fn get_app_or_err(&self, id: &str) -> Result<Application, DbError> {
+        self.db.applications().get_or_err(id).await
+    }
+
+    /// Get application by name, failing with `NotFound` if it doesn't exist
+    pub async fn get_app_by_name_or_err(&self, name: &str) -> Result<Application, DbError> {
+        self.get_app_by_name(name)
+            .await?
+            .ok_or_else(|| DbError::NotFound(name.to_string()))
+    }
+
+    /// Count all applications
+    pub async fn count_apps(&self) -> Result<i64, DbError> {
+        self.db.applications().count().await
     }
 
     /// List all applications
@@ -53,30 +616,571 @@ impl ApplicationService {
         self.db.applications().list().await
     }
 
+    /// List one page of applications along with the total count
+    ///
+    /// The page and the count are read in one transaction so they are consistent.
+    /// Archived applications are left out of both unless `include_archived` is set.
+    pub async fn list_apps_page(
+        &self,
+        limit: Option<i64>,
+        offset: i64,
+        include_archived: bool,
+    ) -> Result<ApplicationPage, DbError> {
+        let offset = offset.max(0);
+        let mut tx = self.db.begin().await?;
+        let total = Self::listing(tx.applications(), include_archived)
+            .count()
+            .await?;
+        let applications = Self::listing(tx.applications(), include_archived)
+            .list_page(limit, offset)
+            .await?;
+        tx.commit().await?;
+
+        Ok(ApplicationPage {
+            applications,
+            total,
+            limit,
+            offset,
+        })
+    }
+
+    /// List up to `limit` applications after `cursor`, newest first
+    ///
+    /// Unlike [`Self::list_apps_page`], pages don't shift when applications are
+    /// created or deleted between calls. See
+    /// [`ApplicationRepository::list_after`](ottershipper_db::ApplicationRepository::list_after).
+    pub async fn list_apps_after(
+        &self,
+        cursor: Option<(i64, &str)>,
+        limit: i64,
+        include_archived: bool,
+    ) -> Result<ApplicationCursorPage, DbError> {
+        let limit = limit.max(1);
+        // Fetch one extra row to learn whether another page exists
+        let mut applications = Self::listing(self.db.applications(), include_archived)
+            .list_after(cursor, limit.saturating_add(1))
+            .await?;
+
+        let has_more = applications.len() > usize::try_from(limit).unwrap_or(usize::MAX);
+        let next_cursor = if has_more {
+            applications.pop();
+            applications
+                .last()
+                .map(|app| (app.created_at, app.id.clone()))
+        } else {
+            None
+        };
+
+        Ok(ApplicationCursorPage {
+            applications,
+            next_cursor,
+        })
+    }
+
+    /// One page of the applications matching `query`, with the total across pages
+    ///
+    /// The limit and offset are clamped as the repository clamps them, see
+    /// [`AppQuery::page_limit`] and [`AppQuery::page_offset`]; the returned
+    /// page reports the values actually used. The page and the total are read
+    /// in one transaction so they are consistent.
+    pub async fn query_apps(&self, query: AppQuery) -> Result<ApplicationPage, DbError> {
+        let query = AppQuery {
+            limit: query.page_limit(),
+            offset: query.page_offset(),
+            ..query
+        };
+
+        let mut tx = self.db.begin().await?;
+        let total = tx.applications().query_count(&query).await?;
+        let applications = tx.applications().query(&query).await?;
+        tx.commit().await?;
+
+        Ok(ApplicationPage {
+            applications,
+            total,
+            limit: Some(query.limit),
+            offset: query.offset,
+        })
+    }
+
+    /// Number of applications matching `query`, ignoring its order and page
+    ///
+    /// Counts in the database, so no rows are read. Fails with
+    /// `InvalidArgument` if `name_contains` is too long.
+    pub async fn count_apps_matching(&self, query: &AppQuery) -> Result<i64, DbError> {
+        self.db.applications().query_count(query).await
+    }
+
+    /// `repo`, hiding archived applications unless `include_archived` is set
+    fn listing(
+        repo: ApplicationRepository<'_>,
+        include_archived: bool,
+    ) -> ApplicationRepository<'_> {
+        if include_archived {
+            repo
+        } else {
+            repo.hide_archived()
+        }
+    }
+
+    /// Rename the application with this ID to `new_name`
+    ///
+    /// Fails with `NotFound` if there is no such application, and
+    /// `InvalidName` or `DuplicateName` if `new_name` can't be used.
+    pub async fn rename_app(&self, id: &str, new_name: &str) -> Result<Application, DbError> {
+        let new_name = self.policy.normalize(new_name);
+        let mut tx = self.db.begin_immediate().await?;
+        let old = tx.applications().get_or_err(id).await?;
+        let app = tx
+            .applications()
+            .with_owner(&self.owner)
+            .with_policy(&self.policy)
+            .rename(id, &new_name)
+            .await?;
+        tx.commit().await?;
+
+        self.uncache_name(&old.name);
+        self.uncache_name(&app.name);
+        self.publish(AppEvent::Renamed {
+            id: app.id.clone(),
+            old: old.name,
+            new: app.name.clone(),
+        });
+        Ok(app)
+    }
+
+    /// Rename every application whose name starts with `old_prefix` to start
+    /// with `new_prefix` instead, e.g. `svc-api` to `team-a-api`
+    ///
+    /// `old_prefix` is normalized like a name, so it matches names stored with
+    /// the policy's casing. Once names are scoped by owner, only this service's
+    /// owner's applications are renamed. All renames happen in one transaction: if any new
+    /// name is invalid or taken by an application that isn't being renamed,
+    /// nothing is renamed and the error is returned. Returns the `(old, new)`
+    /// name pairs, ordered by old name.
+    pub async fn rename_prefix(
+        &self,
+        old_prefix: &str,
+        new_prefix: &str,
+    ) -> Result<Vec<(String, String)>, DbError> {
+        let old_prefix = self.policy.normalize(old_prefix);
+        if old_prefix.is_empty() {
+            return Err(DbError::InvalidArgument(
+                "old prefix must not be empty".to_string(),
+            ));
+        }
+
+        let mut tx = self.db.begin_immediate().await?;
+        let mut apps = tx
+            .applications()
+            .with_owner(&self.owner)
+            .list_with_prefix(&old_prefix)
+            .await?;
+        // Names are unique row by row, so rename in an order where no new name
+        // is still held by an application waiting its turn: longest first when
+        // names grow (`a-a-x` -> `a-a-a-x` before `a-x` -> `a-a-x`), shortest
+        // first when they shrink. Same-length prefixes never overlap.
+        if new_prefix.len() > old_prefix.len() {
+            apps.sort_by_key(|app| std::cmp::Reverse(app.name.len()));
+        } else {
+            apps.sort_by_key(|app| app.name.len());
+        }
+
+        let mut renamed = Vec::with_capacity(apps.len());
+        for app in apps {
+            let Some(rest) = app.name.strip_prefix(old_prefix.as_ref()) else {
+                continue;
+            };
+            let new_name = self
+                .policy
+                .normalize(&format!("{new_prefix}{rest}"))
+                .into_owned();
+            tx.applications()
+                .with_owner(&self.owner)
+                .with_policy(&self.policy)
+                .rename(&app.id, &new_name)
+                .await?;
+            renamed.push((app.id, app.name, new_name));
+        }
+        tx.commit().await?;
+        renamed.sort_by(|(_, a, _), (_, b, _)| a.cmp(b));
+
+        let mut pairs = Vec::with_capacity(renamed.len());
+        for (id, old, new) in renamed {
+            self.uncache_name(&old);
+            self.uncache_name(&new);
+            self.publish(AppEvent::Renamed {
+                id,
+                old: old.clone(),
+                new: new.clone(),
+            });
+            pairs.push((old, new));
+        }
+        Ok(pairs)
+    }
+
+    /// Mark the application with this ID as active now, returning whether it exists
+    ///
+    /// Only `updated_at` changes, and nothing is added to the audit log.
+    pub async fn touch_app(&self, id: &str) -> Result<bool, DbError> {
+        let touched = self.db.applications().touch(id).await?;
+        if touched {
+            self.uncache_id(id);
+        }
+        Ok(touched)
+    }
+
+    /// Number of applications with each status, including statuses with none
+    pub async fn count_apps_by_status(&self) -> Result<Vec<(AppStatus, i64)>, DbError> {
+        self.db.applications().count_by_status().await
+    }
+
+    /// Archive the application with this ID, hiding it from default listings
+    ///
+    /// Archived applications can still be fetched by ID or name.
+    pub async fn archive_app(&self, id: &str) -> Result<Application, DbError> {
+        self.set_status(id, AppStatus::Archived).await
+    }
+
+    /// Return an archived application to default listings
+    pub async fn unarchive_app(&self, id: &str) -> Result<Application, DbError> {
+        self.set_status(id, AppStatus::Active).await
+    }
+
+    async fn set_status(&self, id: &str, status: AppStatus) -> Result<Application, DbError> {
+        let app = self.db.applications().set_status(id, status).await?;
+        self.uncache_id(id);
+        Ok(app)
+    }
+
+    /// List the `n` most recently created applications, newest first
+    pub async fn recent_apps(&self, n: i64) -> Result<Vec<Application>, DbError> {
+        self.db.applications().recent(n).await
+    }
+
+    /// Audit trail of the application with this ID, oldest first
+    pub async fn audit_trail(&self, id: &str) -> Result<Vec<AuditEntry>, DbError> {
+        self.db.applications().audit_trail(id).await
+    }
+
+    /// The `n` most recent audit entries across all applications, newest first
+    pub async fn recent_audit(&self, n: i64) -> Result<Vec<AuditEntry>, DbError> {
+        self.db.applications().recent_audit(n).await
+    }
+
+    /// List applications created within `[start_ms, end_ms]` (epoch millis, inclusive)
+    pub async fn list_apps_created_between(
+        &self,
+        start_ms: i64,
+        end_ms: i64,
+    ) -> Result<Vec<Application>, DbError> {
+        self.db
+            .applications()
+            .list_created_between(start_ms, end_ms)
+            .await
+    }
+
+    /// Applications created within the last 24 hours, 7 days, and 30 days
+    pub async fn recent_activity(&self) -> Result<RecentActivity, DbError> {
+        let now = self.db.clock().now_millis();
+        let last_30d = self
+            .db
+            .applications()
+            .list_created_between(now - 30 * DAY_MS, now)
+            .await?;
+
+        let since = |days: i64| {
+            last_30d
+                .iter()
+                .filter(|app| app.created_at >= now - days * DAY_MS)
+                .cloned()
+                .collect()
+        };
+        Ok(RecentActivity {
+            last_24h: since(1),
+            last_7d: since(7),
+            last_30d,
+        })
+    }
+
+    /// List applications whose name matches a `*`/`?` glob, ordered by name
+    pub async fn find_apps(&self, pattern: &str) -> Result<Vec<Application>, DbError> {
+        self.db.applications().list_matching(pattern).await
+    }
+
+    /// List applications whose name passes `filter`, ordered by name
+    pub async fn filter_apps(&self, filter: &NameFilter) -> Result<Vec<Application>, DbError> {
+        self.db.applications().filter(filter).await
+    }
+
+    /// Search application names, descriptions, and metadata values for `text`
+    ///
+    /// Matching ignores ASCII case and treats every character literally.
+    /// Applications matching by name come first, then by description, then by
+    /// metadata, each listed once. Fails with `InvalidArgument` for blank or
+    /// overly long text.
+    pub async fn search_all(&self, text: &str) -> Result<Vec<Application>, DbError> {
+        self.db.applications().search_all(text).await
+    }
+
+    /// List applications whose name and description contain every word of
+    /// `text`, best match first
+    ///
+    /// Uses the full-text index when enabled, and `LIKE` otherwise; see
+    /// [`ottershipper_db::ApplicationRepository::search_fts`].
+    pub async fn search_text(&self, text: &str) -> Result<Vec<Application>, DbError> {
+        self.db.applications().search_fts(text).await
+    }
+
+    /// Set metadata `key` of the application with this ID, replacing any previous value
+    pub async fn set_app_meta(&self, id: &str, key: &str, value: &str) -> Result<(), DbError> {
+        self.db.applications().set_meta(id, key, value).await
+    }
+
+    /// Value of metadata `key` on the application with this ID, if set
+    ///
+    /// Fails with `NotFound` if the application doesn't exist.
+    pub async fn get_app_meta(&self, id: &str, key: &str) -> Result<Option<String>, DbError> {
+        self.get_app_or_err(id).await?;
+        self.db.applications().get_meta(id, key).await
+    }
+
+    /// All metadata of the application with this ID, ordered by key
+    ///
+    /// Fails with `NotFound` if the application doesn't exist.
+    pub async fn list_app_meta(&self, id: &str) -> Result<Vec<MetadataEntry>, DbError> {
+        self.get_app_or_err(id).await?;
+        self.db.applications().list_meta(id).await
+    }
+
+    /// List applications whose metadata `key` is exactly `value`, ordered by name
+    pub async fn find_apps_by_meta(
+        &self,
+        key: &str,
+        value: &str,
+    ) -> Result<Vec<Application>, DbError> {
+        self.db.applications().find_by_meta(key, value).await
+    }
+
+    /// Remove metadata `key` from the application with this ID, returning whether it was set
+    pub async fn delete_app_meta(&self, id: &str, key: &str) -> Result<bool, DbError> {
+        self.db.applications().delete_meta(id, key).await
+    }
+
     /// Delete application by ID
     pub async fn delete_app(&self, id: &str) -> Result<bool, DbError> {
-        self.db.applications().delete(id).await
+        let deleted = self.db.applications().delete(id).await?;
+        if deleted {
+            self.uncache_id(id);
+            self.publish(AppEvent::Deleted(id.to_string()));
+        }
+        Ok(deleted)
+    }
+
+    /// Delete application by name
+    pub async fn delete_app_by_name(&self, name: &str) -> Result<bool, DbError> {
+        // Resolve the ID first so the event identifies the application removed
+        let name = self.policy.normalize(name);
+        let Some(app) = self.apps().get_by_name(&name).await? else {
+            return Ok(false);
+        };
+        self.delete_app(&app.id).await
+    }
+
+    /// Delete the application with this ID only if it is still named `name`
+    ///
+    /// The check and the delete happen together, so a concurrent rename can't
+    /// slip in between. Fails with `InvalidArgument` if the application has a
+    /// different name; returns `false` if there is no such application.
+    pub async fn delete_app_confirmed(&self, id: &str, name: &str) -> Result<bool, DbError> {
+        let deleted = self
+            .delete_apps_confirmed(&[(id.to_string(), name.to_string())])
+            .await?;
+        Ok(deleted > 0)
+    }
+
+    /// Delete each `(id, name)` application only if it still has that name,
+    /// returning how many were removed
+    ///
+    /// All or nothing: if any ID belongs to an application with a different
+    /// name, nothing is deleted and this fails with `InvalidArgument`.
+    /// Unknown IDs are ignored.
+    pub async fn delete_apps_confirmed(
+        &self,
+        targets: &[(String, String)],
+    ) -> Result<u64, DbError> {
+        let names: Vec<_> = targets
+            .iter()
+            .map(|(_, name)| self.policy.normalize(name))
+            .collect();
+        let targets: Vec<(&str, &str)> = targets
+            .iter()
+            .zip(&names)
+            .map(|((id, _), name)| (id.as_str(), name.as_ref()))
+            .collect();
+        let deleted = self
+            .db
+            .applications()
+            .delete_many_confirmed(&targets)
+            .await?;
+
+        let count = deleted.len() as u64;
+        for id in deleted {
+            self.uncache_id(&id);
+            self.publish(AppEvent::Deleted(id));
+        }
+        Ok(count)
+    }
+
+    /// Delete applications by ID, returning how many were removed
+    pub async fn delete_apps(&self, ids: &[String]) -> Result<u64, DbError> {
+        let ids: Vec<&str> = ids.iter().map(String::as_str).collect();
+        let deleted = self
+            .db
+            .applications()
+            .delete_many_returning_ids(&ids)
+            .await?;
+
+        let count = deleted.len() as u64;
+        for id in deleted {
+            self.uncache_id(&id);
+            self.publish(AppEvent::Deleted(id));
+        }
+        Ok(count)
+    }
+
+    /// Delete every application and its metadata, returning how many
+    /// applications were removed
+    ///
+    /// Meant for resetting development instances. Each removal is audited
+    /// and published like [`Self::delete_apps`].
+    pub async fn reset(&self) -> Result<u64, DbError> {
+        let deleted = self.db.applications().delete_all_returning_ids().await?;
+
+        let count = deleted.len() as u64;
+        for id in deleted {
+            self.uncache_id(&id);
+            self.publish(AppEvent::Deleted(id));
+        }
+        Ok(count)
+    }
+
+    /// Create an application from each `*.json` file in `dir`
+    ///
+    /// Each file holds one [`AppDefinition`](super::AppDefinition). Every file
+    /// is validated before anything is written, and all creates happen in one
+    /// transaction. With [`ImportMode::Skip`], invalid files and names that
+    /// already exist are reported as skipped; with [`ImportMode::Fail`], the
+    /// first one aborts the import. Exceeding `max_applications` always aborts.
+    pub async fn import_dir(
+        &self,
+        dir: &Path,
+        mode: ImportMode,
+    ) -> Result<Vec<FileImport>, DbError> {
+        let definitions = import::read_definitions(dir).await?;
+        self.import_definitions(definitions, mode).await
+    }
+
+    async fn import_definitions(
+        &self,
+        definitions: Vec<ParsedDefinition>,
+        mode: ImportMode,
+    ) -> Result<Vec<FileImport>, DbError> {
+        let skip_or_fail = |path: &Path, error: DbError| match mode {
+            ImportMode::Skip => Ok(ImportOutcome::Skipped(error.to_string())),
+            ImportMode::Fail => Err(match error {
+                e @ DbError::DuplicateName(_) => e,
+                e => DbError::InvalidArgument(format!("{}: {e}", path.display())),
+            }),
+        };
+
+        // Validate everything before taking the write lock
+        let mut checked = Vec::with_capacity(definitions.len());
+        for (path, definition) in definitions {
+            let definition =
+                definition
+                    .map_err(DbError::InvalidArgument)
+                    .and_then(|mut definition| {
+                        definition.name = self.policy.normalize(&definition.name).into_owned();
+                        self.policy.validate(&definition.name)?;
+                        Ok(definition)
+                    });
+            let definition = match definition {
+                Ok(definition) => Ok(definition),
+                Err(e) => Err(skip_or_fail(&path, e)?),
+            };
+            checked.push((path, definition));
+        }
+
+        let mut tx = self.db.begin_immediate().await?;
+        let mut count = tx.applications().count().await?;
+        let mut names = HashSet::new();
+        let mut results = Vec::with_capacity(checked.len());
+        for (path, definition) in checked {
+            let outcome = match definition {
+                Err(skipped) => skipped,
+                Ok(definition) => {
+                    let taken = !names.insert(definition.name.clone())
+                        || tx
+                            .applications()
+                            .with_owner(&self.owner)
+                            .exists_by_name(&definition.name)
+                            .await?;
+                    if taken {
+                        skip_or_fail(&path, DbError::DuplicateName(definition.name))?
+                    } else {
+                        if let Some(max) = self.max_applications {
+                            if u64::try_from(count).unwrap_or(0) >= max {
+                                return Err(DbError::LimitExceeded(max));
+                            }
+                        }
+                        let app = tx
+                            .applications()
+                            .with_owner(&self.owner)
+                            .with_policy(&self.policy)
+                            .create_with_description(
+                                &definition.name,
+                                definition.description.as_deref(),
+                            )
+                            .await?;
+                        count += 1;
+                        ImportOutcome::Imported(app)
+                    }
+                }
+            };
+            results.push(FileImport { path, outcome });
+        }
+        tx.commit().await?;
+
+        for result in &results {
+            if let ImportOutcome::Imported(app) = &result.outcome {
+                self.uncache_name(&app.name);
+                self.publish(AppEvent::Created(app.clone()));
+            }
+        }
+        Ok(results)
     }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
-    use tempfile::tempdir;
+    use tempfile::{tempdir, TempDir};
 
-    async fn setup_test_service() -> Result<ApplicationService, Box<dyn std::error::Error>> {
+    async fn setup_test_service(
+    ) -> Result<(ApplicationService, TempDir), Box<dyn std::error::Error>> {
         let temp_dir = tempdir()?;
         let db_path = temp_dir.path().join("test.db");
         let db = Database::new(&db_path).await?;
         db.migrate().await?;
-        Ok(ApplicationService::new(db))
+        Ok((ApplicationService::new(db), temp_dir))
     }
 
     /// Test that `ApplicationService` correctly integrates with Database layer
     /// This verifies the service layer properly delegates to DB and returns results
     #[tokio::test]
     async fn test_service_integration() -> Result<(), Box<dyn std::error::Error>> {
-        let service = setup_test_service().await?;
+        let (service, _temp_dir) = setup_test_service().await?;
 
         // Test create
         let app = service.create_app("integration-test".to_string()).await?;
@@ -107,7 +1211,7 @@ mod tests {
     /// Test that errors from DB layer are properly propagated
     #[tokio::test]
     async fn test_service_error_propagation() -> Result<(), Box<dyn std::error::Error>> {
-        let service = setup_test_service().await?;
+        let (service, _temp_dir) = setup_test_service().await?;
 
         // Test validation error propagation
         let result = service.create_app("invalid name".to_string()).await;
@@ -120,4 +1224,827 @@ mod tests {
 
         Ok(())
     }
+
+    /// Test that a custom name policy is applied on create
+    #[tokio::test]
+    async fn test_service_custom_name_policy() -> Result<(), Box<dyn std::error::Error>> {
+        let (service, _temp_dir) = setup_test_service().await?;
+        let service = service.with_name_policy(NamePolicy {
+            allow_chars: "-_.".to_string(),
+            reserved_names: vec!["admin".to_string()],
+            ..NamePolicy::default()
+        });
+
+        let app = service.create_app("api.v2".to_string()).await?;
+        assert_eq!(app.name, "api.v2");
+
+        let result = service.create_app("admin".to_string()).await;
+        assert!(matches!(result, Err(DbError::InvalidName(_))));
+
+        Ok(())
+    }
+
+    /// Test that autonamed apps are valid and unique
+    #[tokio::test]
+    async fn test_create_app_autoname() -> Result<(), Box<dyn std::error::Error>> {
+        let (service, _temp_dir) = setup_test_service().await?;
+
+        for _ in 0..50 {
+            let app = service.create_app_autoname().await?;
+            assert!(ottershipper_db::validate_app_name(&app.name).is_ok());
+        }
+
+        let apps = service.list_apps().await?;
+        let names: std::collections::HashSet<_> = apps.iter().map(|a| &a.name).collect();
+        assert_eq!(names.len(), 50);
+
+        Ok(())
+    }
+
+    /// Test that a custom generator is used and collisions are retried
+    #[tokio::test]
+    async fn test_create_app_autoname_custom_generator() -> Result<(), Box<dyn std::error::Error>> {
+        struct Fixed;
+        impl NameGenerator for Fixed {
+            fn generate(&self) -> String {
+                "fixed-name".to_string()
+            }
+        }
+
+        let (service, _temp_dir) = setup_test_service().await?;
+        let service = service.with_name_generator(Fixed);
+
+        let app = service.create_app_autoname().await?;
+        assert_eq!(app.name, "fixed-name");
+
+        // Every retry collides, so the duplicate error surfaces
+        let result = service.create_app_autoname().await;
+        assert!(matches!(result, Err(DbError::DuplicateName(_))));
+
+        Ok(())
+    }
+
+    /// Test that repeating an idempotency key returns the original app
+    #[tokio::test]
+    async fn test_create_app_idempotent() -> Result<(), Box<dyn std::error::Error>> {
+        let (service, _temp_dir) = setup_test_service().await?;
+
+        let (first, created) = service
+            .create_app_idempotent(Some("idem-app".to_string()), "key-1")
+            .await?;
+        assert!(created);
+
+        // Same key returns the same app instead of DuplicateName
+        let (again, created) = service
+            .create_app_idempotent(Some("idem-app".to_string()), "key-1")
+            .await?;
+        assert!(!created);
+        assert_eq!(again.id, first.id);
+
+        // A different key creates a distinct app
+        let (other, created) = service
+            .create_app_idempotent(Some("other-app".to_string()), "key-2")
+            .await?;
+        assert!(created);
+        assert_ne!(other.id, first.id);
+
+        // A different key with a taken name is still a duplicate
+        let result = service
+            .create_app_idempotent(Some("idem-app".to_string()), "key-3")
+            .await;
+        assert!(matches!(result, Err(DbError::DuplicateName(_))));
+
+        assert_eq!(service.list_apps().await?.len(), 2);
+
+        Ok(())
+    }
+
+    /// Test that keys are forgotten once the window has passed
+    #[tokio::test]
+    async fn test_idempotency_key_expires() -> Result<(), Box<dyn std::error::Error>> {
+        let (service, _temp_dir) = setup_test_service().await?;
+        let service = service.with_idempotency_window(Duration::ZERO);
+
+        let (first, _) = service
+            .create_app_idempotent(Some("first".to_string()), "key")
+            .await?;
+        tokio::time::sleep(Duration::from_millis(5)).await;
+
+        let (second, created) = service
+            .create_app_idempotent(Some("second".to_string()), "key")
+            .await?;
+        assert!(created);
+        assert_ne!(second.id, first.id);
+
+        Ok(())
+    }
+
+    /// Test that preflight reports validity and availability without inserting
+    #[tokio::test]
+    async fn test_preflight_create() -> Result<(), Box<dyn std::error::Error>> {
+        let (service, _temp_dir) = setup_test_service().await?;
+        service.create_app("taken".to_string()).await?;
+
+        let check = service.preflight_create("available").await?;
+        assert_eq!(
+            check,
+            CreatePreflight {
+                valid: true,
+                would_create: true,
+                reason: None,
+            }
+        );
+
+        let check = service.preflight_create("taken").await?;
+        assert!(check.valid);
+        assert!(!check.would_create);
+        assert!(check.reason.unwrap().contains("already exists"));
+
+        let check = service.preflight_create("bad name").await?;
+        assert!(!check.valid);
+        assert!(!check.would_create);
+        assert!(check.reason.is_some());
+
+        assert_eq!(service.list_apps().await?.len(), 1);
+
+        Ok(())
+    }
+
+    /// Test that the application cap is enforced exactly
+    #[tokio::test]
+    async fn test_max_applications() -> Result<(), Box<dyn std::error::Error>> {
+        let (service, _temp_dir) = setup_test_service().await?;
+        let service = service.with_max_applications(Some(2));
+
+        service.create_app("one".to_string()).await?;
+        let two = service.create_app("two".to_string()).await?;
+
+        // At the cap, one more is rejected, and a dry run says so
+        let check = service.preflight_create("three").await?;
+        assert!(check.valid);
+        assert!(!check.would_create);
+        assert_eq!(check.reason, Some(DbError::LimitExceeded(2).to_string()));
+        let result = service.create_app("three".to_string()).await;
+        assert!(matches!(result, Err(DbError::LimitExceeded(2))));
+        assert_eq!(service.list_apps().await?.len(), 2);
+
+        // Freeing a slot allows another create
+        service.delete_app(&two.id).await?;
+        service.create_app("three".to_string()).await?;
+
+        Ok(())
+    }
+
+    /// Test that concurrent creates never exceed the cap
+    #[tokio::test]
+    async fn test_max_applications_concurrent() -> Result<(), Box<dyn std::error::Error>> {
+        let (service, _temp_dir) = setup_test_service().await?;
+        let service = service.with_max_applications(Some(5));
+
+        let handles: Vec<_> = (0..10)
+            .map(|i| {
+                let service = service.clone();
+                tokio::spawn(async move { service.create_app(format!("app-{i}")).await })
+            })
+            .collect();
+
+        let mut created = 0;
+        for handle in handles {
+            match handle.await? {
+                Ok(_) => created += 1,
+                Err(DbError::LimitExceeded(5)) => {}
+                Err(e) => return Err(e.into()),
+            }
+        }
+
+        assert_eq!(created, 5);
+        assert_eq!(service.list_apps().await?.len(), 5);
+
+        Ok(())
+    }
+
+    /// Test that apply updates existing applications even at the cap
+    #[tokio::test]
+    async fn test_apply_app_respects_max_applications() -> Result<(), Box<dyn std::error::Error>> {
+        let (service, _temp_dir) = setup_test_service().await?;
+        let service = service.with_max_applications(Some(1));
+
+        let (app, created) = service.apply_app("only", Some("first")).await?;
+        assert!(created);
+
+        // Updating doesn't count against the cap
+        let (updated, created) = service.apply_app("only", Some("second")).await?;
+        assert!(!created);
+        assert_eq!(updated.id, app.id);
+        assert_eq!(updated.description.as_deref(), Some("second"));
+
+        // Creating another does
+        let result = service.apply_app("another", None).await;
+        assert!(matches!(result, Err(DbError::LimitExceeded(1))));
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_subscribe_receives_events() -> Result<(), Box<dyn std::error::Error>> {
+        let (service, _temp_dir) = setup_test_service().await?;
+        let mut events = service.subscribe();
+
+        let app = service.create_app("observed".to_string()).await?;
+        match events.try_recv()? {
+            AppEvent::Created(created) => assert_eq!(created.id, app.id),
+            other => panic!("expected Created, got {other:?}"),
+        }
+
+        // Failed mutations publish nothing
+        assert!(service.create_app("observed".to_string()).await.is_err());
+        assert!(!service.delete_app("missing").await?);
+        assert!(events.try_recv().is_err());
+
+        service.delete_app_by_name("observed").await?;
+        assert!(matches!(events.try_recv()?, AppEvent::Deleted(id) if id == app.id));
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_create_normalizes_name() -> Result<(), Box<dyn std::error::Error>> {
+        let (service, _temp_dir) = setup_test_service().await?;
+
+        let app = service.create_app("  spaced-out\n".to_string()).await?;
+        assert_eq!(app.name, "spaced-out");
+
+        let check = service.preflight_create(" spaced-out ").await?;
+        assert!(!check.would_create);
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_create_app_unique_suffixes() -> Result<(), Box<dyn std::error::Error>> {
+        let (service, _temp_dir) = setup_test_service().await?;
+
+        let mut names = Vec::new();
+        for _ in 0..3 {
+            names.push(service.create_app_unique("api".to_string()).await?.name);
+        }
+        assert_eq!(names, ["api", "api-2", "api-3"]);
+
+        // Invalid bases fail without retrying
+        let result = service.create_app_unique("bad name".to_string()).await;
+        assert!(matches!(result, Err(DbError::InvalidName(_))));
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_create_app_unique_respects_max_len() -> Result<(), Box<dyn std::error::Error>> {
+        let (service, _temp_dir) = setup_test_service().await?;
+        let service = service.with_name_policy(NamePolicy {
+            max_len: 8,
+            ..NamePolicy::default()
+        });
+
+        assert_eq!(
+            service
+                .create_app_unique("abcdefgh".to_string())
+                .await?
+                .name,
+            "abcdefgh"
+        );
+        assert_eq!(
+            service
+                .create_app_unique("abcdefgh".to_string())
+                .await?
+                .name,
+            "abcdef-2"
+        );
+
+        // A separator left at the cut is dropped rather than doubled
+        assert_eq!(
+            service
+                .create_app_unique("abcde-gh".to_string())
+                .await?
+                .name,
+            "abcde-gh"
+        );
+        assert_eq!(
+            service
+                .create_app_unique("abcde-gh".to_string())
+                .await?
+                .name,
+            "abcde-2"
+        );
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_clone_app() -> Result<(), Box<dyn std::error::Error>> {
+        let (service, _temp_dir) = setup_test_service().await?;
+        let (source, _) = service.apply_app("template", Some("base image")).await?;
+
+        let copy = service
+            .clone_app(&source.id, "from-template".to_string())
+            .await?;
+        assert_eq!(copy.name, "from-template");
+        assert_eq!(copy.description.as_deref(), Some("base image"));
+        assert_ne!(copy.id, source.id);
+
+        // Missing source
+        let result = service.clone_app("missing", "orphan".to_string()).await;
+        assert!(matches!(result, Err(DbError::NotFound(id)) if id == "missing"));
+
+        // Taken and invalid names
+        let result = service
+            .clone_app(&source.id, "from-template".to_string())
+            .await;
+        assert!(matches!(result, Err(DbError::DuplicateName(_))));
+        let result = service.clone_app(&source.id, "bad name".to_string()).await;
+        assert!(matches!(result, Err(DbError::InvalidName(_))));
+        assert_eq!(service.count_apps().await?, 2);
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_app_metadata() -> Result<(), Box<dyn std::error::Error>> {
+        let (service, _temp_dir) = setup_test_service().await?;
+        let app = service.create_app("described".to_string()).await?;
+
+        service.set_app_meta(&app.id, "owner", "team-a").await?;
+        service.set_app_meta(&app.id, "owner", "team-b").await?;
+        assert_eq!(
+            service.get_app_meta(&app.id, "owner").await?.as_deref(),
+            Some("team-b")
+        );
+        assert_eq!(service.list_app_meta(&app.id).await?.len(), 1);
+
+        assert!(service.delete_app_meta(&app.id, "owner").await?);
+        assert!(service.list_app_meta(&app.id).await?.is_empty());
+
+        // Unknown applications are reported rather than looking empty
+        let result = service.list_app_meta("missing").await;
+        assert!(matches!(result, Err(DbError::NotFound(_))));
+        let result = service.get_app_meta("missing", "owner").await;
+        assert!(matches!(result, Err(DbError::NotFound(_))));
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_recent_activity() -> Result<(), Box<dyn std::error::Error>> {
+        let (service, _temp_dir) = setup_test_service().await?;
+
+        let activity = service.recent_activity().await?;
+        assert!(activity.last_30d.is_empty());
+
+        service.create_app("fresh".to_string()).await?;
+
+        // Just-created applications fall in every window
+        let activity = service.recent_activity().await?;
+        for window in [&activity.last_24h, &activity.last_7d, &activity.last_30d] {
+            assert_eq!(window.len(), 1);
+            assert_eq!(window[0].name, "fresh");
+        }
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_archive_app() -> Result<(), Box<dyn std::error::Error>> {
+        let (service, _temp_dir) = setup_test_service().await?;
+        let app = service.create_app("shelved".to_string()).await?;
+        service.create_app("in-use".to_string()).await?;
+
+        service.archive_app(&app.id).await?;
+        let page = service.list_apps_page(None, 0, false).await?;
+        assert_eq!(page.total, 1);
+        assert_eq!(page.applications[0].name, "in-use");
+        assert_eq!(service.list_apps_page(None, 0, true).await?.total, 2);
+        let page = service.list_apps_after(None, 10, false).await?;
+        assert_eq!(page.applications.len(), 1);
+
+        let restored = service.unarchive_app(&app.id).await?;
+        assert_eq!(restored.status, AppStatus::Active);
+        assert_eq!(service.list_apps_page(None, 0, false).await?.total, 2);
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_rename_prefix() -> Result<(), Box<dyn std::error::Error>> {
+        let (service, _temp_dir) = setup_test_service().await?;
+        let api = service.create_app("svc-api".to_string()).await?;
+        service.create_app("svc-web".to_string()).await?;
+        service.create_app("billing".to_string()).await?;
+        let mut events = service.subscribe();
+
+        let renamed = service.rename_prefix("svc-", "team-a-").await?;
+        assert_eq!(
+            renamed,
+            [
+                ("svc-api".to_string(), "team-a-api".to_string()),
+                ("svc-web".to_string(), "team-a-web".to_string()),
+            ]
+        );
+        assert_eq!(service.get_app_or_err(&api.id).await?.name, "team-a-api");
+        assert!(service.get_app_by_name("billing").await?.is_some());
+        assert!(matches!(
+            events.try_recv()?,
+            AppEvent::Renamed { id, old, new }
+                if id == api.id && old == "svc-api" && new == "team-a-api"
+        ));
+
+        // Nothing matches any more
+        assert!(service.rename_prefix("svc-", "x-").await?.is_empty());
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_rename_prefix_rolls_back_on_collision() -> Result<(), Box<dyn std::error::Error>>
+    {
+        let (service, _temp_dir) = setup_test_service().await?;
+        service.create_app("svc-api".to_string()).await?;
+        service.create_app("svc-web".to_string()).await?;
+        service.create_app("team-a-web".to_string()).await?;
+
+        // svc-api would rename fine, but svc-web collides, so neither changes
+        let result = service.rename_prefix("svc-", "team-a-").await;
+        assert!(matches!(result, Err(DbError::DuplicateName(name)) if name == "team-a-web"));
+        assert!(service.get_app_by_name("svc-api").await?.is_some());
+        assert!(service.get_app_by_name("svc-web").await?.is_some());
+        assert!(service.get_app_by_name("team-a-api").await?.is_none());
+
+        let result = service.rename_prefix("svc-", "-").await;
+        assert!(matches!(result, Err(DbError::InvalidName(_))));
+        let result = service.rename_prefix("", "x-").await;
+        assert!(matches!(result, Err(DbError::InvalidArgument(_))));
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_rename_prefix_overlapping() -> Result<(), Box<dyn std::error::Error>> {
+        let (service, _temp_dir) = setup_test_service().await?;
+        for name in ["a-b", "a-z-b"] {
+            service.create_app(name.to_string()).await?;
+        }
+
+        // a-b takes the name a-z-b gives up
+        let renamed = service.rename_prefix("a-", "a-z-").await?;
+        assert_eq!(
+            renamed,
+            [
+                ("a-b".to_string(), "a-z-b".to_string()),
+                ("a-z-b".to_string(), "a-z-z-b".to_string()),
+            ]
+        );
+
+        // And back again, shrinking
+        let renamed = service.rename_prefix("a-z-", "a-").await?;
+        assert_eq!(
+            renamed,
+            [
+                ("a-z-b".to_string(), "a-b".to_string()),
+                ("a-z-z-b".to_string(), "a-z-b".to_string()),
+            ]
+        );
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_rename_prefix_normalizes_old_prefix() -> Result<(), Box<dyn std::error::Error>> {
+        use ottershipper_db::NameCasing;
+
+        let (service, _temp_dir) = setup_test_service().await?;
+        let service = service.with_name_policy(NamePolicy {
+            casing: NameCasing::Lower,
+            ..NamePolicy::default()
+        });
+        service.create_app("SVC-api".to_string()).await?;
+
+        let renamed = service.rename_prefix("SVC-", "Team-").await?;
+        assert_eq!(renamed, [("svc-api".to_string(), "team-api".to_string())]);
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_rename_app() -> Result<(), Box<dyn std::error::Error>> {
+        let (service, _temp_dir) = setup_test_service().await?;
+        let app = service.create_app("old-name".to_string()).await?;
+        service.create_app("taken".to_string()).await?;
+        let mut events = service.subscribe();
+
+        let renamed = service.rename_app(&app.id, "  new-name ").await?;
+        assert_eq!(renamed.id, app.id);
+        assert_eq!(renamed.name, "new-name");
+        assert!(service.get_app_by_name("old-name").await?.is_none());
+        assert!(matches!(
+            events.try_recv()?,
+            AppEvent::Renamed { old, new, .. } if old == "old-name" && new == "new-name"
+        ));
+
+        let result = service.rename_app(&app.id, "taken").await;
+        assert!(matches!(result, Err(DbError::DuplicateName(_))));
+        let result = service.rename_app("no-such-id", "fresh").await;
+        assert!(matches!(result, Err(DbError::NotFound(_))));
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_describe_app() -> Result<(), Box<dyn std::error::Error>> {
+        let (service, _temp_dir) = setup_test_service().await?;
+        let app = service.create_app("described".to_string()).await?;
+        service.set_app_meta(&app.id, "team", "payments").await?;
+        service.rename_app(&app.id, "described-2").await?;
+
+        let details = service.describe_app(&app.id).await?;
+        assert_eq!(details.application.name, "described-2");
+        assert_eq!(details.metadata.len(), 1);
+        assert_eq!(details.metadata[0].key, "team");
+        // Create, metadata update, rename
+        assert_eq!(details.audit_trail.len(), 3);
+
+        let result = service.describe_app("no-such-id").await;
+        assert!(matches!(result, Err(DbError::NotFound(_))));
+
+        Ok(())
+    }
+
+    /// Service with a name cache whose entries expire after a minute of `clock` time
+    async fn setup_cached_service(
+        clock: Arc<ottershipper_db::MockClock>,
+    ) -> Result<(ApplicationService, TempDir), Box<dyn std::error::Error>> {
+        let temp_dir = tempdir()?;
+        let db = Database::new(temp_dir.path().join("test.db"))
+            .await?
+            .with_clock(clock);
+        db.migrate().await?;
+        let service = ApplicationService::new(db).with_name_cache(16, Duration::from_mins(1));
+        Ok((service, temp_dir))
+    }
+
+    #[tokio::test]
+    async fn test_name_cache_hit() -> Result<(), Box<dyn std::error::Error>> {
+        let clock = Arc::new(ottershipper_db::MockClock::new(1_000));
+        let (service, _temp_dir) = setup_cached_service(clock.clone()).await?;
+        let app = service.create_app("cached-app".to_string()).await?;
+        assert_eq!(
+            service
+                .get_app_by_name("cached-app")
+                .await?
+                .map(|app| app.id),
+            Some(app.id.clone())
+        );
+
+        // Renaming behind the service's back goes unnoticed until the entry expires
+        service
+            .database()
+            .applications()
+            .rename(&app.id, "moved-app")
+            .await?;
+        assert_eq!(
+            service
+                .get_app_by_name("cached-app")
+                .await?
+                .map(|app| app.id),
+            Some(app.id.clone())
+        );
+        assert!(service.get_app_by_name_or_err("cached-app").await.is_ok());
+
+        clock.advance(Duration::from_mins(1));
+        assert!(service.get_app_by_name("cached-app").await?.is_none());
+        assert!(service.get_app_by_name("moved-app").await?.is_some());
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_name_cache_cleared_on_reconnect() -> Result<(), Box<dyn std::error::Error>> {
+        let clock = Arc::new(ottershipper_db::MockClock::new(1_000));
+        let (service, _temp_dir) = setup_cached_service(clock).await?;
+        let app = service.create_app("restored-away".to_string()).await?;
+        assert!(service.get_app_by_name("restored-away").await?.is_some());
+
+        // Stands in for a restore that no longer has the application
+        service.database().applications().delete(&app.id).await?;
+        assert!(service.get_app_by_name("restored-away").await?.is_some());
+
+        service.reconnect().await?;
+        assert!(service.get_app_by_name("restored-away").await?.is_none());
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_for_owner() -> Result<(), Box<dyn std::error::Error>> {
+        let clock = Arc::new(ottershipper_db::MockClock::new(1_000));
+        let (service, _temp_dir) = setup_cached_service(clock).await?;
+        let team_a = service.for_owner("team-a");
+        let team_b = service.for_owner("team-b");
+        let shared = service.create_app("api".to_string()).await?;
+
+        // Names are unique across owners until scoped, whoever asks
+        assert!(!team_a.preflight_create("api").await?.would_create);
+        assert!(matches!(
+            team_a.create_app("api".to_string()).await,
+            Err(DbError::DuplicateName(_))
+        ));
+
+        assert!(service.database().scope_names_by_owner().await?);
+        let owned = team_a.create_app("api".to_string()).await?;
+        assert_eq!(owned.owner.as_deref(), Some("team-a"));
+        assert!(team_b.preflight_create("api").await?.would_create);
+
+        let id_for = |service: &ApplicationService| {
+            let service = service.clone();
+            async move {
+                service
+                    .get_app_by_name("api")
+                    .await
+                    .map(|app| app.map(|app| app.id))
+            }
+        };
+        assert_eq!(id_for(&team_a).await?, Some(owned.id.clone()));
+        assert_eq!(id_for(&service).await?, Some(shared.id.clone()));
+        assert_eq!(id_for(&team_b).await?, None);
+        // Cached lookups stay separate per owner
+        assert_eq!(id_for(&team_a).await?, Some(owned.id));
+        assert_eq!(id_for(&service).await?, Some(shared.id));
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_rename_prefix_scoped_by_owner() -> Result<(), Box<dyn std::error::Error>> {
+        let clock = Arc::new(ottershipper_db::MockClock::new(1_000));
+        let (service, _temp_dir) = setup_cached_service(clock).await?;
+        assert!(service.database().scope_names_by_owner().await?);
+        let team_a = service.for_owner("team-a");
+        let team_b = service.for_owner("team-b");
+        team_a.create_app("svc-api".to_string()).await?;
+        team_b.create_app("svc-api".to_string()).await?;
+        team_b.create_app("svc-web".to_string()).await?;
+
+        let renamed = team_a.rename_prefix("svc-", "team-a-").await?;
+        assert_eq!(renamed, [("svc-api".to_string(), "team-a-api".to_string())]);
+
+        // The other owner's applications keep their names
+        assert!(team_b.get_app_by_name("svc-api").await?.is_some());
+        assert!(team_b.get_app_by_name("svc-web").await?.is_some());
+        assert!(team_a.get_app_by_name("svc-api").await?.is_none());
+        // Nobody owns an application with the prefix, so nothing is renamed
+        assert!(service.rename_prefix("svc-", "x-").await?.is_empty());
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_name_cache_invalidated_on_rename() -> Result<(), Box<dyn std::error::Error>> {
+        let clock = Arc::new(ottershipper_db::MockClock::new(1_000));
+        let (service, _temp_dir) = setup_cached_service(clock).await?;
+        let api = service.create_app("svc-api".to_string()).await?;
+        let web = service.create_app("team-web".to_string()).await?;
+        assert!(service.get_app_by_name("svc-api").await?.is_some());
+        assert!(service.get_app_by_name("team-web").await?.is_some());
+
+        // Free up the new name behind the service's back, leaving a stale entry
+        service
+            .database()
+            .applications()
+            .rename(&web.id, "other-web")
+            .await?;
+        service
+            .database()
+            .applications()
+            .rename(&api.id, "svc-web")
+            .await?;
+        assert!(service.get_app_by_name("svc-web").await?.is_some());
+        assert_eq!(service.rename_prefix("svc-", "team-").await?.len(), 1);
+
+        // Both the old and the new name are looked up afresh
+        assert!(service.get_app_by_name("svc-web").await?.is_none());
+        let renamed = service.get_app_by_name("team-web").await?;
+        assert_eq!(renamed.map(|app| app.id), Some(api.id));
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_name_cache_invalidated_on_delete() -> Result<(), Box<dyn std::error::Error>> {
+        let clock = Arc::new(ottershipper_db::MockClock::new(1_000));
+        let (service, _temp_dir) = setup_cached_service(clock).await?;
+        let app = service.create_app("doomed".to_string()).await?;
+        assert!(service.get_app_by_name("doomed").await?.is_some());
+        assert!(service.delete_app(&app.id).await?);
+        assert!(service.get_app_by_name("doomed").await?.is_none());
+
+        let app = service.create_app("doomed".to_string()).await?;
+        assert_eq!(
+            service.get_app_by_name("doomed").await?.map(|app| app.id),
+            Some(app.id.clone())
+        );
+        assert_eq!(service.delete_apps(&[app.id]).await?, 1);
+        assert!(service.get_app_by_name("doomed").await?.is_none());
+
+        // A create replaces an entry left stale by a delete elsewhere
+        let app = service.create_app("doomed".to_string()).await?;
+        service.get_app_by_name("doomed").await?;
+        service.database().applications().delete(&app.id).await?;
+        let recreated = service.create_app("doomed".to_string()).await?;
+        assert_eq!(
+            service.get_app_by_name("doomed").await?.map(|app| app.id),
+            Some(recreated.id.clone())
+        );
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_import_dir() -> Result<(), Box<dyn std::error::Error>> {
+        let (service, _temp_dir) = setup_test_service().await?;
+        let dir = tempdir()?;
+        std::fs::write(
+            dir.path().join("api.json"),
+            r#"{"name": "api", "description": "Public API"}"#,
+        )?;
+        std::fs::write(dir.path().join("web.json"), r#"{"name": " web "}"#)?;
+        std::fs::write(dir.path().join("broken.json"), r#"{"name": "bad name!"}"#)?;
+        std::fs::write(dir.path().join("notes.txt"), "not an application")?;
+
+        // Fail mode imports nothing because of the invalid file
+        let result = service.import_dir(dir.path(), ImportMode::Fail).await;
+        assert!(
+            matches!(&result, Err(DbError::InvalidArgument(msg)) if msg.contains("broken.json")),
+            "unexpected result: {result:?}"
+        );
+        assert_eq!(service.count_apps().await?, 0);
+
+        // Skip mode imports the valid files and reports the invalid one
+        let results = service.import_dir(dir.path(), ImportMode::Skip).await?;
+        let paths: Vec<_> = results
+            .iter()
+            .map(|r| r.path.file_name().unwrap())
+            .collect();
+        assert_eq!(paths, ["api.json", "broken.json", "web.json"]);
+        assert!(
+            matches!(&results[0].outcome, ImportOutcome::Imported(app) if app.description.as_deref() == Some("Public API"))
+        );
+        assert!(matches!(&results[1].outcome, ImportOutcome::Skipped(_)));
+        assert!(matches!(&results[2].outcome, ImportOutcome::Imported(app) if app.name == "web"));
+        assert_eq!(service.count_apps().await?, 2);
+
+        // Importing again skips the names that now exist, or fails on them
+        std::fs::remove_file(dir.path().join("broken.json"))?;
+        let results = service.import_dir(dir.path(), ImportMode::Skip).await?;
+        assert!(results
+            .iter()
+            .all(|r| matches!(r.outcome, ImportOutcome::Skipped(_))));
+        let result = service.import_dir(dir.path(), ImportMode::Fail).await;
+        assert!(matches!(result, Err(DbError::DuplicateName(name)) if name == "api"));
+        assert_eq!(service.count_apps().await?, 2);
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_name_casing() -> Result<(), Box<dyn std::error::Error>> {
+        use ottershipper_db::NameCasing;
+
+        for (casing, stored, renamed) in [
+            (NameCasing::Preserve, "Billing-API", "Billing-API-v2"),
+            (NameCasing::Lower, "billing-api", "billing-api-v2"),
+            (NameCasing::Upper, "BILLING-API", "BILLING-API-V2"),
+        ] {
+            let (service, _temp_dir) = setup_test_service().await?;
+            let service = service.with_name_policy(NamePolicy {
+                casing,
+                ..NamePolicy::default()
+            });
+
+            let app = service.create_app(" Billing-API ".to_string()).await?;
+            assert_eq!(app.name, stored, "{casing:?}");
+            let app = service.rename_app(&app.id, "Billing-API-v2").await?;
+            assert_eq!(app.name, renamed, "{casing:?}");
+
+            // Lookups are normalized the same way, so differently-cased names
+            // only collide when the casing is enforced
+            let found = service.get_app_by_name("billing-api-V2").await?;
+            let duplicate = service.create_app("billing-api-V2".to_string()).await;
+            if casing == NameCasing::Preserve {
+                assert!(found.is_none());
+                assert!(duplicate.is_ok());
+            } else {
+                assert_eq!(found.map(|app| app.id), Some(app.id.clone()));
+                assert!(matches!(duplicate, Err(DbError::DuplicateName(_))));
+            }
+        }
+
+        Ok(())
+    }
 }
@@ -0,0 +1,72 @@
+use ottershipper_db::{Application, DbError};
+use serde::Deserialize;
+use std::path::{Path, PathBuf};
+
+/// What an import does with a definition it cannot apply
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ImportMode {
+    /// Report invalid definitions and names already taken as skipped, and
+    /// import the rest
+    #[default]
+    Skip,
+    /// Import nothing if any definition is invalid or its name is taken
+    Fail,
+}
+
+/// An application as described in an import file
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct AppDefinition {
+    pub name: String,
+    #[serde(default)]
+    pub description: Option<String>,
+}
+
+/// What happened to one definition in an import
+#[derive(Debug, Clone)]
+pub enum ImportOutcome {
+    /// The application was created
+    Imported(Application),
+    /// The definition was left out, and why
+    Skipped(String),
+}
+
+/// Result of importing one file
+#[derive(Debug, Clone)]
+pub struct FileImport {
+    pub path: PathBuf,
+    pub outcome: ImportOutcome,
+}
+
+/// A definition and the file it came from, or why it couldn't be read
+pub(crate) type ParsedDefinition = (PathBuf, Result<AppDefinition, String>);
+
+/// Read every `*.json` file in `dir` as an [`AppDefinition`], in path order
+///
+/// Only failing to list the directory is an error; a file that can't be read
+/// or parsed is returned with the reason.
+pub(crate) async fn read_definitions(dir: &Path) -> Result<Vec<ParsedDefinition>, DbError> {
+    let unreadable = |e: std::io::Error| {
+        DbError::InvalidArgument(format!("cannot read directory {}: {e}", dir.display()))
+    };
+
+    let mut paths = Vec::new();
+    let mut entries = tokio::fs::read_dir(dir).await.map_err(unreadable)?;
+    while let Some(entry) = entries.next_entry().await.map_err(unreadable)? {
+        let path = entry.path();
+        if path.extension().is_some_and(|ext| ext == "json") && path.is_file() {
+            paths.push(path);
+        }
+    }
+    paths.sort();
+
+    let mut definitions = Vec::with_capacity(paths.len());
+    for path in paths {
+        let definition = match tokio::fs::read_to_string(&path).await {
+            Ok(contents) => serde_json::from_str(&contents).map_err(|e| e.to_string()),
+            Err(e) => Err(e.to_string()),
+        };
+        definitions.push((path, definition));
+    }
+    Ok(definitions)
+}
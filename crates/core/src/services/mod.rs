@@ -1,3 +1,9 @@
 mod application;
+mod import;
+mod name_cache;
 
-pub use application::ApplicationService;
+pub use application::{
+    ApplicationCursorPage, ApplicationDetails, ApplicationPage, ApplicationService,
+    CreatePreflight, RecentActivity,
+};
+pub use import::{AppDefinition, FileImport, ImportMode, ImportOutcome};
@@ -0,0 +1,165 @@
+use ottershipper_db::Application;
+use std::collections::HashMap;
+use std::sync::Mutex;
+use std::time::Duration;
+
+/// Bounded, expiring cache of applications keyed by owner and name
+///
+/// The owner is the one the lookup was made for, so services acting for
+/// different owners never see each other's entries once names are scoped by
+/// owner.
+///
+/// Least recently used entries are evicted once `capacity` is reached, and
+/// entries older than `ttl` are treated as misses so changes made outside the
+/// service are eventually seen. Times are epoch millis from the database clock.
+pub(crate) struct NameCache {
+    capacity: usize,
+    ttl_ms: i64,
+    inner: Mutex<Inner>,
+}
+
+#[derive(Default)]
+struct Inner {
+    entries: HashMap<(String, String), Entry>,
+    /// Incremented on every access, ordering entries by recency
+    tick: u64,
+}
+
+struct Entry {
+    app: Application,
+    inserted_at: i64,
+    last_used: u64,
+}
+
+impl NameCache {
+    pub(crate) fn new(capacity: usize, ttl: Duration) -> Self {
+        Self {
+            capacity: capacity.max(1),
+            ttl_ms: i64::try_from(ttl.as_millis()).unwrap_or(i64::MAX),
+            inner: Mutex::new(Inner::default()),
+        }
+    }
+
+    /// Cached application called `name` for `owner`, unless missing or expired at `now`
+    pub(crate) fn get(&self, owner: &str, name: &str, now: i64) -> Option<Application> {
+        let mut inner = self.lock();
+        inner.tick += 1;
+        let tick = inner.tick;
+
+        let key = (owner.to_string(), name.to_string());
+        let entry = inner.entries.get_mut(&key)?;
+        if now.saturating_sub(entry.inserted_at) >= self.ttl_ms {
+            inner.entries.remove(&key);
+            return None;
+        }
+        entry.last_used = tick;
+        Some(entry.app.clone())
+    }
+
+    /// Cache `app` under `owner` and its name, evicting the least recently used
+    /// entry if full
+    pub(crate) fn insert(&self, owner: &str, app: Application, now: i64) {
+        let mut inner = self.lock();
+        inner.tick += 1;
+        let tick = inner.tick;
+
+        let key = (owner.to_string(), app.name.clone());
+        if !inner.entries.contains_key(&key) && inner.entries.len() >= self.capacity {
+            let oldest = inner
+                .entries
+                .iter()
+                .min_by_key(|(_, entry)| entry.last_used)
+                .map(|(key, _)| key.clone());
+            if let Some(oldest) = oldest {
+                inner.entries.remove(&oldest);
+            }
+        }
+        inner.entries.insert(
+            key,
+            Entry {
+                app,
+                inserted_at: now,
+                last_used: tick,
+            },
+        );
+    }
+
+    /// Forget the entries for `name`, whichever owner they were looked up for
+    pub(crate) fn remove_name(&self, name: &str) {
+        self.lock().entries.retain(|(_, cached), _| cached != name);
+    }
+
+    /// Forget the entry for the application with this ID, whatever its name
+    pub(crate) fn remove_id(&self, id: &str) {
+        self.lock().entries.retain(|_, entry| entry.app.id != id);
+    }
+
+    /// Forget every entry
+    pub(crate) fn clear(&self) {
+        self.lock().entries.clear();
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, Inner> {
+        // The cache holds no invariants a panicking holder could break
+        self.inner
+            .lock()
+            .unwrap_or_else(std::sync::PoisonError::into_inner)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ottershipper_db::AppStatus;
+
+    fn app(id: &str, name: &str) -> Application {
+        Application {
+            id: id.to_string(),
+            name: name.to_string(),
+            created_at: 0,
+            description: None,
+            status: AppStatus::Active,
+            updated_at: 0,
+            owner: None,
+        }
+    }
+
+    #[test]
+    fn test_evicts_least_recently_used_and_expired() {
+        let cache = NameCache::new(2, Duration::from_secs(10));
+        cache.insert("", app("1", "one"), 0);
+        cache.insert("", app("2", "two"), 0);
+        // Touch "one" so "two" is the least recently used
+        assert!(cache.get("", "one", 0).is_some());
+        cache.insert("", app("3", "three"), 0);
+
+        assert!(cache.get("", "one", 0).is_some());
+        assert!(cache.get("", "two", 0).is_none());
+        assert!(cache.get("", "three", 9_999).is_some());
+        assert!(cache.get("", "three", 10_000).is_none());
+
+        cache.remove_id("1");
+        assert!(cache.get("", "one", 0).is_none());
+    }
+
+    #[test]
+    fn test_keyed_by_owner() {
+        let cache = NameCache::new(4, Duration::from_secs(10));
+        cache.insert("team-a", app("1", "api"), 0);
+        cache.insert("team-b", app("2", "api"), 0);
+
+        assert_eq!(
+            cache.get("team-a", "api", 0).map(|app| app.id),
+            Some("1".to_string())
+        );
+        assert_eq!(
+            cache.get("team-b", "api", 0).map(|app| app.id),
+            Some("2".to_string())
+        );
+        assert!(cache.get("", "api", 0).is_none());
+
+        cache.remove_name("api");
+        assert!(cache.get("team-a", "api", 0).is_none());
+        assert!(cache.get("team-b", "api", 0).is_none());
+    }
+}
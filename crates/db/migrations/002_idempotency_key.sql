@@ -0,0 +1,5 @@
+-- Idempotency key supplied by clients to dedupe retried creates
+ALTER TABLE applications ADD COLUMN idempotency_key TEXT;
+
+-- Each key maps to at most one application (NULLs are not considered equal)
+CREATE UNIQUE INDEX IF NOT EXISTS idx_applications_idempotency_key ON applications(idempotency_key);
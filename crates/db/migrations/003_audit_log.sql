@@ -0,0 +1,23 @@
+-- Append-only trail of mutating operations on applications
+CREATE TABLE IF NOT EXISTS audit_log (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    action TEXT NOT NULL,
+    app_id TEXT NOT NULL,
+    app_name TEXT NOT NULL,
+    actor TEXT,
+    at INTEGER NOT NULL
+);
+
+-- Index for per-application trails
+CREATE INDEX IF NOT EXISTS idx_audit_log_app_id ON audit_log(app_id, at);
+
+-- Audit rows can be appended but never changed
+CREATE TRIGGER IF NOT EXISTS audit_log_no_update BEFORE UPDATE ON audit_log
+BEGIN
+    SELECT RAISE(ABORT, 'audit_log is append-only');
+END;
+
+CREATE TRIGGER IF NOT EXISTS audit_log_no_delete BEFORE DELETE ON audit_log
+BEGIN
+    SELECT RAISE(ABORT, 'audit_log is append-only');
+END;
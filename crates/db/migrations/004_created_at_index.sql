@@ -0,0 +1,4 @@
+-- Supports the default listing order (created_at DESC, name ASC) used by list,
+-- list_page, and recent, so those queries walk the index instead of sorting
+-- the whole table. Also serves created_at range filters.
+CREATE INDEX IF NOT EXISTS idx_applications_created_at ON applications(created_at DESC, name ASC);
@@ -0,0 +1,2 @@
+-- Free-form description, set declaratively via upsert
+ALTER TABLE applications ADD COLUMN description TEXT;
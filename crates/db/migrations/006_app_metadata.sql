@@ -0,0 +1,7 @@
+-- Arbitrary key-value metadata attached to applications (owner, repo URL, ...)
+CREATE TABLE IF NOT EXISTS app_metadata (
+    app_id TEXT NOT NULL REFERENCES applications(id) ON DELETE CASCADE,
+    key TEXT NOT NULL,
+    value TEXT NOT NULL,
+    PRIMARY KEY (app_id, key)
+);
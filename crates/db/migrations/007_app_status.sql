@@ -0,0 +1,3 @@
+-- Lifecycle status; archived applications are hidden from default listings
+ALTER TABLE applications ADD COLUMN status TEXT NOT NULL DEFAULT 'active'
+    CHECK (status IN ('active', 'archived'));
@@ -0,0 +1,3 @@
+-- Last change to the application row, or the last touch; starts at created_at
+ALTER TABLE applications ADD COLUMN updated_at INTEGER NOT NULL DEFAULT 0;
+UPDATE applications SET updated_at = created_at;
@@ -0,0 +1,3 @@
+-- Serves reverse lookups by metadata (find_by_meta), e.g. the application
+-- whose "repo" is a given URL, without scanning every metadata row.
+CREATE INDEX IF NOT EXISTS idx_app_metadata_key_value ON app_metadata(key, value);
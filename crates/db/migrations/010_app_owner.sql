@@ -0,0 +1,3 @@
+-- Owning team or user; '' for applications nobody has claimed. Names are
+-- unique across all owners until Database::scope_names_by_owner is applied.
+ALTER TABLE applications ADD COLUMN owner TEXT NOT NULL DEFAULT '';
@@ -0,0 +1,28 @@
+-- Opt-in: an FTS5 index over application names and descriptions, kept in
+-- sync by triggers. Applied by Database::enable_full_text_search, not
+-- migrate(), since the linked SQLite must be built with FTS5.
+CREATE VIRTUAL TABLE IF NOT EXISTS applications_fts USING fts5(
+    name,
+    description,
+    content = 'applications'
+);
+
+CREATE TRIGGER IF NOT EXISTS applications_fts_insert AFTER INSERT ON applications BEGIN
+    INSERT INTO applications_fts (rowid, name, description)
+    VALUES (new.rowid, new.name, new.description);
+END;
+
+CREATE TRIGGER IF NOT EXISTS applications_fts_delete AFTER DELETE ON applications BEGIN
+    INSERT INTO applications_fts (applications_fts, rowid, name, description)
+    VALUES ('delete', old.rowid, old.name, old.description);
+END;
+
+CREATE TRIGGER IF NOT EXISTS applications_fts_update AFTER UPDATE OF name, description ON applications BEGIN
+    INSERT INTO applications_fts (applications_fts, rowid, name, description)
+    VALUES ('delete', old.rowid, old.name, old.description);
+    INSERT INTO applications_fts (rowid, name, description)
+    VALUES (new.rowid, new.name, new.description);
+END;
+
+-- Index the rows that already exist
+INSERT INTO applications_fts (applications_fts) VALUES ('rebuild');
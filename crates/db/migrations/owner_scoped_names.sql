@@ -0,0 +1,26 @@
+-- Opt-in and one-way: replaces the UNIQUE(name) column constraint with a
+-- unique (owner, name) index. SQLite can't drop a column constraint, so the
+-- table is rebuilt. Applied by Database::scope_names_by_owner, not migrate().
+CREATE TABLE applications_scoped (
+    id TEXT PRIMARY KEY,
+    name TEXT NOT NULL,
+    created_at INTEGER NOT NULL,
+    idempotency_key TEXT,
+    description TEXT,
+    status TEXT NOT NULL DEFAULT 'active' CHECK (status IN ('active', 'archived')),
+    updated_at INTEGER NOT NULL DEFAULT 0,
+    owner TEXT NOT NULL DEFAULT ''
+);
+
+INSERT INTO applications_scoped
+    (id, name, created_at, idempotency_key, description, status, updated_at, owner)
+SELECT id, name, created_at, idempotency_key, description, status, updated_at, owner
+FROM applications;
+
+DROP TABLE applications;
+ALTER TABLE applications_scoped RENAME TO applications;
+
+CREATE INDEX idx_applications_name ON applications(name);
+CREATE UNIQUE INDEX idx_applications_idempotency_key ON applications(idempotency_key);
+CREATE INDEX idx_applications_created_at ON applications(created_at DESC, name ASC);
+CREATE UNIQUE INDEX idx_applications_owner_name ON applications(owner, name);
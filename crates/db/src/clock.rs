@@ -0,0 +1,79 @@
+#[cfg(any(test, feature = "test-util"))]
+use std::sync::atomic::{AtomicI64, Ordering};
+#[cfg(any(test, feature = "test-util"))]
+use std::time::Duration;
+
+/// Source of the current time for timestamps written to the database
+///
+/// [`SystemClock`] is used unless a database is given another clock with
+/// [`crate::Database::with_clock`]; tests use `MockClock` (with the
+/// `test-util` feature) to control `created_at` and audit times.
+pub trait Clock: Send + Sync {
+    /// Current time in milliseconds since the Unix epoch
+    fn now_millis(&self) -> i64;
+}
+
+/// Wall-clock time
+#[derive(Debug, Clone, Copy, Default)]
+pub struct SystemClock;
+
+impl Clock for SystemClock {
+    fn now_millis(&self) -> i64 {
+        chrono::Utc::now().timestamp_millis()
+    }
+}
+
+/// A clock that only moves when told to
+///
+/// Only available in tests and with the `test-util` feature.
+#[cfg(any(test, feature = "test-util"))]
+#[derive(Debug, Default)]
+pub struct MockClock {
+    now: AtomicI64,
+}
+
+#[cfg(any(test, feature = "test-util"))]
+impl MockClock {
+    /// Create a clock stopped at `now_millis`
+    #[must_use]
+    pub fn new(now_millis: i64) -> Self {
+        Self {
+            now: AtomicI64::new(now_millis),
+        }
+    }
+
+    /// Jump to `now_millis`
+    pub fn set(&self, now_millis: i64) {
+        self.now.store(now_millis, Ordering::SeqCst);
+    }
+
+    /// Move forward by `by`
+    pub fn advance(&self, by: Duration) {
+        let millis = i64::try_from(by.as_millis()).unwrap_or(i64::MAX);
+        self.now.fetch_add(millis, Ordering::SeqCst);
+    }
+}
+
+#[cfg(any(test, feature = "test-util"))]
+impl Clock for MockClock {
+    fn now_millis(&self) -> i64 {
+        self.now.load(Ordering::SeqCst)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_mock_clock() {
+        let clock = MockClock::new(1_000);
+        assert_eq!(clock.now_millis(), 1_000);
+
+        clock.advance(Duration::from_millis(500));
+        assert_eq!(clock.now_millis(), 1_500);
+
+        clock.set(42);
+        assert_eq!(clock.now_millis(), 42);
+    }
+}
@@ -8,11 +8,34 @@ pub enum DbError {
     #[error("Name '{0}' already exists")]
     DuplicateName(String),
 
+    #[error("Invalid argument: {0}")]
+    InvalidArgument(String),
+
+    #[error("Application limit of {0} reached")]
+    LimitExceeded(u64),
+
     #[error("Not found: {0}")]
     NotFound(String),
 
+    #[error("Database at {0} is corrupt or not a SQLite database; restore it from a backup")]
+    Corrupt(String),
+
+    #[error("Database is read-only")]
+    ReadOnly,
+
+    #[error("Database operation timed out after {0:?}")]
+    Timeout(std::time::Duration),
+
+    #[error(
+        "Database storage is full ({0}); free disk space or raise the quota of the filesystem holding the database, then retry"
+    )]
+    StorageFull(#[source] sqlx::Error),
+
+    #[error("Database I/O error ({0}); check the disk and filesystem holding the database")]
+    Io(#[source] sqlx::Error),
+
     #[error("Database error: {0}")]
-    DatabaseError(#[from] sqlx::Error),
+    DatabaseError(#[source] sqlx::Error),
 
     #[error("Internal error: {0}")]
     Internal(String),
@@ -20,56 +43,142 @@ pub enum DbError {
 
 pub type Result<T> = std::result::Result<T, DbError>;
 
-/// Validate application name
-pub fn validate_app_name(name: &str) -> Result<()> {
-    if name.is_empty() {
-        return Err(DbError::InvalidName("name cannot be empty".to_string()));
+impl From<sqlx::Error> for DbError {
+    /// Wrap a sqlx error, singling out a full disk (`SQLITE_FULL`) and a
+    /// failing one (`SQLITE_IOERR`) so operators can alert on them
+    ///
+    /// Writes `SQLite` itself refuses (`SQLITE_READONLY`, e.g. a file without
+    /// write permission) become [`DbError::ReadOnly`], like writes to a
+    /// database opened read-only.
+    fn from(e: sqlx::Error) -> Self {
+        match sqlite_primary_code(&e) {
+            Some(8) => Self::ReadOnly,
+            Some(13) => Self::StorageFull(e),
+            Some(10) => Self::Io(e),
+            _ => Self::DatabaseError(e),
+        }
     }
+}
 
-    if name.len() > 255 {
-        return Err(DbError::InvalidName(
-            "name cannot exceed 255 characters".to_string(),
-        ));
+impl DbError {
+    /// Whether the operation failed only because the database was too busy:
+    /// no pooled connection freed up within the acquire timeout, or a lock
+    /// stayed held past the busy timeout (`SQLITE_BUSY`/`SQLITE_LOCKED`)
+    ///
+    /// Retrying after a short wait may succeed.
+    #[must_use]
+    pub fn is_busy(&self) -> bool {
+        match self {
+            Self::DatabaseError(e) => {
+                matches!(e, sqlx::Error::PoolTimedOut)
+                    || matches!(sqlite_primary_code(e), Some(5 | 6))
+            }
+            _ => false,
+        }
     }
+}
 
-    // Must start with alphanumeric
-    if !name.chars().next().unwrap().is_alphanumeric() {
-        return Err(DbError::InvalidName(
-            "name must start with alphanumeric character".to_string(),
-        ));
-    }
+/// Primary `SQLite` result code of a database error (extended codes reduced to their base)
+pub(crate) fn sqlite_primary_code(e: &sqlx::Error) -> Option<i32> {
+    let sqlx::Error::Database(db_err) = e else {
+        return None;
+    };
+    db_err
+        .code()
+        .and_then(|code| code.parse::<i32>().ok())
+        .map(|code| code & 0xff)
+}
 
-    // Only allow alphanumeric, hyphens, and underscores
-    if !name
-        .chars()
-        .all(|c| c.is_alphanumeric() || c == '-' || c == '_')
-    {
-        return Err(DbError::InvalidName(
-            "name can only contain alphanumeric characters, hyphens, and underscores".to_string(),
-        ));
-    }
+/// Whether an error means the file is damaged or not a database at all
+/// (`SQLITE_CORRUPT` or `SQLITE_NOTADB`)
+pub(crate) fn is_corruption(e: &sqlx::Error) -> bool {
+    matches!(sqlite_primary_code(e), Some(11 | 26))
+}
 
-    Ok(())
+/// Whether an error is a `UNIQUE` constraint violation
+///
+/// Relies on sqlx's error classification instead of comparing the extended
+/// result code as a string.
+pub(crate) fn is_unique_violation(e: &sqlx::Error) -> bool {
+    matches!(e, sqlx::Error::Database(db_err) if db_err.is_unique_violation())
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use sqlx::{Connection, SqliteConnection};
+
+    #[tokio::test]
+    async fn test_is_unique_violation() {
+        let mut conn = SqliteConnection::connect("sqlite::memory:").await.unwrap();
+        sqlx::query("CREATE TABLE t (name TEXT UNIQUE NOT NULL)")
+            .execute(&mut conn)
+            .await
+            .unwrap();
+        sqlx::query("INSERT INTO t (name) VALUES ('a')")
+            .execute(&mut conn)
+            .await
+            .unwrap();
+
+        let duplicate = sqlx::query("INSERT INTO t (name) VALUES ('a')")
+            .execute(&mut conn)
+            .await
+            .unwrap_err();
+        assert!(is_unique_violation(&duplicate));
+
+        // Other constraint violations are not duplicates
+        let null = sqlx::query("INSERT INTO t (name) VALUES (NULL)")
+            .execute(&mut conn)
+            .await
+            .unwrap_err();
+        assert!(!is_unique_violation(&null));
+        assert!(!is_unique_violation(&sqlx::Error::RowNotFound));
+    }
+
+    #[tokio::test]
+    async fn test_full_database_is_storage_full() {
+        let mut conn = SqliteConnection::connect("sqlite::memory:").await.unwrap();
+        sqlx::query("CREATE TABLE t (data BLOB NOT NULL)")
+            .execute(&mut conn)
+            .await
+            .unwrap();
+        // Cap the file at a few pages so it fills up like a full disk would
+        sqlx::query("PRAGMA max_page_count = 8")
+            .execute(&mut conn)
+            .await
+            .unwrap();
+
+        let full = sqlx::query("INSERT INTO t (data) VALUES (zeroblob(1000000))")
+            .execute(&mut conn)
+            .await
+            .unwrap_err();
+        let err = DbError::from(full);
+        assert!(matches!(err, DbError::StorageFull(_)), "{err:?}");
+        assert!(err.to_string().contains("free disk space"), "{err}");
+        assert!(!err.is_busy());
+
+        assert!(matches!(
+            DbError::from(sqlx::Error::RowNotFound),
+            DbError::DatabaseError(_)
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_refused_write_is_read_only() {
+        let mut conn = SqliteConnection::connect("sqlite::memory:").await.unwrap();
+        sqlx::query("CREATE TABLE t (name TEXT NOT NULL)")
+            .execute(&mut conn)
+            .await
+            .unwrap();
+        sqlx::query("PRAGMA query_only = 1")
+            .execute(&mut conn)
+            .await
+            .unwrap();
 
-    #[test]
-    fn test_validate_app_name() {
-        // Valid names
-        assert!(validate_app_name("my-app").is_ok());
-        assert!(validate_app_name("my_app").is_ok());
-        assert!(validate_app_name("app123").is_ok());
-        assert!(validate_app_name("MyApp").is_ok());
-
-        // Invalid names
-        assert!(validate_app_name("").is_err());
-        assert!(validate_app_name("-app").is_err());
-        assert!(validate_app_name("_app").is_err());
-        assert!(validate_app_name("my app").is_err());
-        assert!(validate_app_name("my@app").is_err());
-        assert!(validate_app_name(&"a".repeat(256)).is_err());
+        let refused = sqlx::query("INSERT INTO t (name) VALUES ('a')")
+            .execute(&mut conn)
+            .await
+            .unwrap_err();
+        assert!(matches!(DbError::from(refused), DbError::ReadOnly));
     }
 }
@@ -0,0 +1,61 @@
+use crate::error::Result;
+use crate::Database;
+use sqlx::pool::PoolConnection;
+use sqlx::sqlite::SqliteConnectOptions;
+use sqlx::{ConnectOptions, Sqlite, SqliteConnection};
+use std::ops::{Deref, DerefMut};
+use tokio::sync::{Mutex, MutexGuard};
+
+/// Where repository queries are executed
+///
+/// Repositories hold one of these instead of a pool so the same query methods
+/// run against either a pooled connection or an open transaction.
+pub(crate) enum Executor<'a> {
+    /// Check out a connection from the database's current pool for each
+    /// operation, recording how long each checkout waited
+    Pool(&'a Database),
+    /// Open a new connection for each operation, closed once it is dropped
+    Dedicated(&'a SqliteConnectOptions),
+    /// Run every operation on one borrowed connection (e.g. a transaction)
+    Connection(Mutex<&'a mut SqliteConnection>),
+}
+
+impl<'a> Executor<'a> {
+    /// Get a connection to run a single operation on
+    pub(crate) async fn acquire(&self) -> Result<ConnectionGuard<'_, 'a>> {
+        match self {
+            Self::Pool(db) => Ok(ConnectionGuard::Pooled(db.acquire().await?)),
+            Self::Dedicated(options) => Ok(ConnectionGuard::Dedicated(options.connect().await?)),
+            Self::Connection(conn) => Ok(ConnectionGuard::Borrowed(conn.lock().await)),
+        }
+    }
+}
+
+/// Connection checked out by [`Executor::acquire`]
+pub(crate) enum ConnectionGuard<'g, 'a> {
+    Pooled(PoolConnection<Sqlite>),
+    Dedicated(SqliteConnection),
+    Borrowed(MutexGuard<'g, &'a mut SqliteConnection>),
+}
+
+impl Deref for ConnectionGuard<'_, '_> {
+    type Target = SqliteConnection;
+
+    fn deref(&self) -> &Self::Target {
+        match self {
+            Self::Pooled(conn) => conn,
+            Self::Dedicated(conn) => conn,
+            Self::Borrowed(conn) => conn,
+        }
+    }
+}
+
+impl DerefMut for ConnectionGuard<'_, '_> {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        match self {
+            Self::Pooled(conn) => conn,
+            Self::Dedicated(conn) => conn,
+            Self::Borrowed(conn) => conn,
+        }
+    }
+}
@@ -0,0 +1,164 @@
+use crate::error::{DbError, Result};
+use crate::models::AppStatus;
+use regex_automata::meta::{Builder, Config, Regex};
+
+/// Longest regular expression [`NameFilter`] accepts, in characters
+pub const MAX_NAME_REGEX_LEN: usize = 256;
+
+/// Longest `name_contains` [`AppQuery`] accepts, in characters
+pub const MAX_QUERY_TEXT_LEN: usize = 256;
+
+/// Largest page [`ApplicationRepository::query`](crate::ApplicationRepository::query) returns
+pub const MAX_QUERY_LIMIT: i64 = 100;
+
+/// Memory the compiled form of a [`NameFilter`] regex may use, in bytes
+const NAME_REGEX_SIZE_LIMIT: usize = 1 << 20;
+
+/// Criteria for [`ApplicationRepository::filter`](crate::ApplicationRepository::filter)
+///
+/// Every criterion set must match; an empty filter matches every name.
+/// Lengths and the glob are checked in SQL, the regex in Rust on the rows
+/// that pass them.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct NameFilter {
+    /// Shortest name to include, in characters
+    pub min_len: Option<usize>,
+    /// Longest name to include, in characters
+    pub max_len: Option<usize>,
+    /// `*`/`?` pattern as for [`ApplicationRepository::list_matching`](crate::ApplicationRepository::list_matching)
+    pub glob: Option<String>,
+    /// Regular expression the name must match somewhere (anchor with `^`/`$`)
+    ///
+    /// Matching runs in linear time, so a pattern can't stall the server, but
+    /// patterns longer than [`MAX_NAME_REGEX_LEN`] or that compile too large
+    /// are rejected.
+    pub regex: Option<String>,
+}
+
+impl NameFilter {
+    /// Check the lengths and compile the regex, if any
+    pub(crate) fn compile(&self) -> Result<Option<Regex>> {
+        if let (Some(min), Some(max)) = (self.min_len, self.max_len) {
+            if min > max {
+                return Err(DbError::InvalidArgument(format!(
+                    "min_len {min} is greater than max_len {max}"
+                )));
+            }
+        }
+
+        let Some(pattern) = &self.regex else {
+            return Ok(None);
+        };
+        let len = pattern.chars().count();
+        if len > MAX_NAME_REGEX_LEN {
+            return Err(DbError::InvalidArgument(format!(
+                "regex cannot exceed {MAX_NAME_REGEX_LEN} characters (got {len})"
+            )));
+        }
+        Builder::new()
+            .configure(Config::new().nfa_size_limit(Some(NAME_REGEX_SIZE_LIMIT)))
+            .build(pattern)
+            .map(Some)
+            .map_err(|e| DbError::InvalidArgument(format!("invalid regex: {e}")))
+    }
+}
+
+/// Column an [`AppQuery`] sorts by
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum SortField {
+    Name,
+    #[default]
+    CreatedAt,
+    UpdatedAt,
+}
+
+impl SortField {
+    /// Column name in the `applications` table
+    pub(crate) fn column(self) -> &'static str {
+        match self {
+            Self::Name => "name",
+            Self::CreatedAt => "created_at",
+            Self::UpdatedAt => "updated_at",
+        }
+    }
+}
+
+/// Order an [`AppQuery`] sorts in
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum SortDirection {
+    Asc,
+    #[default]
+    Desc,
+}
+
+impl SortDirection {
+    pub(crate) fn keyword(self) -> &'static str {
+        match self {
+            Self::Asc => "ASC",
+            Self::Desc => "DESC",
+        }
+    }
+}
+
+/// Filtered, sorted page for [`ApplicationRepository::query`](crate::ApplicationRepository::query)
+///
+/// The default query is the first [`MAX_QUERY_LIMIT`] applications of any
+/// status, newest first.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AppQuery {
+    /// Text the name must contain; case-sensitive, and no character is special
+    pub name_contains: Option<String>,
+    /// Only applications with this status
+    pub status: Option<AppStatus>,
+    pub sort_by: SortField,
+    pub direction: SortDirection,
+    /// Page size, clamped to `1..=`[`MAX_QUERY_LIMIT`], see [`AppQuery::page_limit`]
+    pub limit: i64,
+    /// Rows skipped before the page; negative values count as 0
+    pub offset: i64,
+}
+
+impl Default for AppQuery {
+    fn default() -> Self {
+        Self {
+            name_contains: None,
+            status: None,
+            sort_by: SortField::default(),
+            direction: SortDirection::default(),
+            limit: MAX_QUERY_LIMIT,
+            offset: 0,
+        }
+    }
+}
+
+impl AppQuery {
+    /// Check `name_contains`, returning it with an empty string treated as unset
+    pub(crate) fn name_contains(&self) -> Result<Option<&str>> {
+        let Some(text) = self
+            .name_contains
+            .as_deref()
+            .filter(|text| !text.is_empty())
+        else {
+            return Ok(None);
+        };
+        let len = text.chars().count();
+        if len > MAX_QUERY_TEXT_LEN {
+            return Err(DbError::InvalidArgument(format!(
+                "name_contains cannot exceed {MAX_QUERY_TEXT_LEN} characters (got {len})"
+            )));
+        }
+        Ok(Some(text))
+    }
+
+    /// Page size actually used: `limit` clamped to `1..=`[`MAX_QUERY_LIMIT`]
+    #[must_use]
+    pub fn page_limit(&self) -> i64 {
+        self.limit.clamp(1, MAX_QUERY_LIMIT)
+    }
+
+    /// Rows actually skipped: `offset`, or 0 if negative
+    #[must_use]
+    pub fn page_offset(&self) -> i64 {
+        self.offset.max(0)
+    }
+}
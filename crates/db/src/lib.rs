@@ -1,37 +1,177 @@
+mod clock;
 mod error;
+mod executor;
+mod filter;
+mod maintenance;
+mod metrics;
 mod models;
 mod repositories;
+mod transaction;
+mod validation;
 
+#[cfg(any(test, feature = "test-util"))]
+pub use clock::MockClock;
+pub use clock::{Clock, SystemClock};
 pub use error::{DbError, Result};
-pub use models::Application;
-pub use repositories::ApplicationRepository;
+pub use filter::{
+    AppQuery, NameFilter, SortDirection, SortField, MAX_NAME_REGEX_LEN, MAX_QUERY_LIMIT,
+    MAX_QUERY_TEXT_LEN,
+};
+pub use maintenance::{CheckpointMode, CheckpointResult, DiskUsage, MigrationStatus, PoolStats};
+pub use metrics::{AcquireBucket, AcquireStats};
+pub use models::{AppStatus, Application, ApplicationView, AuditAction, AuditEntry, MetadataEntry};
+pub use repositories::{ApplicationRepository, MAX_RECENT_LIMIT};
+pub use transaction::Transaction;
+pub use validation::{
+    normalize_app_name, validate_app_name, validate_meta_key, validate_meta_value, NameCasing,
+    NameFormat, NamePolicy, DEFAULT_RESERVED_NAMES, MAX_META_KEY_LEN, MAX_META_VALUE_LEN,
+};
 
+use metrics::AcquireHistogram;
+use serde::{Deserialize, Serialize};
+use sqlx::pool::PoolConnection;
 use sqlx::sqlite::{SqliteConnectOptions, SqlitePool, SqlitePoolOptions};
+use sqlx::Sqlite;
 use std::path::Path;
+use std::str::FromStr;
+use std::sync::{Arc, PoisonError, RwLock};
+use std::time::{Duration, Instant};
 use tracing::info;
 
+/// Schema migrations in the order they are applied
+const MIGRATIONS: &[(&str, &str)] = &[
+    (
+        "001_initial_schema",
+        include_str!("../migrations/001_initial_schema.sql"),
+    ),
+    (
+        "002_idempotency_key",
+        include_str!("../migrations/002_idempotency_key.sql"),
+    ),
+    (
+        "003_audit_log",
+        include_str!("../migrations/003_audit_log.sql"),
+    ),
+    (
+        "004_created_at_index",
+        include_str!("../migrations/004_created_at_index.sql"),
+    ),
+    (
+        "005_description",
+        include_str!("../migrations/005_description.sql"),
+    ),
+    (
+        "006_app_metadata",
+        include_str!("../migrations/006_app_metadata.sql"),
+    ),
+    (
+        "007_app_status",
+        include_str!("../migrations/007_app_status.sql"),
+    ),
+    (
+        "008_updated_at",
+        include_str!("../migrations/008_updated_at.sql"),
+    ),
+    (
+        "009_metadata_value_index",
+        include_str!("../migrations/009_metadata_value_index.sql"),
+    ),
+    (
+        "010_app_owner",
+        include_str!("../migrations/010_app_owner.sql"),
+    ),
+];
+
+/// Opt-in rebuild making names unique per owner; see [`Database::scope_names_by_owner`]
+const OWNER_SCOPED_NAMES: &str = include_str!("../migrations/owner_scoped_names.sql");
+
+/// Opt-in full-text index; see [`Database::enable_full_text_search`]
+const FTS_INDEX: &str = include_str!("../migrations/fts_index.sql");
+
 /// Database configuration
 #[derive(Debug, Clone)]
 pub struct DatabaseConfig {
     /// Maximum number of connections in the pool
     pub max_connections: u32,
+    /// Number of connections the pool keeps open even when idle; must not
+    /// exceed `max_connections`
+    pub min_connections: u32,
+    /// Open `min_connections` connections before returning from the constructor,
+    /// so the first requests don't pay for connection setup. Otherwise they
+    /// are opened in the background.
+    pub warmup: bool,
     /// Enable `SQLite` write-ahead logging for better concurrency
     pub enable_wal: bool,
+    /// Open the database read-only; writes fail with [`DbError::ReadOnly`]
+    pub read_only: bool,
+    /// How long to wait for a lock held by another connection before failing
+    pub busy_timeout: Duration,
+    /// How long to wait for a pooled connection before failing with a busy
+    /// error (see [`DbError::is_busy`])
+    pub acquire_timeout: Duration,
+    /// Longest a repository operation may run before failing with
+    /// [`DbError::Timeout`] (unlimited when `None`)
+    pub statement_timeout: Option<Duration>,
+    /// How repository operations get their connection
+    pub connection_strategy: ConnectionStrategy,
+}
+
+/// How repository operations get a connection to run on
+///
+/// Transactions, migrations, and maintenance always use the pool.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ConnectionStrategy {
+    /// Share connections from the pool between operations
+    #[default]
+    Pooled,
+    /// Open a fresh connection for each operation and close it when the
+    /// operation finishes
+    ///
+    /// No connection state (temp tables, pragmas, prepared statements) carries
+    /// over between operations, which helps when isolating or debugging
+    /// requests. Opening a connection costs far more than reusing one, so
+    /// expect noticeably lower throughput. In-memory databases ignore this and
+    /// stay pooled, since a new connection would see an empty database.
+    PerOperation,
 }
 
 impl Default for DatabaseConfig {
     fn default() -> Self {
         Self {
             max_connections: 5,
+            min_connections: 0,
+            warmup: false,
             enable_wal: true,
+            read_only: false,
+            busy_timeout: Duration::from_secs(5),
+            acquire_timeout: Duration::from_secs(30),
+            statement_timeout: None,
+            connection_strategy: ConnectionStrategy::Pooled,
         }
     }
 }
 
 /// Database connection pool
+///
+/// Call [`Database::close`] before exiting so the write-ahead log is
+/// checkpointed. `Drop` cannot run async code, so simply dropping the pool
+/// leaves `-wal`/`-shm` files behind that slow down the next startup.
+///
+/// An in-memory database lives only as long as a connection to it, so for
+/// those the pool holds exactly one connection, never closed while the
+/// `Database` (or a clone) is alive. Operations on it are serialized, and
+/// `max_connections`/`min_connections` are ignored.
 #[derive(Clone)]
 pub struct Database {
-    pub(crate) pool: SqlitePool,
+    /// Swapped out by [`Database::reconnect`]; use [`Database::pool`] to read it
+    pool: Arc<RwLock<SqlitePool>>,
+    settings: Arc<ConnectSettings>,
+    pub(crate) read_only: bool,
+    pub(crate) statement_timeout: Option<Duration>,
+    pub(crate) connection_strategy: ConnectionStrategy,
+    pub(crate) clock: Arc<dyn Clock>,
+    pub(crate) acquire_timings: Arc<AcquireHistogram>,
 }
 
 impl Database {
@@ -40,37 +180,216 @@ impl Database {
         Self::new_with_config(database_path, DatabaseConfig::default()).await
     }
 
+    /// Create a new, empty in-memory database
+    ///
+    /// Data persists across calls for as long as the `Database` is alive. Run
+    /// [`Database::migrate`] before use.
+    pub async fn new_in_memory() -> Result<Self> {
+        Self::from_url("sqlite::memory:", DatabaseConfig::default()).await
+    }
+
     /// Create a new database connection with custom config
     pub async fn new_with_config(
         database_path: impl AsRef<Path>,
         config: DatabaseConfig,
     ) -> Result<Self> {
-        let database_url = format!("sqlite:{}", database_path.as_ref().display());
-
-        let mut options = SqliteConnectOptions::new()
+        let options = SqliteConnectOptions::new()
             .filename(&database_path)
-            .create_if_missing(true);
+            .create_if_missing(!config.read_only)
+            .read_only(config.read_only);
 
-        // Enable WAL mode for better concurrency
-        if config.enable_wal {
-            options = options.pragma("journal_mode", "WAL");
+        let journal_mode = config.enable_wal.then_some("WAL");
+        let location = database_path.as_ref().display().to_string();
+        let in_memory = database_path.as_ref() == Path::new(":memory:");
+        Self::connect(options, &config, journal_mode, &location, in_memory).await
+    }
+
+    /// Create a new database connection from a `sqlite:` URL
+    ///
+    /// Accepts everything sqlx understands (`?mode=ro`, `cache=shared`,
+    /// `sqlite::memory:`, ...) plus two parameters of our own:
+    /// `journal_mode` (overrides `enable_wal`; one of `delete`, `truncate`,
+    /// `persist`, `memory`, `wal` or `off`) and `busy_timeout` in milliseconds
+    /// (overrides `config.busy_timeout`). `mode=ro` opens the database read-only.
+    /// Parameters are URL-decoded.
+    pub async fn from_url(url: &str, mut config: DatabaseConfig) -> Result<Self> {
+        let invalid = |reason: String| {
+            DbError::InvalidArgument(format!("invalid database URL '{url}': {reason}"))
+        };
+
+        let (base, query) = url.split_once('?').unwrap_or((url, ""));
+        let mut journal_mode = config.enable_wal.then_some("WAL");
+        let mut memory_mode = false;
+        let mut passthrough = Vec::new();
+
+        for pair in query.split('&').filter(|pair| !pair.is_empty()) {
+            // sqlx decodes the parameters passed through to it itself
+            let Some((key, value)) = form_urlencoded::parse(pair.as_bytes()).next() else {
+                continue;
+            };
+            match key.as_ref() {
+                "journal_mode" => {
+                    journal_mode = Some(parse_journal_mode(&value).ok_or_else(|| {
+                        invalid(format!(
+                            "journal_mode: unknown mode '{value}', expected one of delete, truncate, persist, memory, wal, off"
+                        ))
+                    })?);
+                }
+                "busy_timeout" => {
+                    let millis = value
+                        .parse::<u64>()
+                        .map_err(|e| invalid(format!("busy_timeout: {e}")))?;
+                    config.busy_timeout = Duration::from_millis(millis);
+                }
+                "mode" => {
+                    config.read_only |= value == "ro";
+                    memory_mode |= value == "memory";
+                    passthrough.push(pair);
+                }
+                _ => passthrough.push(pair),
+            }
         }
 
-        let pool = SqlitePoolOptions::new()
-            .max_connections(config.max_connections)
-            .connect_with(options)
-            .await?;
+        let sqlx_url = if passthrough.is_empty() {
+            base.to_string()
+        } else {
+            format!("{base}?{}", passthrough.join("&"))
+        };
+        let mut options =
+            SqliteConnectOptions::from_str(&sqlx_url).map_err(|e| invalid(e.to_string()))?;
+        if config.read_only {
+            options = options.read_only(true).create_if_missing(false);
+        }
 
-        info!(
-            "Connected to database at {} (max_connections: {}, wal: {})",
-            database_url, config.max_connections, config.enable_wal
+        let in_memory = base.ends_with(":memory:") || memory_mode;
+        Self::connect(options, &config, journal_mode, url, in_memory).await
+    }
+
+    /// Open the pool, applying settings shared by every way of connecting
+    async fn connect(
+        mut options: SqliteConnectOptions,
+        config: &DatabaseConfig,
+        journal_mode: Option<&str>,
+        location: &str,
+        in_memory: bool,
+    ) -> Result<Self> {
+        options = options.busy_timeout(config.busy_timeout);
+
+        // Switching journal modes requires write access
+        if let Some(mode) = journal_mode.filter(|_| !config.read_only) {
+            options = options.pragma("journal_mode", mode.to_string());
+        }
+
+        let settings = ConnectSettings {
+            options,
+            config: config.clone(),
+            journal_mode: journal_mode.map(str::to_string),
+            location: location.to_string(),
+            in_memory,
+        };
+        let pool = settings.open_pool().await?;
+
+        Ok(Self {
+            pool: Arc::new(RwLock::new(pool)),
+            settings: Arc::new(settings),
+            read_only: config.read_only,
+            statement_timeout: config.statement_timeout,
+            connection_strategy: config.connection_strategy,
+            clock: Arc::new(SystemClock),
+            acquire_timings: Arc::default(),
+        })
+    }
+
+    /// Open a new pool against the same database and config, and retire the old one
+    ///
+    /// Use this after the database file has been replaced (e.g. restored from
+    /// a backup) so no connection keeps reading the old file. The new pool is
+    /// swapped in before the old one is closed, so callers getting a
+    /// connection meanwhile are served by one pool or the other rather than
+    /// failing. Operations that already hold an old connection finish on it;
+    /// closing waits for them. Applies to every clone of this `Database`.
+    /// In-memory databases cannot be reconnected, since closing their only
+    /// connection discards them.
+    ///
+    /// The write-ahead log is checkpointed and truncated first, so the new
+    /// pool doesn't replay the old file's log onto the replacement. A write
+    /// committed on an old connection after that checkpoint but before the
+    /// old pool closes lands in the log the new pool shares, and may be lost
+    /// or applied to the replacement; stop writers before replacing the file.
+    pub async fn reconnect(&self) -> Result<()> {
+        if self.settings.in_memory {
+            return Err(DbError::InvalidArgument(
+                "an in-memory database cannot be reconnected".to_string(),
+            ));
+        }
+
+        if !self.read_only {
+            self.checkpoint(CheckpointMode::Truncate).await?;
+        }
+        let pool = self.settings.open_pool().await?;
+        let old = std::mem::replace(
+            &mut *self.pool.write().unwrap_or_else(PoisonError::into_inner),
+            pool,
         );
+        old.close().await;
+
+        info!("Reconnected to database at {}", self.settings.location);
+        Ok(())
+    }
 
-        Ok(Self { pool })
+    /// The current connection pool
+    pub(crate) fn pool(&self) -> SqlitePool {
+        self.pool
+            .read()
+            .unwrap_or_else(PoisonError::into_inner)
+            .clone()
+    }
+
+    /// Check out a connection from the current pool, recording how long it waited
+    pub(crate) async fn acquire(&self) -> Result<PoolConnection<Sqlite>> {
+        match self.acquire_timings.time(self.pool().acquire()).await {
+            // A reconnect closed the pool after it was looked up; the pool
+            // that replaced it serves the retry
+            Err(sqlx::Error::PoolClosed) if !self.pool().is_closed() => {
+                Ok(self.acquire_timings.time(self.pool().acquire()).await?)
+            }
+            conn => Ok(conn?),
+        }
+    }
+
+    /// Options for opening a connection outside the pool
+    ///
+    /// Returns `None` for in-memory databases, where a new connection would
+    /// see a different, empty database.
+    pub(crate) fn dedicated_options(&self) -> Option<&SqliteConnectOptions> {
+        (!self.settings.in_memory).then_some(&self.settings.options)
+    }
+
+    /// Take timestamps (`created_at`, audit times, ...) from `clock` instead of the system time
+    #[must_use]
+    pub fn with_clock(mut self, clock: Arc<dyn Clock>) -> Self {
+        self.clock = clock;
+        self
+    }
+
+    /// The clock timestamps are taken from
+    #[must_use]
+    pub fn clock(&self) -> &dyn Clock {
+        self.clock.as_ref()
+    }
+
+    /// Whether this database was opened read-only
+    #[must_use]
+    pub fn is_read_only(&self) -> bool {
+        self.read_only
     }
 
     /// Run database migrations
     pub async fn migrate(&self) -> Result<()> {
+        if self.read_only {
+            return Err(DbError::ReadOnly);
+        }
+
         info!("Running database migrations...");
 
         // Create migrations tracking table
@@ -81,41 +400,342 @@ impl Database {
                 applied_at INTEGER NOT NULL
             )",
         )
-        .execute(&self.pool)
+        .execute(&self.pool())
         .await?;
 
-        // Check if migration already applied
-        let applied: Option<(String,)> =
-            sqlx::query_as("SELECT name FROM _migrations WHERE name = ?")
-                .bind("001_initial_schema")
-                .fetch_optional(&self.pool)
-                .await?;
+        for (name, sql) in MIGRATIONS {
+            // Check, run, and record each migration under the write lock, so a
+            // second process migrating the same file waits for this one and
+            // then sees the migration as applied instead of failing
+            let mut tx = self.pool().begin_with("BEGIN IMMEDIATE").await?;
+            let applied: Option<(String,)> =
+                sqlx::query_as("SELECT name FROM _migrations WHERE name = ?")
+                    .bind(name)
+                    .fetch_optional(&mut *tx)
+                    .await?;
 
-        if applied.is_none() {
-            // Run migration
-            sqlx::query(include_str!("../migrations/001_initial_schema.sql"))
-                .execute(&self.pool)
-                .await?;
+            if applied.is_some() {
+                info!("Migration {} already applied, skipping", name);
+                continue;
+            }
 
-            // Record migration
+            sqlx::query(sql).execute(&mut *tx).await?;
             sqlx::query("INSERT INTO _migrations (name, applied_at) VALUES (?, ?)")
-                .bind("001_initial_schema")
-                .bind(chrono::Utc::now().timestamp_millis())
-                .execute(&self.pool)
+                .bind(name)
+                .bind(self.clock.now_millis())
+                .execute(&mut *tx)
                 .await?;
+            tx.commit().await?;
 
-            info!("Applied migration: 001_initial_schema");
-        } else {
-            info!("Migration 001_initial_schema already applied, skipping");
+            info!("Applied migration: {}", name);
         }
 
         info!("Database migrations completed");
         Ok(())
     }
 
+    /// Number of migrations applied to this database
+    ///
+    /// Returns 0 for a database that has never been migrated.
+    pub async fn migration_count(&self) -> Result<i64> {
+        let (tracked,): (bool,) = sqlx::query_as(
+            "SELECT EXISTS(SELECT 1 FROM sqlite_master WHERE type = 'table' AND name = '_migrations')",
+        )
+        .fetch_one(&self.pool())
+        .await?;
+        if !tracked {
+            return Ok(0);
+        }
+
+        let (count,): (i64,) = sqlx::query_as("SELECT COUNT(*) FROM _migrations")
+            .fetch_one(&self.pool())
+            .await?;
+        Ok(count)
+    }
+
+    /// Name of the latest migration applied to this database, e.g. `"007_app_status"`
+    ///
+    /// Returns `None` for a database that has never been migrated. A version
+    /// older than [`Database::latest_schema_version`] means migrations are pending.
+    pub async fn schema_version(&self) -> Result<Option<String>> {
+        if self.migration_count().await? == 0 {
+            return Ok(None);
+        }
+
+        // Migration names start with a zero-padded number, so they sort in order
+        let (version,): (Option<String>,) = sqlx::query_as("SELECT MAX(name) FROM _migrations")
+            .fetch_one(&self.pool())
+            .await?;
+        Ok(version)
+    }
+
+    /// Name of the latest migration this build knows about
+    #[must_use]
+    pub fn latest_schema_version() -> &'static str {
+        MIGRATIONS.last().map_or("", |(name, _)| name)
+    }
+
+    /// Names of every migration this build knows about, in the order
+    /// [`Database::migrate`] applies them
+    pub fn migration_names() -> impl Iterator<Item = &'static str> {
+        MIGRATIONS.iter().map(|(name, _)| *name)
+    }
+
+    /// Make application names unique per owner instead of across all owners
+    ///
+    /// Rebuilds the `applications` table with a unique `(owner, name)` index in
+    /// place of the `UNIQUE(name)` constraint, keeping every row, its metadata,
+    /// and its audit trail. This is one-way: once two owners share a name there
+    /// is no global uniqueness to go back to, so no reverse is provided.
+    ///
+    /// Requires [`Database::migrate`] to have run. Returns `false` without
+    /// changing anything if names are already scoped by owner.
+    pub async fn scope_names_by_owner(&self) -> Result<bool> {
+        if self.read_only {
+            return Err(DbError::ReadOnly);
+        }
+        if self.names_scoped_by_owner().await? {
+            return Ok(false);
+        }
+
+        // Dropping the old table drops the full-text triggers with it
+        let full_text_search = self.full_text_search_enabled().await?;
+
+        // Dropping the old table must not cascade into app_metadata, and the
+        // pragma is a no-op inside a transaction, so switch it off first
+        let mut conn = self.pool().acquire().await?;
+        sqlx::query("PRAGMA foreign_keys = OFF")
+            .execute(&mut *conn)
+            .await?;
+        // Rows are copied with their IDs, so metadata references stay valid
+        let rebuilt = async {
+            let mut tx = sqlx::Connection::begin_with(&mut *conn, "BEGIN IMMEDIATE").await?;
+            sqlx::query(OWNER_SCOPED_NAMES).execute(&mut *tx).await?;
+            if full_text_search {
+                sqlx::query(FTS_INDEX).execute(&mut *tx).await?;
+            }
+            tx.commit().await
+        }
+        .await;
+        sqlx::query("PRAGMA foreign_keys = ON")
+            .execute(&mut *conn)
+            .await?;
+        rebuilt?;
+
+        info!("Application names are now unique per owner");
+        Ok(true)
+    }
+
+    /// Whether application names are unique per owner rather than globally
+    pub async fn names_scoped_by_owner(&self) -> Result<bool> {
+        let (scoped,): (bool,) = sqlx::query_as(
+            "SELECT EXISTS(SELECT 1 FROM sqlite_master \
+             WHERE type = 'index' AND name = 'idx_applications_owner_name')",
+        )
+        .fetch_one(&self.pool())
+        .await?;
+        Ok(scoped)
+    }
+
+    /// Index application names and descriptions for
+    /// [`ApplicationRepository::search_fts`]
+    ///
+    /// Creates an FTS5 table over the existing rows and triggers that keep it
+    /// current on every insert, update, and delete. Writes get slower by the
+    /// cost of updating the index; searches no longer scan every row. Fails
+    /// with `InvalidArgument` if the linked `SQLite` lacks FTS5.
+    ///
+    /// Requires [`Database::migrate`] to have run. Returns `false` without
+    /// changing anything if the index already exists.
+    pub async fn enable_full_text_search(&self) -> Result<bool> {
+        if self.read_only {
+            return Err(DbError::ReadOnly);
+        }
+        if self.full_text_search_enabled().await? {
+            return Ok(false);
+        }
+
+        let mut tx = self.pool().begin_with("BEGIN IMMEDIATE").await?;
+        sqlx::query(FTS_INDEX)
+            .execute(&mut *tx)
+            .await
+            .map_err(|e| match &e {
+                sqlx::Error::Database(db_err) if db_err.message().contains("no such module") => {
+                    DbError::InvalidArgument(
+                        "full-text search requires SQLite built with FTS5".to_string(),
+                    )
+                }
+                _ => DbError::from(e),
+            })?;
+        tx.commit().await?;
+
+        info!("Full-text search index enabled");
+        Ok(true)
+    }
+
+    /// Drop the full-text index and its triggers
+    ///
+    /// [`ApplicationRepository::search_fts`] falls back to `LIKE` afterwards.
+    /// Returns `false` if there was no index.
+    pub async fn disable_full_text_search(&self) -> Result<bool> {
+        if self.read_only {
+            return Err(DbError::ReadOnly);
+        }
+        if !self.full_text_search_enabled().await? {
+            return Ok(false);
+        }
+
+        let mut tx = self.pool().begin_with("BEGIN IMMEDIATE").await?;
+        sqlx::query(
+            "DROP TRIGGER IF EXISTS applications_fts_insert;
+             DROP TRIGGER IF EXISTS applications_fts_delete;
+             DROP TRIGGER IF EXISTS applications_fts_update;
+             DROP TABLE IF EXISTS applications_fts;",
+        )
+        .execute(&mut *tx)
+        .await?;
+        tx.commit().await?;
+
+        info!("Full-text search index dropped");
+        Ok(true)
+    }
+
+    /// Whether application names and descriptions have a full-text index
+    pub async fn full_text_search_enabled(&self) -> Result<bool> {
+        let (enabled,): (bool,) = sqlx::query_as(
+            "SELECT EXISTS(SELECT 1 FROM sqlite_master \
+             WHERE type = 'table' AND name = 'applications_fts')",
+        )
+        .fetch_one(&self.pool())
+        .await?;
+        Ok(enabled)
+    }
+
+    /// Checkpoint the write-ahead log and close all pooled connections
+    ///
+    /// Closing affects every clone of this `Database`, since they share one pool.
+    pub async fn close(self) -> Result<()> {
+        if !self.read_only {
+            self.checkpoint(CheckpointMode::Truncate).await?;
+        }
+        self.pool().close().await;
+
+        info!("Database closed");
+        Ok(())
+    }
+
+    /// Begin a new transaction
+    pub async fn begin(&self) -> Result<Transaction> {
+        self.begin_with(None).await
+    }
+
+    /// Begin a new transaction that takes the write lock immediately
+    ///
+    /// Use this for read-then-write sequences (e.g. check a count, then insert)
+    /// so concurrent writers queue up instead of acting on stale reads.
+    pub async fn begin_immediate(&self) -> Result<Transaction> {
+        self.begin_with(Some("BEGIN IMMEDIATE")).await
+    }
+
+    /// Begin a transaction with `statement`, or a plain `BEGIN` if `None`
+    async fn begin_with(&self, statement: Option<&'static str>) -> Result<Transaction> {
+        let conn = self.acquire().await?;
+        let tx = sqlx::Transaction::begin(conn, statement.map(Into::into)).await?;
+        Ok(Transaction::new(
+            tx,
+            self.read_only,
+            self.statement_timeout,
+            self.clock.clone(),
+        ))
+    }
+
     /// Get repository for application operations
     #[must_use]
     pub fn applications(&self) -> ApplicationRepository<'_> {
         ApplicationRepository::new(self)
     }
 }
+
+/// Canonical name of a `journal_mode` URL parameter, matched case-insensitively
+fn parse_journal_mode(value: &str) -> Option<&'static str> {
+    ["DELETE", "TRUNCATE", "PERSIST", "MEMORY", "WAL", "OFF"]
+        .into_iter()
+        .find(|mode| mode.eq_ignore_ascii_case(value))
+}
+
+/// Everything needed to open the pool again, see [`Database::reconnect`]
+struct ConnectSettings {
+    options: SqliteConnectOptions,
+    config: DatabaseConfig,
+    journal_mode: Option<String>,
+    location: String,
+    in_memory: bool,
+}
+
+impl ConnectSettings {
+    async fn open_pool(&self) -> Result<SqlitePool> {
+        let config = &self.config;
+        let corrupt = |e: sqlx::Error| {
+            if error::is_corruption(&e) {
+                DbError::Corrupt(self.location.clone())
+            } else {
+                DbError::from(e)
+            }
+        };
+
+        let pool_options = if self.in_memory {
+            // Closing the only connection would discard the database
+            SqlitePoolOptions::new()
+                .max_connections(1)
+                .min_connections(1)
+                .idle_timeout(None)
+                .max_lifetime(None)
+        } else {
+            if config.min_connections > config.max_connections {
+                return Err(DbError::InvalidArgument(format!(
+                    "min_connections ({}) cannot exceed max_connections ({})",
+                    config.min_connections, config.max_connections
+                )));
+            }
+            SqlitePoolOptions::new()
+                .max_connections(config.max_connections)
+                .min_connections(config.min_connections)
+        }
+        .acquire_timeout(config.acquire_timeout);
+
+        // sqlx opens `min_connections` before `connect_with` returns; a lazy
+        // pool opens them in the background instead
+        let started = Instant::now();
+        let pool = if config.warmup || self.in_memory {
+            pool_options
+                .connect_with(self.options.clone())
+                .await
+                .map_err(corrupt)?
+        } else {
+            pool_options.connect_lazy_with(self.options.clone())
+        };
+        if config.warmup && !self.in_memory {
+            info!(
+                "Warmed up {} database connections in {:?}",
+                config.min_connections,
+                started.elapsed()
+            );
+        }
+
+        // Read the schema so a damaged header is reported now, not on first use
+        sqlx::query("SELECT COUNT(*) FROM sqlite_master")
+            .execute(&pool)
+            .await
+            .map_err(corrupt)?;
+
+        info!(
+            "Connected to database at {} (max_connections: {}, journal_mode: {}, read_only: {})",
+            self.location,
+            pool.options().get_max_connections(),
+            self.journal_mode.as_deref().unwrap_or("default"),
+            config.read_only
+        );
+
+        Ok(pool)
+    }
+}
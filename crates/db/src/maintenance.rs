@@ -0,0 +1,249 @@
+use crate::error::{DbError, Result};
+use crate::{AcquireStats, Database, MIGRATIONS};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::path::Path;
+use std::time::Duration;
+use tokio::time::MissedTickBehavior;
+
+/// `PRAGMA wal_checkpoint` modes, from least to most aggressive
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum CheckpointMode {
+    /// Checkpoint as many frames as possible without waiting for readers or writers
+    #[default]
+    Passive,
+    /// Wait for writers, then checkpoint every frame
+    Full,
+    /// Like `Full`, then wait for readers so the next writer restarts the log
+    Restart,
+    /// Like `Restart`, then truncate the WAL file to zero bytes
+    Truncate,
+}
+
+impl CheckpointMode {
+    fn as_sql(self) -> &'static str {
+        match self {
+            Self::Passive => "PRAGMA wal_checkpoint(PASSIVE)",
+            Self::Full => "PRAGMA wal_checkpoint(FULL)",
+            Self::Restart => "PRAGMA wal_checkpoint(RESTART)",
+            Self::Truncate => "PRAGMA wal_checkpoint(TRUNCATE)",
+        }
+    }
+}
+
+/// Frame counts reported by `SQLite` for a checkpoint
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CheckpointResult {
+    /// The checkpoint could not complete because of a concurrent reader or writer
+    pub busy: bool,
+    /// Frames in the WAL file
+    pub log_frames: i64,
+    /// Frames copied back into the database file
+    pub checkpointed_frames: i64,
+}
+
+/// On-disk footprint of the database
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DiskUsage {
+    /// Path of the main database file, or `None` for an in-memory database
+    pub path: Option<String>,
+    /// Size of the main database file in bytes
+    pub main_bytes: u64,
+    /// Size of the `-wal` file in bytes (0 when absent)
+    pub wal_bytes: u64,
+    /// Size of the `-shm` file in bytes (0 when absent)
+    pub shm_bytes: u64,
+}
+
+impl DiskUsage {
+    /// Combined size of all database files in bytes
+    #[must_use]
+    pub fn total_bytes(&self) -> u64 {
+        self.main_bytes + self.wal_bytes + self.shm_bytes
+    }
+}
+
+/// Connection pool usage
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PoolStats {
+    /// Connections currently open, idle or in use
+    pub size: u32,
+    /// Open connections not currently in use
+    pub idle: usize,
+    /// Upper bound on open connections
+    pub max_connections: u32,
+    /// How long repository operations and transactions waited for a connection
+    pub acquire: AcquireStats,
+}
+
+/// Whether a known migration has been applied
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct MigrationStatus {
+    pub name: String,
+    /// When the migration was applied (epoch millis), or `None` if pending
+    pub applied_at: Option<i64>,
+}
+
+/// Size of the file at `path`, or 0 if it doesn't exist
+async fn file_size(path: &Path) -> Result<u64> {
+    match tokio::fs::metadata(path).await {
+        Ok(metadata) => Ok(metadata.len()),
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(0),
+        Err(e) => Err(DbError::Internal(format!(
+            "Failed to stat {}: {e}",
+            path.display()
+        ))),
+    }
+}
+
+impl Database {
+    /// Report the size of the database file and its `-wal` and `-shm` companions
+    ///
+    /// In-memory databases report no path and zero sizes.
+    pub async fn disk_usage(&self) -> Result<DiskUsage> {
+        let databases: Vec<(i64, String, String)> = sqlx::query_as("PRAGMA database_list")
+            .fetch_all(&self.pool())
+            .await?;
+        let file = databases
+            .into_iter()
+            .find(|(_, name, _)| name == "main")
+            .map(|(_, _, file)| file)
+            .filter(|file| !file.is_empty());
+
+        let Some(file) = file else {
+            return Ok(DiskUsage {
+                path: None,
+                main_bytes: 0,
+                wal_bytes: 0,
+                shm_bytes: 0,
+            });
+        };
+
+        Ok(DiskUsage {
+            main_bytes: file_size(Path::new(&file)).await?,
+            wal_bytes: file_size(Path::new(&format!("{file}-wal"))).await?,
+            shm_bytes: file_size(Path::new(&format!("{file}-shm"))).await?,
+            path: Some(file),
+        })
+    }
+
+    /// Report how many pooled connections are open and idle
+    #[must_use]
+    pub fn pool_stats(&self) -> PoolStats {
+        let pool = self.pool();
+        PoolStats {
+            size: pool.size(),
+            idle: pool.num_idle(),
+            max_connections: pool.options().get_max_connections(),
+            acquire: self.acquire_timings.snapshot(),
+        }
+    }
+
+    /// List every known migration in order, with when it was applied
+    pub async fn migration_status(&self) -> Result<Vec<MigrationStatus>> {
+        let mut applied = HashMap::new();
+        if self.migration_count().await? > 0 {
+            let rows: Vec<(String, i64)> =
+                sqlx::query_as("SELECT name, applied_at FROM _migrations")
+                    .fetch_all(&self.pool())
+                    .await?;
+            applied.extend(rows);
+        }
+
+        Ok(MIGRATIONS
+            .iter()
+            .map(|(name, _)| MigrationStatus {
+                name: (*name).to_string(),
+                applied_at: applied.get(*name).copied(),
+            })
+            .collect())
+    }
+
+    /// Names of the known migrations not yet applied, in the order
+    /// [`Database::migrate`] would apply them
+    ///
+    /// Nothing is changed: this only reads the migrations table.
+    pub async fn migration_plan(&self) -> Result<Vec<String>> {
+        Ok(self
+            .migration_status()
+            .await?
+            .into_iter()
+            .filter(|m| m.applied_at.is_none())
+            .map(|m| m.name)
+            .collect())
+    }
+
+    /// Refresh the query planner's statistics with `ANALYZE` and `PRAGMA optimize`
+    ///
+    /// Safe to run while the database is serving reads: in WAL mode readers
+    /// are never blocked, and writers wait at most `busy_timeout` for the brief
+    /// write that stores the new statistics. Run it periodically as the table grows.
+    pub async fn optimize(&self) -> Result<()> {
+        if self.read_only {
+            return Err(DbError::ReadOnly);
+        }
+
+        sqlx::query("ANALYZE").execute(&self.pool()).await?;
+        sqlx::query("PRAGMA optimize").execute(&self.pool()).await?;
+        Ok(())
+    }
+
+    /// Checkpoint the write-ahead log into the main database file
+    ///
+    /// Returns `None` without doing anything when the database isn't in WAL mode.
+    pub async fn checkpoint(&self, mode: CheckpointMode) -> Result<Option<CheckpointResult>> {
+        if self.read_only {
+            return Err(DbError::ReadOnly);
+        }
+
+        let (journal_mode,): (String,) = sqlx::query_as("PRAGMA journal_mode")
+            .fetch_one(&self.pool())
+            .await?;
+        if !journal_mode.eq_ignore_ascii_case("wal") {
+            return Ok(None);
+        }
+
+        let (busy, log_frames, checkpointed_frames): (i64, i64, i64) =
+            sqlx::query_as(mode.as_sql())
+                .fetch_one(&self.pool())
+                .await?;
+
+        Ok(Some(CheckpointResult {
+            busy: busy != 0,
+            log_frames,
+            checkpointed_frames,
+        }))
+    }
+
+    /// Run a passive checkpoint every `interval`, until this future is dropped
+    ///
+    /// Keeps the WAL from growing between `SQLite`'s own automatic checkpoints
+    /// on a busy server. Returns right away for databases that aren't in WAL
+    /// mode or are read-only. Failed checkpoints are logged and retried on the
+    /// next tick.
+    pub async fn checkpoint_periodically(&self, interval: Duration) {
+        let mut ticker = tokio::time::interval(interval);
+        ticker.set_missed_tick_behavior(MissedTickBehavior::Delay);
+        // The first tick completes immediately
+        ticker.tick().await;
+
+        loop {
+            ticker.tick().await;
+            match self.checkpoint(CheckpointMode::Passive).await {
+                Ok(Some(result)) => tracing::debug!(
+                    log_frames = result.log_frames,
+                    checkpointed_frames = result.checkpointed_frames,
+                    "Periodic WAL checkpoint"
+                ),
+                Ok(None) | Err(DbError::ReadOnly) => {
+                    tracing::info!(
+                        "Database is not in writable WAL mode, skipping periodic checkpoints"
+                    );
+                    return;
+                }
+                Err(e) => tracing::warn!("Periodic WAL checkpoint failed: {}", e),
+            }
+        }
+    }
+}
@@ -0,0 +1,108 @@
+use serde::{Deserialize, Serialize};
+use std::future::Future;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::time::{Duration, Instant};
+
+/// Upper bounds of the acquire latency buckets, in microseconds
+const BUCKET_BOUNDS_MICROS: [u64; 8] = [
+    100, 1_000, 5_000, 10_000, 50_000, 100_000, 500_000, 1_000_000,
+];
+
+/// Histogram of how long callers waited for a pooled connection
+///
+/// Only the wait for the pool is measured, not the queries run afterwards, so
+/// slow acquires point at too few connections rather than a slow database.
+#[derive(Debug, Default)]
+pub(crate) struct AcquireHistogram {
+    /// One counter per bound, plus one for waits longer than every bound
+    buckets: [AtomicU64; BUCKET_BOUNDS_MICROS.len() + 1],
+    count: AtomicU64,
+    total_micros: AtomicU64,
+    max_micros: AtomicU64,
+}
+
+impl AcquireHistogram {
+    /// Await `acquire`, recording how long it took whether or not it succeeded
+    pub(crate) async fn time<T>(&self, acquire: impl Future<Output = T>) -> T {
+        let started = Instant::now();
+        let result = acquire.await;
+        self.record(started.elapsed());
+        result
+    }
+
+    fn record(&self, elapsed: Duration) {
+        let micros = u64::try_from(elapsed.as_micros()).unwrap_or(u64::MAX);
+        let bucket = BUCKET_BOUNDS_MICROS
+            .iter()
+            .position(|&bound| micros <= bound)
+            .unwrap_or(BUCKET_BOUNDS_MICROS.len());
+
+        self.buckets[bucket].fetch_add(1, Ordering::Relaxed);
+        self.count.fetch_add(1, Ordering::Relaxed);
+        self.total_micros.fetch_add(micros, Ordering::Relaxed);
+        self.max_micros.fetch_max(micros, Ordering::Relaxed);
+    }
+
+    /// Current totals
+    pub(crate) fn snapshot(&self) -> AcquireStats {
+        let bounds = BUCKET_BOUNDS_MICROS.iter().copied().map(Some).chain([None]);
+        AcquireStats {
+            count: self.count.load(Ordering::Relaxed),
+            total_micros: self.total_micros.load(Ordering::Relaxed),
+            max_micros: self.max_micros.load(Ordering::Relaxed),
+            buckets: bounds
+                .zip(&self.buckets)
+                .map(|(le_micros, count)| AcquireBucket {
+                    le_micros,
+                    count: count.load(Ordering::Relaxed),
+                })
+                .collect(),
+        }
+    }
+}
+
+/// Time spent waiting for pooled connections since the database was opened
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct AcquireStats {
+    /// Connections handed out
+    pub count: u64,
+    /// Total time spent waiting, in microseconds
+    pub total_micros: u64,
+    /// Longest single wait, in microseconds
+    pub max_micros: u64,
+    /// Waits by duration, shortest bucket first
+    pub buckets: Vec<AcquireBucket>,
+}
+
+/// Number of acquires that took longer than the previous bucket's bound and
+/// at most `le_micros`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct AcquireBucket {
+    /// Upper bound in microseconds; `None` for the last, unbounded bucket
+    pub le_micros: Option<u64>,
+    pub count: u64,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_record_buckets() {
+        let histogram = AcquireHistogram::default();
+        histogram.record(Duration::from_micros(50));
+        histogram.record(Duration::from_millis(3));
+        histogram.record(Duration::from_secs(2));
+
+        let stats = histogram.snapshot();
+        assert_eq!(stats.count, 3);
+        assert_eq!(stats.max_micros, 2_000_000);
+        assert_eq!(stats.total_micros, 2_003_050);
+        assert_eq!(stats.buckets.len(), BUCKET_BOUNDS_MICROS.len() + 1);
+        assert_eq!(stats.buckets[0].count, 1);
+        assert_eq!(stats.buckets[2].le_micros, Some(5_000));
+        assert_eq!(stats.buckets[2].count, 1);
+        assert_eq!(stats.buckets.last().unwrap().le_micros, None);
+        assert_eq!(stats.buckets.last().unwrap().count, 1);
+    }
+}
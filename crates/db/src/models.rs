@@ -7,4 +7,108 @@ pub struct Application {
     pub id: String,
     pub name: String,
     pub created_at: i64,
+    pub description: Option<String>,
+    pub status: AppStatus,
+    /// Last change to the name, description, or status, or the last
+    /// [`touch`](crate::ApplicationRepository::touch) (epoch millis)
+    pub updated_at: i64,
+    /// Owning team or user, if any
+    pub owner: Option<String>,
+}
+
+/// Lifecycle status of an application
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize, sqlx::Type)]
+#[serde(rename_all = "lowercase")]
+#[sqlx(type_name = "TEXT", rename_all = "lowercase")]
+pub enum AppStatus {
+    /// In use; the status of every new application
+    #[default]
+    Active,
+    /// Hidden from default listings but still gettable by ID or name
+    Archived,
+}
+
+impl AppStatus {
+    /// Every status, in lifecycle order
+    pub const ALL: [Self; 2] = [Self::Active, Self::Archived];
+
+    /// Name as stored and serialized, e.g. `"archived"`
+    #[must_use]
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Active => "active",
+            Self::Archived => "archived",
+        }
+    }
+}
+
+/// An application as presented to clients
+///
+/// Storage keeps `created_at` and `updated_at` as epoch milliseconds; this
+/// renders them as RFC 3339 UTC timestamps for consumers that can't handle
+/// bare integers.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ApplicationView {
+    pub id: String,
+    pub name: String,
+    /// Creation time, e.g. `2024-01-31T12:00:00.000Z`
+    pub created_at: String,
+    pub description: Option<String>,
+    pub status: AppStatus,
+    /// Last change, in the same format as `created_at`
+    pub updated_at: String,
+    pub owner: Option<String>,
+}
+
+impl From<&Application> for ApplicationView {
+    fn from(app: &Application) -> Self {
+        Self {
+            id: app.id.clone(),
+            name: app.name.clone(),
+            created_at: format_timestamp(app.created_at),
+            description: app.description.clone(),
+            status: app.status,
+            updated_at: format_timestamp(app.updated_at),
+            owner: app.owner.clone(),
+        }
+    }
+}
+
+/// Render epoch milliseconds as RFC 3339 in UTC with millisecond precision
+///
+/// Values outside the range chrono can represent are rendered as the raw number.
+fn format_timestamp(millis: i64) -> String {
+    chrono::DateTime::from_timestamp_millis(millis).map_or_else(
+        || millis.to_string(),
+        |dt| dt.to_rfc3339_opts(chrono::SecondsFormat::Millis, true),
+    )
+}
+
+/// Kind of change recorded in the audit log
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, sqlx::Type)]
+#[serde(rename_all = "lowercase")]
+#[sqlx(type_name = "TEXT", rename_all = "lowercase")]
+pub enum AuditAction {
+    Create,
+    Update,
+    Delete,
+}
+
+/// One row of the audit log
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct AuditEntry {
+    pub id: i64,
+    pub action: AuditAction,
+    pub app_id: String,
+    pub app_name: String,
+    /// Who made the change, when known
+    pub actor: Option<String>,
+    pub at: i64,
+}
+
+/// One key-value pair of an application's metadata
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, FromRow)]
+pub struct MetadataEntry {
+    pub key: String,
+    pub value: String,
 }
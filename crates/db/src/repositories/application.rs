@@ -1,82 +1,1400 @@
-use crate::error::{validate_app_name, DbError, Result};
-use crate::models::Application;
-use crate::Database;
+use crate::clock::Clock;
+use crate::error::{self, DbError, Result};
+use crate::executor::Executor;
+use crate::filter::{AppQuery, NameFilter, MAX_QUERY_TEXT_LEN};
+use crate::models::{AppStatus, Application, AuditAction, AuditEntry, MetadataEntry};
+use crate::validation::{validate_meta_key, validate_meta_value, NamePolicy};
+use crate::{ConnectionStrategy, Database};
+use futures::stream::{BoxStream, StreamExt, TryStreamExt};
+use sqlx::sqlite::SqlitePoolOptions;
+use sqlx::{Connection, FromRow, SqliteConnection};
+use std::future::Future;
+use std::sync::LazyLock;
+use std::time::Duration;
+use tokio::sync::Mutex;
+
+/// Columns selected into [`Application`]
+///
+/// Listed explicitly rather than `SELECT *` so rows keep decoding after
+/// migrations add columns the model doesn't map.
+const APP_COLUMNS: &str =
+    "id, name, created_at, description, status, updated_at, NULLIF(owner, '') AS owner";
+
+/// Translate a `*`/`?` pattern into a `GLOB` pattern for `SQLite`
+///
+/// `GLOB` also treats `[...]` as a character class; wrapping `[` in a class of
+/// its own makes it literal (a lone `]` is already literal).
+fn sql_glob(pattern: &str) -> String {
+    pattern.replace('[', "[[]")
+}
+
+/// Translate `text` into a `LIKE ... ESCAPE '\'` pattern matching it anywhere
+///
+/// `%`, `_`, and the escape character itself match literally.
+fn sql_like_contains(text: &str) -> String {
+    let escaped = text
+        .replace('\\', "\\\\")
+        .replace('%', "\\%")
+        .replace('_', "\\_");
+    format!("%{escaped}%")
+}
+
+/// Split search text into terms, each `(word, is_prefix)`
+///
+/// A trailing `*` makes a term match any word starting with it. Fails with
+/// `InvalidArgument` if there are no terms or the text is too long.
+fn search_terms(text: &str) -> Result<Vec<(&str, bool)>> {
+    let len = text.chars().count();
+    if len > MAX_QUERY_TEXT_LEN {
+        return Err(DbError::InvalidArgument(format!(
+            "search text cannot exceed {MAX_QUERY_TEXT_LEN} characters (got {len})"
+        )));
+    }
+    let terms: Vec<_> = text
+        .split_whitespace()
+        .map(|term| match term.strip_suffix('*') {
+            Some(prefix) => (prefix, true),
+            None => (term, false),
+        })
+        .filter(|(word, _)| !word.is_empty())
+        .collect();
+    if terms.is_empty() {
+        return Err(DbError::InvalidArgument(
+            "search text cannot be empty".to_string(),
+        ));
+    }
+    Ok(terms)
+}
+
+/// FTS5 `MATCH` expression requiring every term
+///
+/// Each term is quoted so punctuation in it can't be read as query syntax.
+fn fts_match(terms: &[(&str, bool)]) -> String {
+    terms
+        .iter()
+        .map(|(word, prefix)| {
+            let quoted = format!("\"{}\"", word.replace('"', "\"\""));
+            if *prefix {
+                quoted + "*"
+            } else {
+                quoted
+            }
+        })
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+/// `WHERE` condition shared by [`ApplicationRepository::query`] and
+/// [`ApplicationRepository::query_count`]; binds `name_contains` then `status`
+const QUERY_CONDITION: &str = "(?1 IS NULL OR instr(name, ?1) > 0) AND (?2 IS NULL OR status = ?2)";
+
+/// Maximum number of ids bound into a single `DELETE ... IN (...)` statement
+///
+/// Kept well below `SQLite`'s bound-parameter limit.
+const DELETE_CHUNK_SIZE: usize = 500;
+
+/// Query behind [`ApplicationRepository::stream`]
+///
+/// Streams borrow their SQL for as long as they run, so it has to be `'static`.
+static STREAM_SQL: LazyLock<String> = LazyLock::new(|| {
+    format!("SELECT {APP_COLUMNS} FROM applications ORDER BY created_at DESC, name ASC")
+});
+
+/// `WHERE` condition limiting name lookups to one owner's applications; binds the owner
+///
+/// Only applies once [`Database::scope_names_by_owner`] has run. While names
+/// are unique across owners, a name identifies one application whoever owns it.
+const OWNER_MATCHES: &str = "(owner = ? OR NOT EXISTS (SELECT 1 FROM sqlite_master \
+     WHERE type = 'index' AND name = 'idx_applications_owner_name'))";
+
+/// Columns selected into [`AuditEntry`]
+const AUDIT_COLUMNS: &str = "id, action, app_id, app_name, actor, at";
+
+/// Maximum number of rows returned by [`ApplicationRepository::recent`]
+pub const MAX_RECENT_LIMIT: i64 = 100;
 
 /// Repository for application-related database operations
 pub struct ApplicationRepository<'a> {
-    db: &'a Database,
+    executor: Executor<'a>,
+    policy: &'a NamePolicy,
+    actor: Option<&'a str>,
+    owner: &'a str,
+    read_only: bool,
+    statement_timeout: Option<Duration>,
+    clock: &'a dyn Clock,
+    hide_archived: bool,
 }
 
 impl<'a> ApplicationRepository<'a> {
-    /// Create a new `ApplicationRepository`
+    /// Create a new `ApplicationRepository` backed by the database's
+    /// [`ConnectionStrategy`]
     pub(crate) fn new(db: &'a Database) -> Self {
-        Self { db }
+        let executor = match (db.connection_strategy, db.dedicated_options()) {
+            (ConnectionStrategy::PerOperation, Some(options)) => Executor::Dedicated(options),
+            _ => Executor::Pool(db),
+        };
+        Self {
+            executor,
+            policy: NamePolicy::default_ref(),
+            actor: None,
+            owner: "",
+            read_only: db.read_only,
+            statement_timeout: db.statement_timeout,
+            clock: db.clock.as_ref(),
+            hide_archived: false,
+        }
+    }
+
+    /// Create a new `ApplicationRepository` running on a single connection
+    pub(crate) fn with_connection(
+        conn: &'a mut SqliteConnection,
+        read_only: bool,
+        statement_timeout: Option<Duration>,
+        clock: &'a dyn Clock,
+    ) -> Self {
+        Self {
+            executor: Executor::Connection(Mutex::new(conn)),
+            policy: NamePolicy::default_ref(),
+            actor: None,
+            owner: "",
+            read_only,
+            statement_timeout,
+            clock,
+            hide_archived: false,
+        }
+    }
+
+    /// Run `op`, failing with [`DbError::Timeout`] if it exceeds the statement timeout
+    ///
+    /// `SQLite` has no statement timeout of its own, so this bounds the whole
+    /// operation. Dropping a future mid-query abandons it (rolling back any
+    /// transaction it opened), though `SQLite` may keep working briefly on the
+    /// connection before it is reused.
+    async fn timed<T>(&self, op: impl Future<Output = Result<T>>) -> Result<T> {
+        match self.statement_timeout {
+            Some(limit) => tokio::time::timeout(limit, op)
+                .await
+                .map_err(|_| DbError::Timeout(limit))?,
+            None => op.await,
+        }
+    }
+
+    /// Fail fast before attempting a write on a read-only database
+    fn ensure_writable(&self) -> Result<()> {
+        if self.read_only {
+            return Err(DbError::ReadOnly);
+        }
+        Ok(())
+    }
+
+    /// Use a custom name policy for validation
+    #[must_use]
+    pub fn with_policy(mut self, policy: &'a NamePolicy) -> Self {
+        self.policy = policy;
+        self
+    }
+
+    /// Attribute changes made through this repository to `actor` in the audit log
+    #[must_use]
+    pub fn with_actor(mut self, actor: &'a str) -> Self {
+        self.actor = Some(actor);
+        self
+    }
+
+    /// Create applications for `owner`, and look names up among its applications
+    ///
+    /// Applies to inserts, and once [`Database::scope_names_by_owner`] has been
+    /// applied, to [`Self::get_by_name`], [`Self::exists_by_name`],
+    /// [`Self::list_with_prefix`], and [`Self::delete_by_name`]. Until then
+    /// names are unique across owners, so those find an application by name
+    /// whoever owns it. Without this, new applications belong to nobody.
+    #[must_use]
+    pub fn with_owner(mut self, owner: &'a str) -> Self {
+        self.owner = owner;
+        self
+    }
+
+    /// Leave archived applications out of [`Self::list`], [`Self::list_page`],
+    /// [`Self::list_after`], and [`Self::count`]
+    #[must_use]
+    pub fn hide_archived(mut self) -> Self {
+        self.hide_archived = true;
+        self
+    }
+
+    /// `WHERE` condition selecting the applications this repository lists
+    fn visible(&self) -> &'static str {
+        if self.hide_archived {
+            "status != 'archived'"
+        } else {
+            "TRUE"
+        }
+    }
+
+    /// Append an audit row for a change made on `conn`
+    async fn record_audit(
+        &self,
+        conn: &mut SqliteConnection,
+        action: AuditAction,
+        app_id: &str,
+        app_name: &str,
+    ) -> Result<()> {
+        sqlx::query(
+            "INSERT INTO audit_log (action, app_id, app_name, actor, at) VALUES (?, ?, ?, ?, ?)",
+        )
+        .bind(action)
+        .bind(app_id)
+        .bind(app_name)
+        .bind(self.actor)
+        .bind(self.clock.now_millis())
+        .execute(conn)
+        .await?;
+
+        Ok(())
     }
 
     /// Create a new application
     pub async fn create(&self, name: &str) -> Result<Application> {
-        // Validate name
-        validate_app_name(name)?;
+        self.insert(name, None, None).await
+    }
+
+    /// Create a new application with an optional description
+    pub async fn create_with_description(
+        &self,
+        name: &str,
+        description: Option<&str>,
+    ) -> Result<Application> {
+        self.insert(name, None, description).await
+    }
 
-        let id = uuid::Uuid::new_v4().to_string();
-        let created_at = chrono::Utc::now().timestamp_millis();
+    /// Create a new application tagged with a client-supplied idempotency key
+    ///
+    /// Fails with `DuplicateName` if the name or the key is already taken; callers
+    /// should look up the key with [`Self::find_by_idempotency_key`] first.
+    pub async fn create_with_idempotency_key(
+        &self,
+        name: &str,
+        idempotency_key: &str,
+    ) -> Result<Application> {
+        self.insert(name, Some(idempotency_key), None).await
+    }
 
-        sqlx::query_as::<_, Application>(
-            "INSERT INTO applications (id, name, created_at) VALUES (?, ?, ?) RETURNING *",
-        )
-        .bind(&id)
-        .bind(name)
-        .bind(created_at)
-        .fetch_one(&self.db.pool)
-        .await
-        .map_err(|e| {
-            if let sqlx::Error::Database(ref db_err) = e {
-                // Check for UNIQUE constraint violation (SQLITE_CONSTRAINT_UNIQUE = 2067)
-                if let Some(code) = db_err.code() {
-                    if code == "2067" {
-                        return DbError::DuplicateName(name.to_string());
-                    }
+    async fn insert(
+        &self,
+        name: &str,
+        idempotency_key: Option<&str>,
+        description: Option<&str>,
+    ) -> Result<Application> {
+        self.timed(async {
+            self.ensure_writable()?;
+
+            // Validate name
+            self.policy.validate(name)?;
+
+            let id = uuid::Uuid::new_v4().to_string();
+            let created_at = self.clock.now_millis();
+
+            // The application and its audit row are written together or not at all
+            let mut conn = self.executor.acquire().await?;
+            let mut tx = conn.begin().await?;
+
+            let app = sqlx::query_as::<_, Application>(
+                &format!("INSERT INTO applications (id, name, created_at, updated_at, idempotency_key, description, owner) VALUES (?, ?, ?, ?, ?, ?, ?) RETURNING {APP_COLUMNS}"),
+            )
+            .bind(&id)
+            .bind(name)
+            .bind(created_at)
+            .bind(created_at)
+            .bind(idempotency_key)
+            .bind(description)
+            .bind(self.owner)
+            .fetch_one(&mut *tx)
+            .await
+            .map_err(|e| {
+                if error::is_unique_violation(&e) {
+                    DbError::DuplicateName(name.to_string())
+                } else {
+                    DbError::from(e)
                 }
+            })?;
+
+            self.record_audit(&mut tx, AuditAction::Create, &app.id, &app.name)
+                .await?;
+            tx.commit().await?;
+
+            Ok(app)
+        })
+        .await
+    }
+
+    /// Create the application called `name`, or update it if it already exists
+    ///
+    /// Returns the resulting row and `true` if it was inserted, `false` if an
+    /// existing application was updated. Updates keep the original ID and
+    /// `created_at`.
+    pub async fn upsert_by_name(
+        &self,
+        name: &str,
+        description: Option<&str>,
+    ) -> Result<(Application, bool)> {
+        self.timed(async {
+            self.ensure_writable()?;
+            self.policy.validate(name)?;
+
+            let id = uuid::Uuid::new_v4().to_string();
+            let created_at = self.clock.now_millis();
+
+            let mut conn = self.executor.acquire().await?;
+            let mut tx = conn.begin().await?;
+
+            // No conflict target, so this matches whichever name constraint the
+            // schema has; an application of another owner is left alone
+            let app = sqlx::query_as::<_, Application>(&format!(
+                "INSERT INTO applications (id, name, created_at, updated_at, description, owner) \
+                 VALUES (?, ?, ?, ?, ?, ?) \
+                 ON CONFLICT DO UPDATE SET description = excluded.description, \
+                 updated_at = excluded.updated_at WHERE owner = excluded.owner \
+                 RETURNING {APP_COLUMNS}"
+            ))
+            .bind(&id)
+            .bind(name)
+            .bind(created_at)
+            .bind(created_at)
+            .bind(description)
+            .bind(self.owner)
+            .fetch_optional(&mut *tx)
+            .await?
+            .ok_or_else(|| DbError::DuplicateName(name.to_string()))?;
+
+            let inserted = app.id == id;
+            let action = if inserted {
+                AuditAction::Create
+            } else {
+                AuditAction::Update
+            };
+            self.record_audit(&mut tx, action, &app.id, &app.name)
+                .await?;
+            tx.commit().await?;
+
+            Ok((app, inserted))
+        })
+        .await
+    }
+
+    /// Create the application called `name` unless it already exists
+    ///
+    /// Returns the row and `true` if this call inserted it, `false` if it was
+    /// already there. Unlike checking then calling [`Self::create`], concurrent
+    /// callers never see a spurious `DuplicateName`: exactly one of them inserts
+    /// and the rest get the same row back.
+    pub async fn create_if_absent(&self, name: &str) -> Result<(Application, bool)> {
+        self.timed(async {
+            self.ensure_writable()?;
+            self.policy.validate(name)?;
+
+            let id = uuid::Uuid::new_v4().to_string();
+            let created_at = self.clock.now_millis();
+
+            // The INSERT takes the write lock first, so the SELECT below can't race
+            let mut conn = self.executor.acquire().await?;
+            let mut tx = conn.begin().await?;
+
+            let inserted = sqlx::query_as::<_, Application>(&format!(
+                "INSERT INTO applications (id, name, created_at, updated_at, owner) \
+                 VALUES (?, ?, ?, ?, ?) ON CONFLICT DO NOTHING RETURNING {APP_COLUMNS}"
+            ))
+            .bind(&id)
+            .bind(name)
+            .bind(created_at)
+            .bind(created_at)
+            .bind(self.owner)
+            .fetch_optional(&mut *tx)
+            .await?;
+
+            let result = if let Some(app) = inserted {
+                self.record_audit(&mut tx, AuditAction::Create, &app.id, &app.name)
+                    .await?;
+                (app, true)
+            } else {
+                // Taken by another owner when names are unique across owners
+                let app = sqlx::query_as::<_, Application>(&format!(
+                    "SELECT {APP_COLUMNS} FROM applications WHERE name = ? AND owner = ?"
+                ))
+                .bind(name)
+                .bind(self.owner)
+                .fetch_optional(&mut *tx)
+                .await?
+                .ok_or_else(|| DbError::DuplicateName(name.to_string()))?;
+                (app, false)
+            };
+            tx.commit().await?;
+
+            Ok(result)
+        })
+        .await
+    }
+
+    /// Find the application created with `idempotency_key` at or after `since_ms`
+    pub async fn find_by_idempotency_key(
+        &self,
+        idempotency_key: &str,
+        since_ms: i64,
+    ) -> Result<Option<Application>> {
+        self.timed(async {
+            sqlx::query_as::<_, Application>(&format!(
+                "SELECT {APP_COLUMNS} FROM applications WHERE idempotency_key = ? AND created_at >= ?"
+            ))
+            .bind(idempotency_key)
+            .bind(since_ms)
+            .fetch_optional(&mut *self.executor.acquire().await?)
+            .await
+            .map_err(Into::into)
+        })
+        .await
+    }
+
+    /// Release `idempotency_key` if it was used before `before_ms`, so it can be reused
+    pub async fn release_idempotency_key(
+        &self,
+        idempotency_key: &str,
+        before_ms: i64,
+    ) -> Result<bool> {
+        self.timed(async {
+            self.ensure_writable()?;
+
+            let result = sqlx::query(
+                "UPDATE applications SET idempotency_key = NULL WHERE idempotency_key = ? AND created_at < ?",
+            )
+            .bind(idempotency_key)
+            .bind(before_ms)
+            .execute(&mut *self.executor.acquire().await?)
+            .await?;
+
+            Ok(result.rows_affected() > 0)
+        })
+        .await
+    }
+
+    /// Overwrite the `created_at` of the application with this ID
+    ///
+    /// Only for tests that need applications of a known age; real rows keep
+    /// their creation time. Not audited.
+    #[cfg(feature = "test-util")]
+    pub async fn set_created_at(&self, id: &str, created_at: i64) -> Result<()> {
+        self.timed(async {
+            self.ensure_writable()?;
+
+            let result = sqlx::query("UPDATE applications SET created_at = ? WHERE id = ?")
+                .bind(created_at)
+                .bind(id)
+                .execute(&mut *self.executor.acquire().await?)
+                .await?;
+            if result.rows_affected() == 0 {
+                return Err(DbError::NotFound(id.to_string()));
             }
-            DbError::DatabaseError(e)
+
+            Ok(())
         })
+        .await
     }
 
     /// Get application by ID
     pub async fn get(&self, id: &str) -> Result<Option<Application>> {
-        sqlx::query_as::<_, Application>("SELECT * FROM applications WHERE id = ?")
+        self.timed(async {
+            sqlx::query_as::<_, Application>(&format!(
+                "SELECT {APP_COLUMNS} FROM applications WHERE id = ?"
+            ))
             .bind(id)
-            .fetch_optional(&self.db.pool)
+            .fetch_optional(&mut *self.executor.acquire().await?)
             .await
             .map_err(Into::into)
+        })
+        .await
     }
 
-    /// Get application by name
+    /// Get application by name, among this repository's owner's applications
+    /// if names are scoped by owner
     pub async fn get_by_name(&self, name: &str) -> Result<Option<Application>> {
-        sqlx::query_as::<_, Application>("SELECT * FROM applications WHERE name = ?")
+        self.timed(async {
+            sqlx::query_as::<_, Application>(&format!(
+                "SELECT {APP_COLUMNS} FROM applications WHERE name = ? AND {OWNER_MATCHES}"
+            ))
             .bind(name)
-            .fetch_optional(&self.db.pool)
+            .bind(self.owner)
+            .fetch_optional(&mut *self.executor.acquire().await?)
             .await
             .map_err(Into::into)
+        })
+        .await
+    }
+
+    /// Get application by ID, failing with `NotFound` if it doesn't exist
+    pub async fn get_or_err(&self, id: &str) -> Result<Application> {
+        self.get(id)
+            .await?
+            .ok_or_else(|| DbError::NotFound(id.to_string()))
+    }
+
+    /// Get application by name, failing with `NotFound` if it doesn't exist
+    pub async fn get_by_name_or_err(&self, name: &str) -> Result<Application> {
+        self.get_by_name(name)
+            .await?
+            .ok_or_else(|| DbError::NotFound(name.to_string()))
+    }
+
+    /// Check whether an application has this name, among this repository's
+    /// owner's applications if names are scoped by owner
+    pub async fn exists_by_name(&self, name: &str) -> Result<bool> {
+        self.timed(async {
+            let (exists,): (bool,) = sqlx::query_as(&format!(
+                "SELECT EXISTS(SELECT 1 FROM applications WHERE name = ? AND {OWNER_MATCHES})"
+            ))
+            .bind(name)
+            .bind(self.owner)
+            .fetch_one(&mut *self.executor.acquire().await?)
+            .await?;
+
+            Ok(exists)
+        })
+        .await
     }
 
     /// List all applications
     pub async fn list(&self) -> Result<Vec<Application>> {
-        sqlx::query_as::<_, Application>(
-            "SELECT * FROM applications ORDER BY created_at DESC, name ASC",
-        )
-        .fetch_all(&self.db.pool)
+        self.timed(async {
+            sqlx::query_as::<_, Application>(&format!(
+                "SELECT {APP_COLUMNS} FROM applications WHERE {} ORDER BY created_at DESC, name ASC",
+                self.visible()
+            ))
+            .fetch_all(&mut *self.executor.acquire().await?)
+            .await
+            .map_err(Into::into)
+        })
+        .await
+    }
+
+    /// Stream all applications in the same order as [`Self::list`]
+    ///
+    /// Rows are decoded as they are read instead of being collected up front.
+    /// The stream keeps its connection for as long as it runs, so this takes the
+    /// repository by value.
+    pub fn stream(self) -> BoxStream<'a, Result<Application>> {
+        let query = sqlx::query_as::<_, Application>(STREAM_SQL.as_str());
+        match self.executor {
+            // Unlike `query.fetch`, the pool executor's stream owns its own
+            // handle to the pool, so it can outlive this local clone
+            Executor::Pool(db) => sqlx::Executor::fetch(&db.pool(), query)
+                .and_then(|row| async move { Application::from_row(&row) })
+                .map_err(DbError::from)
+                .boxed(),
+            // A one-connection pool owns its connection, which a bare
+            // connection borrowed by the stream couldn't
+            Executor::Dedicated(options) => {
+                let pool = SqlitePoolOptions::new()
+                    .max_connections(1)
+                    .connect_lazy_with(options.clone());
+                sqlx::Executor::fetch(&pool, query)
+                    .and_then(|row| async move { Application::from_row(&row) })
+                    .map_err(DbError::from)
+                    .boxed()
+            }
+            Executor::Connection(conn) => query
+                .fetch(conn.into_inner())
+                .map_err(DbError::from)
+                .boxed(),
+        }
+    }
+
+    /// List one page of applications in the same order as [`Self::list`]
+    ///
+    /// A `limit` of `None` returns every application from `offset` onwards.
+    pub async fn list_page(&self, limit: Option<i64>, offset: i64) -> Result<Vec<Application>> {
+        self.timed(async {
+            // SQLite treats a negative LIMIT as "no limit"
+            sqlx::query_as::<_, Application>(&format!(
+                "SELECT {APP_COLUMNS} FROM applications WHERE {} ORDER BY created_at DESC, name ASC LIMIT ? OFFSET ?",
+                self.visible()
+            ))
+            .bind(limit.unwrap_or(-1))
+            .bind(offset.max(0))
+            .fetch_all(&mut *self.executor.acquire().await?)
+            .await
+            .map_err(Into::into)
+        })
+        .await
+    }
+
+    /// List up to `limit` applications after `cursor`, newest first
+    ///
+    /// `cursor` is the `(created_at, id)` of the last row of the previous page,
+    /// or `None` for the first page. Rows are ordered by `created_at` then `id`,
+    /// both descending, so pages stay consistent when rows are inserted or
+    /// deleted between requests. A negative `limit` is treated as 0.
+    pub async fn list_after(
+        &self,
+        cursor: Option<(i64, &str)>,
+        limit: i64,
+    ) -> Result<Vec<Application>> {
+        self.timed(async {
+            let limit = limit.max(0);
+            let mut conn = self.executor.acquire().await?;
+            let rows = match cursor {
+                Some((created_at, id)) => {
+                    sqlx::query_as::<_, Application>(&format!(
+                        "SELECT {APP_COLUMNS} FROM applications WHERE {} AND (created_at, id) < (?, ?) \
+                         ORDER BY created_at DESC, id DESC LIMIT ?",
+                        self.visible()
+                    ))
+                    .bind(created_at)
+                    .bind(id)
+                    .bind(limit)
+                    .fetch_all(&mut *conn)
+                    .await?
+                }
+                None => {
+                    sqlx::query_as::<_, Application>(&format!(
+                    "SELECT {APP_COLUMNS} FROM applications WHERE {} ORDER BY created_at DESC, id DESC LIMIT ?",
+                    self.visible()
+                ))
+                    .bind(limit)
+                    .fetch_all(&mut *conn)
+                    .await?
+                }
+            };
+
+            Ok(rows)
+        })
+        .await
+    }
+
+    /// Count all applications
+    pub async fn count(&self) -> Result<i64> {
+        self.timed(async {
+            let (count,): (i64,) = sqlx::query_as(&format!(
+                "SELECT COUNT(*) FROM applications WHERE {}",
+                self.visible()
+            ))
+            .fetch_one(&mut *self.executor.acquire().await?)
+            .await?;
+
+            Ok(count)
+        })
+        .await
+    }
+
+    /// Number of applications, the latest `updated_at` among them, and the ID
+    /// of the latest audit entry (0 where there are none)
+    ///
+    /// Every create, update, and delete changes at least one of them, even
+    /// several within the same millisecond, so together they tag a version
+    /// of the catalog, e.g. for an HTTP `ETag`.
+    pub async fn change_marker(&self) -> Result<(i64, i64, i64)> {
+        self.timed(async {
+            let marker: (i64, i64, i64) = sqlx::query_as(&format!(
+                "SELECT COUNT(*), COALESCE(MAX(updated_at), 0), \
+                 (SELECT COALESCE(MAX(id), 0) FROM audit_log) FROM applications WHERE {}",
+                self.visible()
+            ))
+            .fetch_one(&mut *self.executor.acquire().await?)
+            .await?;
+
+            Ok(marker)
+        })
+        .await
+    }
+
+    /// Number of applications with each status, in [`AppStatus::ALL`] order
+    ///
+    /// Statuses no application has are included with a count of 0.
+    pub async fn count_by_status(&self) -> Result<Vec<(AppStatus, i64)>> {
+        self.timed(async {
+            let counts: Vec<(AppStatus, i64)> =
+                sqlx::query_as("SELECT status, COUNT(*) FROM applications GROUP BY status")
+                    .fetch_all(&mut *self.executor.acquire().await?)
+                    .await?;
+
+            Ok(AppStatus::ALL
+                .into_iter()
+                .map(|status| {
+                    let count = counts
+                        .iter()
+                        .find(|(s, _)| *s == status)
+                        .map_or(0, |(_, count)| *count);
+                    (status, count)
+                })
+                .collect())
+        })
+        .await
+    }
+
+    /// List the `n` most recently created applications, newest first
+    ///
+    /// `n` is clamped to `0..=MAX_RECENT_LIMIT`.
+    pub async fn recent(&self, n: i64) -> Result<Vec<Application>> {
+        self.timed(async {
+            sqlx::query_as::<_, Application>(&format!(
+                "SELECT {APP_COLUMNS} FROM applications ORDER BY created_at DESC, name ASC LIMIT ?"
+            ))
+            .bind(n.clamp(0, MAX_RECENT_LIMIT))
+            .fetch_all(&mut *self.executor.acquire().await?)
+            .await
+            .map_err(Into::into)
+        })
+        .await
+    }
+
+    /// List applications created within `[start_ms, end_ms]` (inclusive), newest first
+    pub async fn list_created_between(
+        &self,
+        start_ms: i64,
+        end_ms: i64,
+    ) -> Result<Vec<Application>> {
+        self.timed(async {
+            if start_ms > end_ms {
+                return Err(DbError::InvalidArgument(format!(
+                    "start ({start_ms}) must not be after end ({end_ms})"
+                )));
+            }
+
+            sqlx::query_as::<_, Application>(
+                &format!("SELECT {APP_COLUMNS} FROM applications WHERE created_at BETWEEN ? AND ? ORDER BY created_at DESC, name ASC"),
+            )
+            .bind(start_ms)
+            .bind(end_ms)
+            .fetch_all(&mut *self.executor.acquire().await?)
+            .await
+            .map_err(Into::into)
+        })
+        .await
+    }
+
+    /// List applications whose name matches `pattern`, ordered by name
+    ///
+    /// `*` matches any run of characters and `?` exactly one; everything else,
+    /// including `[`, matches literally. Matching is case-sensitive.
+    pub async fn list_matching(&self, pattern: &str) -> Result<Vec<Application>> {
+        self.timed(async {
+            sqlx::query_as::<_, Application>(&format!(
+                "SELECT {APP_COLUMNS} FROM applications WHERE name GLOB ? ORDER BY name ASC"
+            ))
+            .bind(sql_glob(pattern))
+            .fetch_all(&mut *self.executor.acquire().await?)
+            .await
+            .map_err(Into::into)
+        })
+        .await
+    }
+
+    /// List applications whose name passes `filter`, ordered by name
+    ///
+    /// Fails with `InvalidArgument` if the regex is invalid or `min_len`
+    /// exceeds `max_len`.
+    pub async fn filter(&self, filter: &NameFilter) -> Result<Vec<Application>> {
+        let regex = filter.compile()?;
+        let to_i64 = |len: usize| i64::try_from(len).unwrap_or(i64::MAX);
+
+        self.timed(async {
+            let mut apps = sqlx::query_as::<_, Application>(&format!(
+                "SELECT {APP_COLUMNS} FROM applications
+                 WHERE {}
+                   AND (?1 IS NULL OR length(name) >= ?1)
+                   AND (?2 IS NULL OR length(name) <= ?2)
+                   AND (?3 IS NULL OR name GLOB ?3)
+                 ORDER BY name ASC",
+                self.visible()
+            ))
+            .bind(filter.min_len.map(to_i64))
+            .bind(filter.max_len.map(to_i64))
+            .bind(filter.glob.as_deref().map(sql_glob))
+            .fetch_all(&mut *self.executor.acquire().await?)
+            .await?;
+
+            if let Some(regex) = &regex {
+                apps.retain(|app| regex.is_match(&app.name));
+            }
+            Ok(apps)
+        })
+        .await
+    }
+
+    /// One page of the applications matching `query`, in its order
+    ///
+    /// Ties are broken by ID so pages don't overlap. Fails with
+    /// `InvalidArgument` if `name_contains` is too long.
+    pub async fn query(&self, query: &AppQuery) -> Result<Vec<Application>> {
+        let name_contains = query.name_contains()?;
+
+        self.timed(async {
+            let direction = query.direction.keyword();
+            sqlx::query_as::<_, Application>(&format!(
+                "SELECT {APP_COLUMNS} FROM applications WHERE {} AND {QUERY_CONDITION} \
+                 ORDER BY {} {direction}, id {direction} LIMIT ?3 OFFSET ?4",
+                self.visible(),
+                query.sort_by.column()
+            ))
+            .bind(name_contains)
+            .bind(query.status)
+            .bind(query.page_limit())
+            .bind(query.page_offset())
+            .fetch_all(&mut *self.executor.acquire().await?)
+            .await
+            .map_err(Into::into)
+        })
+        .await
+    }
+
+    /// Number of applications matching `query`, ignoring its order and page
+    pub async fn query_count(&self, query: &AppQuery) -> Result<i64> {
+        let name_contains = query.name_contains()?;
+
+        self.timed(async {
+            let (count,): (i64,) = sqlx::query_as(&format!(
+                "SELECT COUNT(*) FROM applications WHERE {} AND {QUERY_CONDITION}",
+                self.visible()
+            ))
+            .bind(name_contains)
+            .bind(query.status)
+            .fetch_one(&mut *self.executor.acquire().await?)
+            .await?;
+
+            Ok(count)
+        })
+        .await
+    }
+
+    /// List applications whose name, description, or any metadata value
+    /// contains `text`, ignoring ASCII case
+    ///
+    /// Name matches come first, then description matches, then metadata
+    /// matches, each ordered by name; an application matching in several
+    /// places is listed once, at its best rank. No character in `text` is
+    /// special. Fails with `InvalidArgument` if `text` is blank or longer than
+    /// [`MAX_QUERY_TEXT_LEN`].
+    pub async fn search_all(&self, text: &str) -> Result<Vec<Application>> {
+        if text.trim().is_empty() {
+            return Err(DbError::InvalidArgument(
+                "search text cannot be empty".to_string(),
+            ));
+        }
+        let len = text.chars().count();
+        if len > MAX_QUERY_TEXT_LEN {
+            return Err(DbError::InvalidArgument(format!(
+                "search text cannot exceed {MAX_QUERY_TEXT_LEN} characters (got {len})"
+            )));
+        }
+
+        self.timed(async {
+            sqlx::query_as::<_, Application>(&format!(
+                r"SELECT {APP_COLUMNS} FROM applications
+                 WHERE {} AND (
+                     name LIKE ?1 ESCAPE '\'
+                     OR description LIKE ?1 ESCAPE '\'
+                     OR EXISTS (SELECT 1 FROM app_metadata
+                                WHERE app_id = applications.id AND value LIKE ?1 ESCAPE '\')
+                 )
+                 ORDER BY CASE
+                     WHEN name LIKE ?1 ESCAPE '\' THEN 0
+                     WHEN description LIKE ?1 ESCAPE '\' THEN 1
+                     ELSE 2
+                 END, name ASC",
+                self.visible()
+            ))
+            .bind(sql_like_contains(text))
+            .fetch_all(&mut *self.executor.acquire().await?)
+            .await
+            .map_err(Into::into)
+        })
+        .await
+    }
+
+    /// List applications whose name and description contain every word of
+    /// `text`, best match first
+    ///
+    /// Uses the full-text index when [`Database::enable_full_text_search`] has
+    /// been applied: words match whole (a trailing `*` matches by prefix),
+    /// case-insensitively, and results are ranked by relevance with name
+    /// matches weighted above description matches. Without the index, each
+    /// word is matched as a case-insensitive substring with `LIKE` and results
+    /// are ordered by name. Fails with `InvalidArgument` if `text` has no
+    /// words or is longer than [`MAX_QUERY_TEXT_LEN`].
+    pub async fn search_fts(&self, text: &str) -> Result<Vec<Application>> {
+        let terms = search_terms(text)?;
+
+        self.timed(async {
+            let mut conn = self.executor.acquire().await?;
+            let (indexed,): (bool,) = sqlx::query_as(
+                "SELECT EXISTS(SELECT 1 FROM sqlite_master \
+                 WHERE type = 'table' AND name = 'applications_fts')",
+            )
+            .fetch_one(&mut *conn)
+            .await?;
+
+            if indexed {
+                return sqlx::query_as::<_, Application>(&format!(
+                    "SELECT {APP_COLUMNS} FROM applications
+                     JOIN (SELECT rowid AS fts_rowid, bm25(applications_fts, 10.0, 1.0) AS fts_rank
+                           FROM applications_fts WHERE applications_fts MATCH ?) AS hits
+                       ON hits.fts_rowid = applications.rowid
+                     WHERE {}
+                     ORDER BY hits.fts_rank ASC, name ASC",
+                    self.visible()
+                ))
+                .bind(fts_match(&terms))
+                .fetch_all(&mut *conn)
+                .await
+                .map_err(Into::into);
+            }
+
+            let condition =
+                vec![r"(name LIKE ? ESCAPE '\' OR description LIKE ? ESCAPE '\')"; terms.len()]
+                    .join(" AND ");
+            let sql = format!(
+                "SELECT {APP_COLUMNS} FROM applications WHERE {} AND {condition} ORDER BY name ASC",
+                self.visible()
+            );
+            let mut query = sqlx::query_as::<_, Application>(&sql);
+            for (word, _) in &terms {
+                let pattern = sql_like_contains(word);
+                query = query.bind(pattern.clone()).bind(pattern);
+            }
+            query.fetch_all(&mut *conn).await.map_err(Into::into)
+        })
+        .await
+    }
+
+    /// List applications whose name starts with `prefix`, ordered by name,
+    /// among this repository's owner's applications if names are scoped by owner
+    ///
+    /// Unlike [`Self::list_matching`], no character in `prefix` is special.
+    pub async fn list_with_prefix(&self, prefix: &str) -> Result<Vec<Application>> {
+        self.timed(async {
+            sqlx::query_as::<_, Application>(&format!(
+                "SELECT {APP_COLUMNS} FROM applications \
+                 WHERE substr(name, 1, length(?)) = ? AND {OWNER_MATCHES} ORDER BY name ASC"
+            ))
+            .bind(prefix)
+            .bind(prefix)
+            .bind(self.owner)
+            .fetch_all(&mut *self.executor.acquire().await?)
+            .await
+            .map_err(Into::into)
+        })
+        .await
+    }
+
+    /// Rename the application with this ID, returning the updated row
+    ///
+    /// Fails with `InvalidName` or `DuplicateName` if `new_name` can't be used,
+    /// and `NotFound` if there is no such application.
+    pub async fn rename(&self, id: &str, new_name: &str) -> Result<Application> {
+        self.timed(async {
+            self.ensure_writable()?;
+            self.policy.validate(new_name)?;
+
+            let mut conn = self.executor.acquire().await?;
+            let mut tx = conn.begin().await?;
+
+            let app = sqlx::query_as::<_, Application>(&format!(
+                "UPDATE applications SET name = ?, updated_at = ? WHERE id = ? \
+                 RETURNING {APP_COLUMNS}"
+            ))
+            .bind(new_name)
+            .bind(self.clock.now_millis())
+            .bind(id)
+            .fetch_optional(&mut *tx)
+            .await
+            .map_err(|e| {
+                if error::is_unique_violation(&e) {
+                    DbError::DuplicateName(new_name.to_string())
+                } else {
+                    DbError::from(e)
+                }
+            })?
+            .ok_or_else(|| DbError::NotFound(id.to_string()))?;
+
+            self.record_audit(&mut tx, AuditAction::Update, &app.id, &app.name)
+                .await?;
+            tx.commit().await?;
+
+            Ok(app)
+        })
+        .await
+    }
+
+    /// Set metadata `key` of the application with this ID to `value`, replacing any previous value
+    ///
+    /// Fails with [`DbError::NotFound`] if there is no such application.
+    pub async fn set_meta(&self, app_id: &str, key: &str, value: &str) -> Result<()> {
+        self.timed(async {
+            self.ensure_writable()?;
+            validate_meta_key(key)?;
+            validate_meta_value(value)?;
+
+            let mut conn = self.executor.acquire().await?;
+            let mut tx = conn.begin().await?;
+
+            let name: Option<String> =
+                sqlx::query_scalar("SELECT name FROM applications WHERE id = ?")
+                    .bind(app_id)
+                    .fetch_optional(&mut *tx)
+                    .await?;
+            let Some(name) = name else {
+                return Err(DbError::NotFound(app_id.to_string()));
+            };
+
+            sqlx::query(
+                "INSERT INTO app_metadata (app_id, key, value) VALUES (?, ?, ?) \
+                 ON CONFLICT(app_id, key) DO UPDATE SET value = excluded.value",
+            )
+            .bind(app_id)
+            .bind(key)
+            .bind(value)
+            .execute(&mut *tx)
+            .await?;
+
+            self.record_audit(&mut tx, AuditAction::Update, app_id, &name)
+                .await?;
+            tx.commit().await?;
+
+            Ok(())
+        })
+        .await
+    }
+
+    /// Value of metadata `key` on the application with this ID, if set
+    pub async fn get_meta(&self, app_id: &str, key: &str) -> Result<Option<String>> {
+        self.timed(async {
+            sqlx::query_scalar("SELECT value FROM app_metadata WHERE app_id = ? AND key = ?")
+                .bind(app_id)
+                .bind(key)
+                .fetch_optional(&mut *self.executor.acquire().await?)
+                .await
+                .map_err(Into::into)
+        })
+        .await
+    }
+
+    /// Remove metadata `key` from the application with this ID
+    ///
+    /// Returns whether the key was set.
+    pub async fn delete_meta(&self, app_id: &str, key: &str) -> Result<bool> {
+        self.timed(async {
+            self.ensure_writable()?;
+
+            let mut conn = self.executor.acquire().await?;
+            let mut tx = conn.begin().await?;
+
+            let deleted = sqlx::query("DELETE FROM app_metadata WHERE app_id = ? AND key = ?")
+                .bind(app_id)
+                .bind(key)
+                .execute(&mut *tx)
+                .await?
+                .rows_affected()
+                > 0;
+            if !deleted {
+                return Ok(false);
+            }
+
+            let name: String = sqlx::query_scalar("SELECT name FROM applications WHERE id = ?")
+                .bind(app_id)
+                .fetch_one(&mut *tx)
+                .await?;
+            self.record_audit(&mut tx, AuditAction::Update, app_id, &name)
+                .await?;
+            tx.commit().await?;
+
+            Ok(true)
+        })
+        .await
+    }
+
+    /// All metadata of the application with this ID, ordered by key
+    ///
+    /// Empty both for an application without metadata and for an unknown ID.
+    pub async fn list_meta(&self, app_id: &str) -> Result<Vec<MetadataEntry>> {
+        self.timed(async {
+            sqlx::query_as::<_, MetadataEntry>(
+                "SELECT key, value FROM app_metadata WHERE app_id = ? ORDER BY key ASC",
+            )
+            .bind(app_id)
+            .fetch_all(&mut *self.executor.acquire().await?)
+            .await
+            .map_err(Into::into)
+        })
+        .await
+    }
+
+    /// List applications whose metadata `key` is exactly `value`, ordered by name
+    pub async fn find_by_meta(&self, key: &str, value: &str) -> Result<Vec<Application>> {
+        self.timed(async {
+            sqlx::query_as::<_, Application>(&format!(
+                "SELECT {APP_COLUMNS} FROM applications
+                 WHERE {} AND id IN (SELECT app_id FROM app_metadata WHERE key = ? AND value = ?)
+                 ORDER BY name ASC",
+                self.visible()
+            ))
+            .bind(key)
+            .bind(value)
+            .fetch_all(&mut *self.executor.acquire().await?)
+            .await
+            .map_err(Into::into)
+        })
+        .await
+    }
+
+    /// Set the status of the application with this ID, returning the updated row
+    ///
+    /// Fails with `NotFound` if the application doesn't exist. Setting the
+    /// status it already has succeeds without an audit entry.
+    pub async fn set_status(&self, id: &str, status: AppStatus) -> Result<Application> {
+        self.timed(async {
+            self.ensure_writable()?;
+
+            let mut conn = self.executor.acquire().await?;
+            let mut tx = conn.begin().await?;
+
+            let current = sqlx::query_as::<_, Application>(&format!(
+                "SELECT {APP_COLUMNS} FROM applications WHERE id = ?"
+            ))
+            .bind(id)
+            .fetch_optional(&mut *tx)
+            .await?
+            .ok_or_else(|| DbError::NotFound(id.to_string()))?;
+            if current.status == status {
+                return Ok(current);
+            }
+
+            let app = sqlx::query_as::<_, Application>(&format!(
+                "UPDATE applications SET status = ?, updated_at = ? WHERE id = ? \
+                 RETURNING {APP_COLUMNS}"
+            ))
+            .bind(status)
+            .bind(self.clock.now_millis())
+            .bind(id)
+            .fetch_one(&mut *tx)
+            .await?;
+            self.record_audit(&mut tx, AuditAction::Update, &app.id, &app.name)
+                .await?;
+            tx.commit().await?;
+
+            Ok(app)
+        })
+        .await
+    }
+
+    /// Mark the application with this ID as active now by setting its
+    /// `updated_at`, changing nothing else
+    ///
+    /// Returns whether the application exists. Touches are frequent heartbeats,
+    /// so unlike other changes they aren't recorded in the audit log.
+    pub async fn touch(&self, id: &str) -> Result<bool> {
+        self.timed(async {
+            self.ensure_writable()?;
+
+            let result = sqlx::query("UPDATE applications SET updated_at = ? WHERE id = ?")
+                .bind(self.clock.now_millis())
+                .bind(id)
+                .execute(&mut *self.executor.acquire().await?)
+                .await?;
+            Ok(result.rows_affected() > 0)
+        })
         .await
-        .map_err(Into::into)
     }
 
     /// Delete application by ID
     pub async fn delete(&self, id: &str) -> Result<bool> {
-        let result = sqlx::query("DELETE FROM applications WHERE id = ?")
+        self.delete_where("id = ?", &[id]).await
+    }
+
+    /// Delete application by name, among this repository's owner's
+    /// applications if names are scoped by owner
+    pub async fn delete_by_name(&self, name: &str) -> Result<bool> {
+        self.delete_where(
+            &format!("name = ? AND {OWNER_MATCHES}"),
+            &[name, self.owner],
+        )
+        .await
+    }
+
+    /// Delete each `(id, name)` application, but only while it still has that name
+    ///
+    /// The name is checked by the delete itself, and every target is handled
+    /// in one transaction, so a concurrent rename can't make this remove an
+    /// application the caller didn't name. If any ID belongs to an
+    /// application with a different name, nothing is deleted and this fails
+    /// with `InvalidArgument`. Unknown IDs are ignored. Returns the IDs removed.
+    pub async fn delete_many_confirmed(&self, targets: &[(&str, &str)]) -> Result<Vec<String>> {
+        self.timed(async {
+            if targets.is_empty() {
+                return Ok(Vec::new());
+            }
+            self.ensure_writable()?;
+
+            let mut conn = self.executor.acquire().await?;
+            let mut tx = conn.begin().await?;
+            let mut deleted = Vec::new();
+
+            for (id, name) in targets {
+                let removed: Option<(String, String)> = sqlx::query_as(
+                    "DELETE FROM applications WHERE id = ? AND name = ? RETURNING id, name",
+                )
+                .bind(id)
+                .bind(name)
+                .fetch_optional(&mut *tx)
+                .await?;
+
+                if let Some((id, name)) = removed {
+                    self.record_audit(&mut tx, AuditAction::Delete, &id, &name)
+                        .await?;
+                    deleted.push(id);
+                    continue;
+                }
+
+                let actual: Option<(String,)> =
+                    sqlx::query_as("SELECT name FROM applications WHERE id = ?")
+                        .bind(id)
+                        .fetch_optional(&mut *tx)
+                        .await?;
+                if let Some((actual,)) = actual {
+                    // Dropping the transaction rolls back earlier deletes
+                    return Err(DbError::InvalidArgument(format!(
+                        "refusing to delete application {id}: its name is '{actual}', not '{name}'"
+                    )));
+                }
+            }
+
+            tx.commit().await?;
+            Ok(deleted)
+        })
+        .await
+    }
+
+    /// Delete the application matching `condition` with `values` bound, auditing the removal
+    async fn delete_where(&self, condition: &str, values: &[&str]) -> Result<bool> {
+        self.timed(async {
+            self.ensure_writable()?;
+
+            let mut conn = self.executor.acquire().await?;
+            let mut tx = conn.begin().await?;
+
+            let sql = format!("DELETE FROM applications WHERE {condition} RETURNING id, name");
+            let mut query = sqlx::query_as(&sql);
+            for value in values {
+                query = query.bind(*value);
+            }
+            let deleted: Option<(String, String)> = query.fetch_optional(&mut *tx).await?;
+
+            let Some((id, name)) = deleted else {
+                return Ok(false);
+            };
+            self.record_audit(&mut tx, AuditAction::Delete, &id, &name)
+                .await?;
+            tx.commit().await?;
+
+            Ok(true)
+        })
+        .await
+    }
+
+    /// Delete all applications with the given IDs, returning how many were removed
+    ///
+    /// Large inputs are deleted in chunks within a single transaction, so either
+    /// every chunk is applied or none are. Unknown IDs are ignored.
+    pub async fn delete_many(&self, ids: &[&str]) -> Result<u64> {
+        let deleted = self.delete_many_returning_ids(ids).await?;
+        Ok(deleted.len() as u64)
+    }
+
+    /// Like [`Self::delete_many`], but returns the IDs that were actually removed
+    pub async fn delete_many_returning_ids(&self, ids: &[&str]) -> Result<Vec<String>> {
+        self.timed(async {
+            if ids.is_empty() {
+                return Ok(Vec::new());
+            }
+            self.ensure_writable()?;
+
+            let mut conn = self.executor.acquire().await?;
+            let mut tx = conn.begin().await?;
+            let mut deleted = Vec::new();
+
+            for chunk in ids.chunks(DELETE_CHUNK_SIZE) {
+                let placeholders = vec!["?"; chunk.len()].join(", ");
+                let sql = format!(
+                    "DELETE FROM applications WHERE id IN ({placeholders}) RETURNING id, name"
+                );
+
+                let mut query = sqlx::query_as::<_, (String, String)>(&sql);
+                for id in chunk {
+                    query = query.bind(*id);
+                }
+                for (id, name) in query.fetch_all(&mut *tx).await? {
+                    self.record_audit(&mut tx, AuditAction::Delete, &id, &name)
+                        .await?;
+                    deleted.push(id);
+                }
+            }
+
+            tx.commit().await?;
+            Ok(deleted)
+        })
+        .await
+    }
+
+    /// Delete every application and its metadata, returning the IDs removed
+    ///
+    /// Runs in one transaction and audits each removal; the audit log itself
+    /// is append-only and is kept.
+    pub async fn delete_all_returning_ids(&self) -> Result<Vec<String>> {
+        self.timed(async {
+            self.ensure_writable()?;
+
+            let mut conn = self.executor.acquire().await?;
+            let mut tx = conn.begin().await?;
+
+            let rows: Vec<(String, String)> =
+                sqlx::query_as("DELETE FROM applications RETURNING id, name")
+                    .fetch_all(&mut *tx)
+                    .await?;
+            let mut deleted = Vec::with_capacity(rows.len());
+            for (id, name) in rows {
+                self.record_audit(&mut tx, AuditAction::Delete, &id, &name)
+                    .await?;
+                deleted.push(id);
+            }
+
+            tx.commit().await?;
+            Ok(deleted)
+        })
+        .await
+    }
+
+    /// Audit trail of the application with this ID, oldest first
+    pub async fn audit_trail(&self, id: &str) -> Result<Vec<AuditEntry>> {
+        self.timed(async {
+            sqlx::query_as::<_, AuditEntry>(&format!(
+                "SELECT {AUDIT_COLUMNS} FROM audit_log WHERE app_id = ? ORDER BY at ASC, id ASC"
+            ))
             .bind(id)
-            .execute(&self.db.pool)
-            .await?;
+            .fetch_all(&mut *self.executor.acquire().await?)
+            .await
+            .map_err(Into::into)
+        })
+        .await
+    }
 
-        Ok(result.rows_affected() > 0)
+    /// The `n` most recent audit entries across all applications, newest first
+    ///
+    /// `n` is clamped to `0..=MAX_RECENT_LIMIT`.
+    pub async fn recent_audit(&self, n: i64) -> Result<Vec<AuditEntry>> {
+        self.timed(async {
+            sqlx::query_as::<_, AuditEntry>(&format!(
+                "SELECT {AUDIT_COLUMNS} FROM audit_log ORDER BY at DESC, id DESC LIMIT ?"
+            ))
+            .bind(n.clamp(0, MAX_RECENT_LIMIT))
+            .fetch_all(&mut *self.executor.acquire().await?)
+            .await
+            .map_err(Into::into)
+        })
+        .await
     }
 }
@@ -1,3 +1,3 @@
 mod application;
 
-pub use application::ApplicationRepository;
+pub use application::{ApplicationRepository, MAX_RECENT_LIMIT};
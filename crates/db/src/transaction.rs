@@ -0,0 +1,54 @@
+use crate::clock::Clock;
+use crate::error::Result;
+use crate::repositories::ApplicationRepository;
+use sqlx::Sqlite;
+use std::sync::Arc;
+use std::time::Duration;
+
+/// An open database transaction
+///
+/// Repositories obtained from a transaction run all of their queries on the
+/// transaction's connection. Changes are discarded unless [`Transaction::commit`]
+/// is called; dropping the transaction rolls it back.
+pub struct Transaction {
+    inner: sqlx::Transaction<'static, Sqlite>,
+    read_only: bool,
+    statement_timeout: Option<Duration>,
+    clock: Arc<dyn Clock>,
+}
+
+impl Transaction {
+    pub(crate) fn new(
+        inner: sqlx::Transaction<'static, Sqlite>,
+        read_only: bool,
+        statement_timeout: Option<Duration>,
+        clock: Arc<dyn Clock>,
+    ) -> Self {
+        Self {
+            inner,
+            read_only,
+            statement_timeout,
+            clock,
+        }
+    }
+
+    /// Get repository for application operations within this transaction
+    pub fn applications(&mut self) -> ApplicationRepository<'_> {
+        ApplicationRepository::with_connection(
+            &mut self.inner,
+            self.read_only,
+            self.statement_timeout,
+            self.clock.as_ref(),
+        )
+    }
+
+    /// Commit the transaction
+    pub async fn commit(self) -> Result<()> {
+        self.inner.commit().await.map_err(Into::into)
+    }
+
+    /// Roll back the transaction
+    pub async fn rollback(self) -> Result<()> {
+        self.inner.rollback().await.map_err(Into::into)
+    }
+}
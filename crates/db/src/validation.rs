@@ -0,0 +1,530 @@
+use crate::error::{DbError, Result};
+use serde::{Deserialize, Serialize};
+use std::borrow::Cow;
+use std::sync::LazyLock;
+
+/// Names reserved for planned system routes
+pub const DEFAULT_RESERVED_NAMES: &[&str] = &["admin", "health", "metrics", "system"];
+
+/// Longest allowed DNS label (RFC 1123)
+const DNS_LABEL_MAX_LEN: usize = 63;
+
+/// Longest allowed metadata key
+pub const MAX_META_KEY_LEN: usize = 64;
+
+/// Longest allowed metadata value
+pub const MAX_META_VALUE_LEN: usize = 4096;
+
+/// Policy used when none is supplied explicitly
+static DEFAULT_POLICY: LazyLock<NamePolicy> = LazyLock::new(NamePolicy::default);
+
+/// Character rules applied to application names
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum NameFormat {
+    /// Alphanumerics plus the policy's `allow_chars`
+    #[default]
+    Default,
+    /// RFC 1123 DNS labels: lowercase ASCII letters, digits and hyphens, starting
+    /// and ending with a letter or digit, at most 63 characters
+    ///
+    /// `allow_chars` and `require_prefix_alnum` are ignored in this mode.
+    DnsLabel,
+}
+
+/// How names are cased when they are normalized
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum NameCasing {
+    /// Keep names as given
+    #[default]
+    Preserve,
+    /// Convert names to lowercase
+    Lower,
+    /// Convert names to uppercase
+    Upper,
+}
+
+/// Rules applied when validating application names
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct NamePolicy {
+    /// Minimum name length in characters (e.g. 3 to rule out names like "a")
+    pub min_len: usize,
+
+    /// Maximum name length in characters (e.g. 63 for DNS compatibility)
+    pub max_len: usize,
+
+    /// Non-alphanumeric characters permitted in names
+    pub allow_chars: String,
+
+    /// Require names to start with an alphanumeric character
+    pub require_prefix_alnum: bool,
+
+    /// Names that may not be used (compared case-insensitively)
+    pub reserved_names: Vec<String>,
+
+    /// Which characters names may contain
+    pub format: NameFormat,
+
+    /// Casing applied by [`NamePolicy::normalize`]
+    ///
+    /// Names are unique case-sensitively in the database. With `Lower` or
+    /// `Upper`, every name is recased before it is stored or looked up, so
+    /// "My-App" and "my-app" refer to the same application and can't both
+    /// exist. Names stored before the casing was changed are not rewritten:
+    /// lookups by name only find them if they are already in the new case,
+    /// so rename them (by ID) when switching. `Upper` rejects every name with
+    /// a letter under [`NameFormat::DnsLabel`], which only allows lowercase.
+    pub casing: NameCasing,
+}
+
+impl Default for NamePolicy {
+    fn default() -> Self {
+        Self {
+            min_len: 1,
+            max_len: 255,
+            allow_chars: "-_".to_string(),
+            require_prefix_alnum: true,
+            reserved_names: DEFAULT_RESERVED_NAMES
+                .iter()
+                .map(ToString::to_string)
+                .collect(),
+            format: NameFormat::Default,
+            casing: NameCasing::Preserve,
+        }
+    }
+}
+
+impl NamePolicy {
+    /// Shared instance of the default policy
+    pub(crate) fn default_ref() -> &'static Self {
+        &DEFAULT_POLICY
+    }
+
+    /// Canonical form of a user-supplied name under this policy: trimmed as
+    /// by [`normalize_app_name`], then recased according to `casing`
+    #[must_use]
+    pub fn normalize<'a>(&self, name: &'a str) -> Cow<'a, str> {
+        let name = normalize_app_name(name);
+        match self.casing {
+            NameCasing::Preserve => Cow::Borrowed(name),
+            NameCasing::Lower => Cow::Owned(name.to_lowercase()),
+            NameCasing::Upper => Cow::Owned(name.to_uppercase()),
+        }
+    }
+
+    /// Validate an application name against this policy
+    pub fn validate(&self, name: &str) -> Result<()> {
+        if name.is_empty() {
+            return Err(DbError::InvalidName("name cannot be empty".to_string()));
+        }
+
+        let len = name.chars().count();
+        if len < self.min_len {
+            return Err(DbError::InvalidName(format!(
+                "name must be at least {} characters (got {len})",
+                self.min_len
+            )));
+        }
+        if len > self.max_len {
+            return Err(DbError::InvalidName(format!(
+                "name cannot exceed {} characters (got {len})",
+                self.max_len
+            )));
+        }
+
+        match self.format {
+            NameFormat::Default => self.validate_default_format(name)?,
+            NameFormat::DnsLabel => Self::validate_dns_label(name)?,
+        }
+
+        if self
+            .reserved_names
+            .iter()
+            .any(|reserved| reserved.eq_ignore_ascii_case(name))
+        {
+            return Err(DbError::InvalidName(format!(
+                "name '{name}' is reserved for system use"
+            )));
+        }
+
+        Ok(())
+    }
+
+    fn validate_default_format(&self, name: &str) -> Result<()> {
+        if self.require_prefix_alnum && !name.chars().next().unwrap().is_alphanumeric() {
+            return Err(DbError::InvalidName(
+                "name must start with alphanumeric character".to_string(),
+            ));
+        }
+
+        if !name
+            .chars()
+            .all(|c| c.is_alphanumeric() || self.allow_chars.contains(c))
+        {
+            return Err(DbError::InvalidName(format!(
+                "name can only contain {}",
+                self.describe_allowed_chars()
+            )));
+        }
+
+        Ok(())
+    }
+
+    fn validate_dns_label(name: &str) -> Result<()> {
+        if name.len() > DNS_LABEL_MAX_LEN {
+            return Err(DbError::InvalidName(format!(
+                "name cannot exceed {DNS_LABEL_MAX_LEN} characters to be a DNS label"
+            )));
+        }
+
+        if !name
+            .chars()
+            .all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || c == '-')
+        {
+            return Err(DbError::InvalidName(
+                "name can only contain lowercase letters, digits, and hyphens".to_string(),
+            ));
+        }
+
+        if name.starts_with('-') || name.ends_with('-') {
+            return Err(DbError::InvalidName(
+                "name must start and end with a letter or digit".to_string(),
+            ));
+        }
+
+        Ok(())
+    }
+
+    /// Describe this policy in a sentence, e.g. for documentation shown to clients
+    #[must_use]
+    pub fn describe(&self) -> String {
+        let mut rules = match self.format {
+            NameFormat::Default => {
+                let mut rules = vec![format!(
+                    "be {}-{} characters of {}",
+                    self.min_len.max(1),
+                    self.max_len,
+                    self.describe_allowed_chars()
+                )];
+                if self.require_prefix_alnum {
+                    rules.push("start with an alphanumeric character".to_string());
+                }
+                rules
+            }
+            NameFormat::DnsLabel => vec![
+                format!(
+                    "be {}-{} lowercase letters, digits, and hyphens",
+                    self.min_len.max(1),
+                    self.max_len.min(DNS_LABEL_MAX_LEN)
+                ),
+                "start and end with a letter or digit".to_string(),
+            ],
+        };
+        if !self.reserved_names.is_empty() {
+            rules.push(format!(
+                "not be a reserved name ({})",
+                self.reserved_names.join(", ")
+            ));
+        }
+
+        let rules = match rules.as_slice() {
+            [only] => only.clone(),
+            [first, second] => format!("{first} and {second}"),
+            [rest @ .., last] => format!("{}, and {last}", rest.join(", ")),
+            [] => unreachable!(),
+        };
+        let casing = match self.casing {
+            NameCasing::Preserve => "",
+            NameCasing::Lower => " Names are converted to lowercase.",
+            NameCasing::Upper => " Names are converted to uppercase.",
+        };
+        format!("Names must {rules}.{casing}")
+    }
+
+    fn describe_allowed_chars(&self) -> String {
+        let mut kinds = vec!["alphanumeric characters".to_string()];
+        kinds.extend(self.allow_chars.chars().map(|c| match c {
+            '-' => "hyphens".to_string(),
+            '_' => "underscores".to_string(),
+            '.' => "dots".to_string(),
+            other => format!("'{other}'"),
+        }));
+
+        match kinds.as_slice() {
+            [only] => only.clone(),
+            [first, second] => format!("{first} and {second}"),
+            [rest @ .., last] => format!("{}, and {last}", rest.join(", ")),
+            [] => unreachable!(),
+        }
+    }
+}
+
+/// Canonical form of a user-supplied name: surrounding whitespace removed
+///
+/// Names are normalized before validation wherever they are created, so
+/// `" my-app "` creates `my-app`.
+#[must_use]
+pub fn normalize_app_name(name: &str) -> &str {
+    name.trim()
+}
+
+/// Validate application name against the default policy
+pub fn validate_app_name(name: &str) -> Result<()> {
+    NamePolicy::default_ref().validate(name)
+}
+
+/// Validate a metadata key: like a default-policy name, but at most
+/// [`MAX_META_KEY_LEN`] characters and with nothing reserved
+pub fn validate_meta_key(key: &str) -> Result<()> {
+    let invalid = |reason: String| DbError::InvalidArgument(format!("metadata key {reason}"));
+
+    if key.is_empty() {
+        return Err(invalid("cannot be empty".to_string()));
+    }
+    let len = key.chars().count();
+    if len > MAX_META_KEY_LEN {
+        return Err(invalid(format!(
+            "cannot exceed {MAX_META_KEY_LEN} characters (got {len})"
+        )));
+    }
+    if !key.chars().next().unwrap().is_alphanumeric() {
+        return Err(invalid(
+            "must start with alphanumeric character".to_string(),
+        ));
+    }
+    if !key
+        .chars()
+        .all(|c| c.is_alphanumeric() || c == '-' || c == '_')
+    {
+        return Err(invalid(
+            "can only contain alphanumeric characters, hyphens, and underscores".to_string(),
+        ));
+    }
+
+    Ok(())
+}
+
+/// Validate a metadata value: any text up to [`MAX_META_VALUE_LEN`] characters
+pub fn validate_meta_value(value: &str) -> Result<()> {
+    let len = value.chars().count();
+    if len > MAX_META_VALUE_LEN {
+        return Err(DbError::InvalidArgument(format!(
+            "metadata value cannot exceed {MAX_META_VALUE_LEN} characters (got {len})"
+        )));
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_validate_app_name() {
+        // Valid names
+        assert!(validate_app_name("my-app").is_ok());
+        assert!(validate_app_name("my_app").is_ok());
+        assert!(validate_app_name("app123").is_ok());
+        assert!(validate_app_name("MyApp").is_ok());
+
+        // Invalid names
+        assert!(validate_app_name("").is_err());
+        assert!(validate_app_name("-app").is_err());
+        assert!(validate_app_name("_app").is_err());
+        assert!(validate_app_name("my app").is_err());
+        assert!(validate_app_name("my@app").is_err());
+        assert!(validate_app_name(&"a".repeat(256)).is_err());
+    }
+
+    #[test]
+    fn test_validate_meta() {
+        assert!(validate_meta_key("owner").is_ok());
+        assert!(validate_meta_key("cost_center").is_ok());
+        assert!(validate_meta_key("repo-url").is_ok());
+        // Reserved application names are fine as keys
+        assert!(validate_meta_key("admin").is_ok());
+
+        assert!(validate_meta_key("").is_err());
+        assert!(validate_meta_key("-owner").is_err());
+        assert!(validate_meta_key("owner email").is_err());
+        assert!(validate_meta_key(&"k".repeat(MAX_META_KEY_LEN + 1)).is_err());
+
+        assert!(validate_meta_value("").is_ok());
+        assert!(validate_meta_value("https://example.com/repo").is_ok());
+        assert!(validate_meta_value(&"v".repeat(MAX_META_VALUE_LEN)).is_ok());
+        let err = validate_meta_value(&"v".repeat(MAX_META_VALUE_LEN + 1)).unwrap_err();
+        assert!(matches!(err, DbError::InvalidArgument(_)));
+    }
+
+    #[test]
+    fn test_default_reserved_names() {
+        for reserved in DEFAULT_RESERVED_NAMES {
+            let err = validate_app_name(reserved).unwrap_err();
+            assert!(matches!(err, DbError::InvalidName(ref msg) if msg.contains(reserved)));
+        }
+        assert!(validate_app_name("Health").is_err());
+
+        // Near-misses are allowed
+        assert!(validate_app_name("health-check").is_ok());
+        assert!(validate_app_name("metrics2").is_ok());
+        assert!(validate_app_name("my-admin").is_ok());
+
+        // The reserved set can be cleared
+        let policy = NamePolicy {
+            reserved_names: Vec::new(),
+            ..NamePolicy::default()
+        };
+        assert!(policy.validate("health").is_ok());
+    }
+
+    #[test]
+    fn test_custom_max_len() {
+        let policy = NamePolicy {
+            max_len: 63,
+            ..NamePolicy::default()
+        };
+
+        assert!(policy.validate(&"a".repeat(63)).is_ok());
+        let err = policy.validate(&"a".repeat(64)).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "Invalid name: name cannot exceed 63 characters (got 64)"
+        );
+
+        // The default policy still allows longer names
+        assert!(validate_app_name(&"a".repeat(64)).is_ok());
+        assert!(validate_app_name(&"a".repeat(255)).is_ok());
+
+        // The limit counts characters, not bytes
+        assert!(policy.validate(&"é".repeat(63)).is_ok());
+    }
+
+    #[test]
+    fn test_normalize_app_name() {
+        assert_eq!(normalize_app_name("  my-app\t"), "my-app");
+        assert_eq!(normalize_app_name("my-app"), "my-app");
+        assert_eq!(normalize_app_name("   "), "");
+    }
+
+    #[test]
+    fn test_normalize_casing() {
+        let policy = |casing| NamePolicy {
+            casing,
+            ..NamePolicy::default()
+        };
+
+        assert_eq!(policy(NameCasing::Preserve).normalize(" My-App "), "My-App");
+        assert_eq!(policy(NameCasing::Lower).normalize(" My-App "), "my-app");
+        assert_eq!(policy(NameCasing::Upper).normalize(" My-App "), "MY-APP");
+        assert_eq!(policy(NameCasing::Lower).normalize("ÜBER"), "über");
+        assert!(policy(NameCasing::Lower)
+            .describe()
+            .ends_with("Names are converted to lowercase."));
+    }
+
+    #[test]
+    fn test_custom_min_len() {
+        let policy = NamePolicy {
+            min_len: 3,
+            ..NamePolicy::default()
+        };
+
+        assert!(policy.validate("abc").is_ok());
+        let err = policy.validate("ab").unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "Invalid name: name must be at least 3 characters (got 2)"
+        );
+        assert!(policy
+            .describe()
+            .starts_with("Names must be 3-255 characters"));
+
+        // The default policy still allows single-character names
+        assert!(validate_app_name("a").is_ok());
+    }
+
+    #[test]
+    fn test_dns_label_format() {
+        let dns = NamePolicy {
+            format: NameFormat::DnsLabel,
+            ..NamePolicy::default()
+        };
+
+        assert!(dns.validate("my_app").is_err());
+        assert!(validate_app_name("my_app").is_ok());
+        assert!(dns.validate("my-app").is_ok());
+        assert!(validate_app_name("my-app").is_ok());
+
+        assert!(dns.validate("app-v2").is_ok());
+        assert!(dns.validate("2fast").is_ok());
+        assert!(dns.validate("MyApp").is_err());
+        assert!(dns.validate("-app").is_err());
+        assert!(dns.validate("app-").is_err());
+        assert!(dns.validate("my.app").is_err());
+        assert!(dns.validate("café").is_err());
+        assert!(dns.validate(&"a".repeat(63)).is_ok());
+        assert!(dns.validate(&"a".repeat(64)).is_err());
+
+        // Reserved names still apply
+        assert!(dns.validate("admin").is_err());
+
+        assert!(dns
+            .describe()
+            .starts_with("Names must be 1-63 lowercase letters, digits, and hyphens"));
+    }
+
+    #[test]
+    fn test_custom_policy_allows_dots() {
+        let policy = NamePolicy {
+            allow_chars: "-_.".to_string(),
+            ..NamePolicy::default()
+        };
+
+        assert!(policy.validate("my.app").is_ok());
+        assert!(policy.validate("my.app-v2_beta").is_ok());
+        assert!(validate_app_name("my.app").is_err());
+
+        // Leading dot is still rejected by the prefix rule
+        assert!(policy.validate(".app").is_err());
+    }
+
+    #[test]
+    fn test_describe_policy() {
+        assert_eq!(
+            NamePolicy::default().describe(),
+            "Names must be 1-255 characters of alphanumeric characters, hyphens, and underscores, \
+             start with an alphanumeric character, and not be a reserved name (admin, health, metrics, system)."
+        );
+
+        let policy = NamePolicy {
+            min_len: 1,
+            max_len: 10,
+            allow_chars: String::new(),
+            require_prefix_alnum: false,
+            reserved_names: Vec::new(),
+            format: NameFormat::Default,
+            casing: NameCasing::Preserve,
+        };
+        assert_eq!(
+            policy.describe(),
+            "Names must be 1-10 characters of alphanumeric characters."
+        );
+    }
+
+    #[test]
+    fn test_custom_policy_blocks_reserved_names() {
+        let policy = NamePolicy {
+            reserved_names: vec!["admin".to_string(), "billing".to_string()],
+            ..NamePolicy::default()
+        };
+
+        assert!(policy.validate("billing").is_err());
+        let err = policy.validate("admin").unwrap_err();
+        assert!(matches!(err, DbError::InvalidName(ref msg) if msg.contains("admin")));
+        assert!(policy.validate("ADMIN").is_err());
+        assert!(policy.validate("admin-panel").is_ok());
+    }
+}
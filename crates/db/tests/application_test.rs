@@ -1,4 +1,9 @@
-use ottershipper_db::{Database, DbError};
+use ottershipper_db::{
+    AppQuery, AppStatus, Application, ApplicationView, AuditAction, CheckpointMode,
+    ConnectionStrategy, Database, DatabaseConfig, DbError, MockClock, NameFilter, SortDirection,
+    SortField,
+};
+use std::sync::Arc;
 use tempfile::tempdir;
 
 #[tokio::test]
@@ -50,6 +55,31 @@ async fn test_get_application_by_name() -> Result<(), Box<dyn std::error::Error>
     Ok(())
 }
 
+#[tokio::test]
+async fn test_get_or_err() -> Result<(), Box<dyn std::error::Error>> {
+    let temp_dir = tempdir()?;
+    let db_path = temp_dir.path().join("test.db");
+
+    let db = Database::new(&db_path).await?;
+    db.migrate().await?;
+
+    let app = db.applications().create("my-service").await?;
+
+    // Found
+    let by_id = db.applications().get_or_err(&app.id).await?;
+    assert_eq!(by_id.name, "my-service");
+    let by_name = db.applications().get_by_name_or_err("my-service").await?;
+    assert_eq!(by_name.id, app.id);
+
+    // Not found
+    let result = db.applications().get_or_err("missing-id").await;
+    assert!(matches!(result.unwrap_err(), DbError::NotFound(id) if id == "missing-id"));
+    let result = db.applications().get_by_name_or_err("does-not-exist").await;
+    assert!(matches!(result.unwrap_err(), DbError::NotFound(name) if name == "does-not-exist"));
+
+    Ok(())
+}
+
 #[tokio::test]
 async fn test_list_applications() -> Result<(), Box<dyn std::error::Error>> {
     let temp_dir = tempdir()?;
@@ -80,6 +110,448 @@ async fn test_list_applications() -> Result<(), Box<dyn std::error::Error>> {
     Ok(())
 }
 
+#[tokio::test]
+async fn test_list_uses_created_at_index() -> Result<(), Box<dyn std::error::Error>> {
+    let temp_dir = tempdir()?;
+    let db_path = temp_dir.path().join("test.db");
+
+    let db = Database::new(&db_path).await?;
+    db.migrate().await?;
+    db.close().await?;
+
+    let pool = sqlx::SqlitePool::connect(&format!("sqlite://{}", db_path.display())).await?;
+    let plan: Vec<(i64, i64, i64, String)> = sqlx::query_as(
+        "EXPLAIN QUERY PLAN SELECT id, name, created_at FROM applications ORDER BY created_at DESC, name ASC",
+    )
+    .fetch_all(&pool)
+    .await?;
+    let details: Vec<_> = plan.into_iter().map(|(_, _, _, detail)| detail).collect();
+
+    assert!(
+        details
+            .iter()
+            .any(|detail| detail.contains("idx_applications_created_at")),
+        "list should use the created_at index: {details:?}"
+    );
+    assert!(
+        !details.iter().any(|detail| detail.contains("TEMP B-TREE")),
+        "list should not sort in a temporary b-tree: {details:?}"
+    );
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_stream_applications() -> Result<(), Box<dyn std::error::Error>> {
+    use futures::TryStreamExt;
+
+    let temp_dir = tempdir()?;
+    let db_path = temp_dir.path().join("test.db");
+
+    let db = Database::new(&db_path).await?;
+    db.migrate().await?;
+
+    for i in 0..50 {
+        db.applications().create(&format!("app-{i:02}")).await?;
+    }
+
+    // Rows arrive one at a time in list order
+    let listed = db.applications().list().await?;
+    let mut stream = db.applications().stream();
+    let mut count = 0;
+    while let Some(app) = stream.try_next().await? {
+        assert_eq!(app.id, listed[count].id);
+        count += 1;
+    }
+    assert_eq!(count, 50);
+
+    // Streaming inside a transaction sees its uncommitted writes
+    let mut tx = db.begin().await?;
+    tx.applications().create("in-tx").await?;
+    let streamed: Vec<_> = tx.applications().stream().try_collect().await?;
+    assert_eq!(streamed.len(), 51);
+    tx.rollback().await?;
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_count_and_list_page() -> Result<(), Box<dyn std::error::Error>> {
+    let temp_dir = tempdir()?;
+    let db_path = temp_dir.path().join("test.db");
+
+    let db = Database::new(&db_path).await?;
+    db.migrate().await?;
+
+    assert_eq!(db.applications().count().await?, 0);
+    for i in 0..5 {
+        db.applications().create(&format!("app-{i}")).await?;
+    }
+    assert_eq!(db.applications().count().await?, 5);
+
+    // Pages follow list() order
+    let all = db.applications().list().await?;
+    let page = db.applications().list_page(Some(2), 1).await?;
+    assert_eq!(page.len(), 2);
+    assert_eq!(page[0].id, all[1].id);
+    assert_eq!(page[1].id, all[2].id);
+
+    // No limit returns the rest; past the end returns nothing
+    assert_eq!(db.applications().list_page(None, 3).await?.len(), 2);
+    assert!(db.applications().list_page(Some(2), 10).await?.is_empty());
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_recent_applications() -> Result<(), Box<dyn std::error::Error>> {
+    let temp_dir = tempdir()?;
+    let db_path = temp_dir.path().join("test.db");
+
+    let db = Database::new(&db_path).await?;
+    db.migrate().await?;
+
+    // Create five applications with distinct timestamps
+    for i in 1..=5 {
+        db.applications().create(&format!("app-{i}")).await?;
+        tokio::time::sleep(std::time::Duration::from_millis(5)).await;
+    }
+
+    // Three newest, newest first
+    let recent = db.applications().recent(3).await?;
+    let names: Vec<_> = recent.iter().map(|a| a.name.as_str()).collect();
+    assert_eq!(names, vec!["app-5", "app-4", "app-3"]);
+
+    // Out-of-range values are clamped
+    assert_eq!(db.applications().recent(1000).await?.len(), 5);
+    assert!(db.applications().recent(-1).await?.is_empty());
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_list_created_between() -> Result<(), Box<dyn std::error::Error>> {
+    let temp_dir = tempdir()?;
+    let db_path = temp_dir.path().join("test.db");
+
+    let db = Database::new(&db_path).await?;
+    db.migrate().await?;
+
+    let mut apps = Vec::new();
+    for name in ["first", "second", "third"] {
+        apps.push(db.applications().create(name).await?);
+        tokio::time::sleep(std::time::Duration::from_millis(5)).await;
+    }
+    let (first, third) = (apps[0].created_at, apps[2].created_at);
+
+    // Boundaries are inclusive
+    let all = db.applications().list_created_between(first, third).await?;
+    assert_eq!(all.len(), 3);
+
+    // Narrowing the window by one millisecond on each side keeps only the middle app
+    let middle = db
+        .applications()
+        .list_created_between(first + 1, third - 1)
+        .await?;
+    assert_eq!(middle.len(), 1);
+    assert_eq!(middle[0].name, "second");
+
+    // A single-instant window matches exactly
+    let exact = db.applications().list_created_between(first, first).await?;
+    assert_eq!(exact.len(), 1);
+    assert_eq!(exact[0].name, "first");
+
+    // Inverted range is rejected
+    let result = db.applications().list_created_between(third, first).await;
+    assert!(matches!(result.unwrap_err(), DbError::InvalidArgument(_)));
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_list_matching() -> Result<(), Box<dyn std::error::Error>> {
+    let temp_dir = tempdir()?;
+    let db_path = temp_dir.path().join("test.db");
+
+    let db = Database::new(&db_path).await?;
+    db.migrate().await?;
+
+    for name in [
+        "app-prod-api",
+        "app-prod-web",
+        "app-staging-web",
+        "app-prod",
+        "app1",
+        "app12",
+    ] {
+        db.applications().create(name).await?;
+    }
+
+    let names = |apps: Vec<ottershipper_db::Application>| {
+        apps.into_iter().map(|app| app.name).collect::<Vec<_>>()
+    };
+
+    // Trailing star matches any suffix, but needs the literal prefix in full
+    let matched = db.applications().list_matching("app-prod-*").await?;
+    assert_eq!(names(matched), ["app-prod-api", "app-prod-web"]);
+
+    // Star in the middle
+    let matched = db.applications().list_matching("app-*-web").await?;
+    assert_eq!(names(matched), ["app-prod-web", "app-staging-web"]);
+
+    // Question mark matches exactly one character
+    let matched = db.applications().list_matching("app?").await?;
+    assert_eq!(names(matched), ["app1"]);
+    let matched = db.applications().list_matching("app??").await?;
+    assert_eq!(names(matched), ["app12"]);
+
+    // Brackets are literal, not a character class matching "app1"
+    assert!(db.applications().list_matching("app[1]").await?.is_empty());
+
+    // Matching is case-sensitive and whole-name
+    assert!(db.applications().list_matching("APP*").await?.is_empty());
+    assert!(db.applications().list_matching("prod").await?.is_empty());
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_filter_by_name_length() -> Result<(), Box<dyn std::error::Error>> {
+    let temp_dir = tempdir()?;
+    let db_path = temp_dir.path().join("test.db");
+
+    let db = Database::new(&db_path).await?;
+    db.migrate().await?;
+
+    for name in ["a", "ab", "abc", "abcd", "über"] {
+        db.applications().create(name).await?;
+    }
+
+    let names = |apps: Vec<Application>| apps.into_iter().map(|app| app.name).collect::<Vec<_>>();
+    let filter = |min_len, max_len| NameFilter {
+        min_len,
+        max_len,
+        ..Default::default()
+    };
+
+    let short = db.applications().filter(&filter(None, Some(2))).await?;
+    assert_eq!(names(short), ["a", "ab"]);
+    // Lengths count characters, not bytes
+    let long = db.applications().filter(&filter(Some(4), None)).await?;
+    assert_eq!(names(long), ["abcd", "über"]);
+    let between = db.applications().filter(&filter(Some(2), Some(3))).await?;
+    assert_eq!(names(between), ["ab", "abc"]);
+    assert_eq!(
+        db.applications()
+            .filter(&NameFilter::default())
+            .await?
+            .len(),
+        5
+    );
+
+    let result = db.applications().filter(&filter(Some(3), Some(2))).await;
+    assert!(matches!(result, Err(DbError::InvalidArgument(_))));
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_filter_by_name_pattern() -> Result<(), Box<dyn std::error::Error>> {
+    let temp_dir = tempdir()?;
+    let db_path = temp_dir.path().join("test.db");
+
+    let db = Database::new(&db_path).await?;
+    db.migrate().await?;
+
+    for name in [
+        "legacy_api",
+        "legacy_web",
+        "legacy-web",
+        "svc-api",
+        "svc-web-2",
+    ] {
+        db.applications().create(name).await?;
+    }
+
+    let names = |apps: Vec<Application>| apps.into_iter().map(|app| app.name).collect::<Vec<_>>();
+
+    let deprecated = NameFilter {
+        regex: Some("^legacy_[a-z]+$".to_string()),
+        ..Default::default()
+    };
+    let matched = db.applications().filter(&deprecated).await?;
+    assert_eq!(names(matched), ["legacy_api", "legacy_web"]);
+
+    // The glob and lengths narrow the rows the regex is checked against
+    let combined = NameFilter {
+        max_len: Some(7),
+        glob: Some("svc-*".to_string()),
+        regex: Some("api|web".to_string()),
+        ..Default::default()
+    };
+    let matched = db.applications().filter(&combined).await?;
+    assert_eq!(names(matched), ["svc-api"]);
+
+    for regex in ["(unclosed", &"a".repeat(300)] {
+        let invalid = NameFilter {
+            regex: Some(regex.to_string()),
+            ..Default::default()
+        };
+        let result = db.applications().filter(&invalid).await;
+        assert!(matches!(result, Err(DbError::InvalidArgument(_))));
+    }
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_metadata() -> Result<(), Box<dyn std::error::Error>> {
+    let temp_dir = tempdir()?;
+    let db_path = temp_dir.path().join("test.db");
+
+    let db = Database::new(&db_path).await?;
+    db.migrate().await?;
+
+    let app = db.applications().create("with-meta").await?;
+    let other = db.applications().create("other").await?;
+
+    // Setting and reading back
+    db.applications()
+        .set_meta(&app.id, "owner", "team@example.com")
+        .await?;
+    db.applications()
+        .set_meta(&app.id, "cost_center", "42")
+        .await?;
+    assert_eq!(
+        db.applications().get_meta(&app.id, "owner").await?,
+        Some("team@example.com".to_string())
+    );
+    assert_eq!(db.applications().get_meta(&app.id, "missing").await?, None);
+
+    // Overwriting replaces the value
+    db.applications()
+        .set_meta(&app.id, "owner", "ops@example.com")
+        .await?;
+    assert_eq!(
+        db.applications().get_meta(&app.id, "owner").await?,
+        Some("ops@example.com".to_string())
+    );
+
+    // Listing is ordered by key and scoped to the application
+    let meta = db.applications().list_meta(&app.id).await?;
+    let keys: Vec<_> = meta.iter().map(|entry| entry.key.as_str()).collect();
+    assert_eq!(keys, ["cost_center", "owner"]);
+    assert_eq!(meta[1].value, "ops@example.com");
+    assert!(db.applications().list_meta(&other.id).await?.is_empty());
+
+    // Deleting a key
+    assert!(
+        db.applications()
+            .delete_meta(&app.id, "cost_center")
+            .await?
+    );
+    assert!(
+        !db.applications()
+            .delete_meta(&app.id, "cost_center")
+            .await?
+    );
+    assert_eq!(db.applications().list_meta(&app.id).await?.len(), 1);
+
+    // Invalid keys, oversized values, and unknown applications are rejected
+    let result = db.applications().set_meta(&app.id, "bad key", "x").await;
+    assert!(matches!(result.unwrap_err(), DbError::InvalidArgument(_)));
+    let long = "v".repeat(ottershipper_db::MAX_META_VALUE_LEN + 1);
+    let result = db.applications().set_meta(&app.id, "owner", &long).await;
+    assert!(matches!(result.unwrap_err(), DbError::InvalidArgument(_)));
+    let result = db.applications().set_meta("no-such-id", "owner", "x").await;
+    assert!(matches!(result.unwrap_err(), DbError::NotFound(_)));
+
+    // Metadata changes are audited as updates
+    let trail = db.applications().audit_trail(&app.id).await?;
+    assert_eq!(trail.len(), 1 + 3 + 1);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_metadata_deleted_with_application() -> Result<(), Box<dyn std::error::Error>> {
+    let temp_dir = tempdir()?;
+    let db_path = temp_dir.path().join("test.db");
+
+    let db = Database::new(&db_path).await?;
+    db.migrate().await?;
+
+    let app = db.applications().create("short-lived").await?;
+    let kept = db.applications().create("kept").await?;
+    db.applications().set_meta(&app.id, "owner", "me").await?;
+    db.applications().set_meta(&kept.id, "owner", "you").await?;
+
+    assert!(db.applications().delete(&app.id).await?);
+    assert!(db.applications().list_meta(&app.id).await?.is_empty());
+    assert_eq!(db.applications().list_meta(&kept.id).await?.len(), 1);
+
+    // Bulk deletes cascade too
+    db.applications().delete_many(&[kept.id.as_str()]).await?;
+    assert!(db.applications().list_meta(&kept.id).await?.is_empty());
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_find_by_meta() -> Result<(), Box<dyn std::error::Error>> {
+    let temp_dir = tempdir()?;
+    let db_path = temp_dir.path().join("test.db");
+
+    let db = Database::new(&db_path).await?;
+    db.migrate().await?;
+
+    let api = db.applications().create("api").await?;
+    let web = db.applications().create("web").await?;
+    db.applications()
+        .set_meta(&api.id, "repo", "github.com/acme/api")
+        .await?;
+    db.applications()
+        .set_meta(&web.id, "repo", "github.com/acme/web")
+        .await?;
+    db.applications()
+        .set_meta(&web.id, "mirror", "github.com/acme/api")
+        .await?;
+
+    // Only the key asked for is matched, not the same value under another key
+    let found = db
+        .applications()
+        .find_by_meta("repo", "github.com/acme/api")
+        .await?;
+    assert_eq!(
+        found.iter().map(|app| app.id.as_str()).collect::<Vec<_>>(),
+        [api.id.as_str()]
+    );
+
+    // Every match is returned
+    db.applications()
+        .set_meta(&web.id, "owner", "payments")
+        .await?;
+    db.applications()
+        .set_meta(&api.id, "owner", "payments")
+        .await?;
+    let found = db.applications().find_by_meta("owner", "payments").await?;
+    assert_eq!(
+        found
+            .iter()
+            .map(|app| app.name.as_str())
+            .collect::<Vec<_>>(),
+        ["api", "web"]
+    );
+
+    assert!(db
+        .applications()
+        .find_by_meta("repo", "github.com/acme/API")
+        .await?
+        .is_empty());
+
+    Ok(())
+}
+
 #[tokio::test]
 async fn test_delete_application() -> Result<(), Box<dyn std::error::Error>> {
     let temp_dir = tempdir()?;
@@ -111,114 +583,1215 @@ async fn test_delete_application() -> Result<(), Box<dyn std::error::Error>> {
 }
 
 #[tokio::test]
-async fn test_duplicate_name_fails() -> Result<(), Box<dyn std::error::Error>> {
+async fn test_delete_application_by_name() -> Result<(), Box<dyn std::error::Error>> {
     let temp_dir = tempdir()?;
     let db_path = temp_dir.path().join("test.db");
 
     let db = Database::new(&db_path).await?;
     db.migrate().await?;
 
-    // Create first application
-    db.applications().create("duplicate").await?;
+    db.applications().create("named-delete").await?;
 
-    // Try to create with same name (should return DuplicateName error)
-    let result = db.applications().create("duplicate").await;
-    assert!(result.is_err());
-    assert!(matches!(result.unwrap_err(), DbError::DuplicateName(_)));
+    // Delete existing name
+    assert!(db.applications().delete_by_name("named-delete").await?);
+    assert!(db
+        .applications()
+        .get_by_name("named-delete")
+        .await?
+        .is_none());
+
+    // Nonexistent name (including the one just deleted)
+    assert!(!db.applications().delete_by_name("named-delete").await?);
+    assert!(!db.applications().delete_by_name("never-existed").await?);
 
     Ok(())
 }
 
-// Edge case tests
+#[tokio::test]
+async fn test_delete_many_applications() -> Result<(), Box<dyn std::error::Error>> {
+    let temp_dir = tempdir()?;
+    let db_path = temp_dir.path().join("test.db");
+
+    let db = Database::new(&db_path).await?;
+    db.migrate().await?;
+
+    let a = db.applications().create("bulk-a").await?;
+    let b = db.applications().create("bulk-b").await?;
+    db.applications().create("bulk-keep").await?;
+
+    // Empty input is a no-op
+    assert_eq!(db.applications().delete_many(&[]).await?, 0);
+
+    // Mix of existing and nonexistent IDs counts only real deletions
+    let deleted = db
+        .applications()
+        .delete_many(&[&a.id, "fake-id", &b.id])
+        .await?;
+    assert_eq!(deleted, 2);
+
+    let names: Vec<_> = db
+        .applications()
+        .list()
+        .await?
+        .into_iter()
+        .map(|a| a.name)
+        .collect();
+    assert_eq!(names, vec!["bulk-keep".to_string()]);
+
+    // Inputs larger than one chunk are handled
+    let keep = db.applications().get_by_name("bulk-keep").await?.unwrap();
+    let mut ids: Vec<String> = (0..1200).map(|i| format!("missing-{i}")).collect();
+    ids.push(keep.id.clone());
+    let ids: Vec<&str> = ids.iter().map(String::as_str).collect();
+    assert_eq!(db.applications().delete_many(&ids).await?, 1);
+    assert!(db.applications().list().await?.is_empty());
+
+    Ok(())
+}
 
 #[tokio::test]
-async fn test_empty_name_validation() -> Result<(), Box<dyn std::error::Error>> {
+async fn test_list_after_is_stable_across_deletes() -> Result<(), Box<dyn std::error::Error>> {
     let temp_dir = tempdir()?;
     let db_path = temp_dir.path().join("test.db");
 
     let db = Database::new(&db_path).await?;
     db.migrate().await?;
 
-    // Empty name should fail
-    let result = db.applications().create("").await;
-    assert!(result.is_err());
-    assert!(matches!(result.unwrap_err(), DbError::InvalidName(_)));
+    // Many of these share a created_at millisecond, exercising the id tiebreak
+    for i in 0..10 {
+        db.applications().create(&format!("app-{i}")).await?;
+    }
+    let initial: Vec<_> = db
+        .applications()
+        .list_after(None, 100)
+        .await?
+        .into_iter()
+        .map(|app| app.id)
+        .collect();
+    assert_eq!(initial.len(), 10);
+
+    let mut seen = Vec::new();
+    let mut cursor: Option<(i64, String)> = None;
+    loop {
+        let page = db
+            .applications()
+            .list_after(cursor.as_ref().map(|(at, id)| (*at, id.as_str())), 3)
+            .await?;
+        let Some(last) = page.last() else {
+            break;
+        };
+        cursor = Some((last.created_at, last.id.clone()));
+        seen.extend(page.iter().map(|app| app.id.clone()));
+
+        // Mid-iteration, delete a row already returned and one not yet reached;
+        // with offsets this would skip a row
+        if seen.len() == 3 {
+            db.applications().delete(&initial[0]).await?;
+            db.applications().delete(&initial[9]).await?;
+        }
+    }
+
+    // Every surviving row was returned exactly once, in order
+    assert_eq!(seen, initial[..9]);
 
     Ok(())
 }
 
 #[tokio::test]
-async fn test_invalid_name_validation() -> Result<(), Box<dyn std::error::Error>> {
+async fn test_upsert_by_name() -> Result<(), Box<dyn std::error::Error>> {
     let temp_dir = tempdir()?;
     let db_path = temp_dir.path().join("test.db");
 
     let db = Database::new(&db_path).await?;
     db.migrate().await?;
 
-    // Name with spaces
-    let result = db.applications().create("my app").await;
-    assert!(matches!(result.unwrap_err(), DbError::InvalidName(_)));
+    // Insert path
+    let (app, inserted) = db
+        .applications()
+        .upsert_by_name("declared", Some("first"))
+        .await?;
+    assert!(inserted);
+    assert_eq!(app.name, "declared");
+    assert_eq!(app.description.as_deref(), Some("first"));
 
-    // Name starting with hyphen
-    let result = db.applications().create("-myapp").await;
-    assert!(matches!(result.unwrap_err(), DbError::InvalidName(_)));
+    tokio::time::sleep(std::time::Duration::from_millis(5)).await;
 
-    // Name with special characters
-    let result = db.applications().create("my@app").await;
-    assert!(matches!(result.unwrap_err(), DbError::InvalidName(_)));
+    // Update path keeps the ID and created_at
+    let (updated, inserted) = db
+        .applications()
+        .upsert_by_name("declared", Some("second"))
+        .await?;
+    assert!(!inserted);
+    assert_eq!(updated.id, app.id);
+    assert_eq!(updated.created_at, app.created_at);
+    assert_eq!(updated.description.as_deref(), Some("second"));
 
-    // Name too long
-    let result = db.applications().create(&"a".repeat(256)).await;
+    // Omitting the description clears it
+    let (cleared, _) = db.applications().upsert_by_name("declared", None).await?;
+    assert_eq!(cleared.description, None);
+    assert_eq!(db.applications().count().await?, 1);
+
+    // Invalid names are rejected before touching the table
+    let result = db.applications().upsert_by_name("bad name", None).await;
     assert!(matches!(result.unwrap_err(), DbError::InvalidName(_)));
 
+    // Each upsert is audited as a create or an update
+    let actions: Vec<_> = db
+        .applications()
+        .audit_trail(&app.id)
+        .await?
+        .into_iter()
+        .map(|entry| entry.action)
+        .collect();
+    assert_eq!(
+        actions,
+        vec![
+            AuditAction::Create,
+            AuditAction::Update,
+            AuditAction::Update
+        ]
+    );
+
     Ok(())
 }
 
 #[tokio::test]
-async fn test_migration_idempotency() -> Result<(), Box<dyn std::error::Error>> {
+async fn test_duplicate_name_fails() -> Result<(), Box<dyn std::error::Error>> {
     let temp_dir = tempdir()?;
     let db_path = temp_dir.path().join("test.db");
 
     let db = Database::new(&db_path).await?;
-
-    // Run migration multiple times
-    db.migrate().await?;
-    db.migrate().await?;
     db.migrate().await?;
 
-    // Should still work
-    let app = db.applications().create("test").await?;
-    assert_eq!(app.name, "test");
+    // Create first application
+    db.applications().create("duplicate").await?;
+
+    // Try to create with same name (should return DuplicateName error)
+    let result = db.applications().create("duplicate").await;
+    assert!(result.is_err());
+    assert!(matches!(result.unwrap_err(), DbError::DuplicateName(_)));
 
     Ok(())
 }
 
 #[tokio::test]
-async fn test_concurrent_creates() -> Result<(), Box<dyn std::error::Error>> {
+async fn test_duplicate_name_in_transaction_fails() -> Result<(), Box<dyn std::error::Error>> {
     let temp_dir = tempdir()?;
     let db_path = temp_dir.path().join("test.db");
 
     let db = Database::new(&db_path).await?;
     db.migrate().await?;
+    db.applications().create("duplicate").await?;
 
-    // Create multiple applications concurrently
-    let handles: Vec<_> = (0..10)
-        .map(|i| {
-            let db_clone = db.clone();
-            tokio::spawn(async move { db_clone.applications().create(&format!("app-{i}")).await })
-        })
-        .collect();
+    // Duplicates are detected on a transaction's connection and with an idempotency key too
+    let mut tx = db.begin().await?;
+    let result = tx.applications().create("duplicate").await;
+    assert!(matches!(result.unwrap_err(), DbError::DuplicateName(name) if name == "duplicate"));
+    tx.rollback().await?;
 
-    // Wait for all to complete
-    let results: Vec<_> = futures::future::join_all(handles).await;
+    let result = db
+        .applications()
+        .create_with_idempotency_key("duplicate", "key-1")
+        .await;
+    assert!(matches!(result.unwrap_err(), DbError::DuplicateName(_)));
 
-    // All should succeed
-    let success_count = results.iter().filter(|r| r.is_ok()).count();
-    assert_eq!(success_count, 10);
+    Ok(())
+}
 
-    // Verify all 10 apps exist
-    let apps = db.applications().list().await?;
-    assert_eq!(apps.len(), 10);
+#[tokio::test]
+async fn test_repository_via_transaction() -> Result<(), Box<dyn std::error::Error>> {
+    let temp_dir = tempdir()?;
+    let db_path = temp_dir.path().join("test.db");
+
+    let db = Database::new(&db_path).await?;
+    db.migrate().await?;
+
+    // Same operations via a transaction handle
+    let mut tx = db.begin().await?;
+    let app = tx.applications().create("tx-app").await?;
+    assert_eq!(app.name, "tx-app");
+
+    let fetched = tx.applications().get(&app.id).await?;
+    assert_eq!(fetched.unwrap().name, "tx-app");
+    let by_name = tx.applications().get_by_name("tx-app").await?;
+    assert_eq!(by_name.unwrap().id, app.id);
+    assert_eq!(tx.applications().list().await?.len(), 1);
+
+    // Duplicate detection behaves identically inside a transaction
+    let result = tx.applications().create("tx-app").await;
+    assert!(matches!(result.unwrap_err(), DbError::DuplicateName(_)));
+    tx.commit().await?;
+
+    // Committed row is visible via the pool
+    let fetched = db.applications().get(&app.id).await?;
+    assert_eq!(fetched.unwrap().name, "tx-app");
+
+    // Rolled back changes are not
+    let mut tx = db.begin().await?;
+    tx.applications().create("rolled-back").await?;
+    assert!(tx.applications().delete(&app.id).await?);
+    tx.rollback().await?;
+
+    let names: Vec<_> = db
+        .applications()
+        .list()
+        .await?
+        .into_iter()
+        .map(|a| a.name)
+        .collect();
+    assert_eq!(names, vec!["tx-app".to_string()]);
+
+    Ok(())
+}
+
+// Edge case tests
+
+#[tokio::test]
+async fn test_empty_name_validation() -> Result<(), Box<dyn std::error::Error>> {
+    let temp_dir = tempdir()?;
+    let db_path = temp_dir.path().join("test.db");
+
+    let db = Database::new(&db_path).await?;
+    db.migrate().await?;
+
+    // Empty name should fail
+    let result = db.applications().create("").await;
+    assert!(result.is_err());
+    assert!(matches!(result.unwrap_err(), DbError::InvalidName(_)));
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_invalid_name_validation() -> Result<(), Box<dyn std::error::Error>> {
+    let temp_dir = tempdir()?;
+    let db_path = temp_dir.path().join("test.db");
+
+    let db = Database::new(&db_path).await?;
+    db.migrate().await?;
+
+    // Name with spaces
+    let result = db.applications().create("my app").await;
+    assert!(matches!(result.unwrap_err(), DbError::InvalidName(_)));
+
+    // Name starting with hyphen
+    let result = db.applications().create("-myapp").await;
+    assert!(matches!(result.unwrap_err(), DbError::InvalidName(_)));
+
+    // Name with special characters
+    let result = db.applications().create("my@app").await;
+    assert!(matches!(result.unwrap_err(), DbError::InvalidName(_)));
+
+    // Name too long
+    let result = db.applications().create(&"a".repeat(256)).await;
+    assert!(matches!(result.unwrap_err(), DbError::InvalidName(_)));
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_reserved_name_validation() -> Result<(), Box<dyn std::error::Error>> {
+    let temp_dir = tempdir()?;
+    let db_path = temp_dir.path().join("test.db");
+
+    let db = Database::new(&db_path).await?;
+    db.migrate().await?;
+
+    // Reserved names are rejected
+    let result = db.applications().create("health").await;
+    assert!(matches!(result.unwrap_err(), DbError::InvalidName(_)));
+
+    // Names that merely contain a reserved word are fine
+    let app = db.applications().create("health-check").await?;
+    assert_eq!(app.name, "health-check");
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_migration_idempotency() -> Result<(), Box<dyn std::error::Error>> {
+    let temp_dir = tempdir()?;
+    let db_path = temp_dir.path().join("test.db");
+
+    let db = Database::new(&db_path).await?;
+    assert_eq!(db.migration_count().await?, 0);
+    assert_eq!(db.schema_version().await?, None);
+
+    // Run migration multiple times
+    db.migrate().await?;
+    let applied = db.migration_count().await?;
+    assert!(applied > 0);
+    db.migrate().await?;
+    db.migrate().await?;
+    assert_eq!(db.migration_count().await?, applied);
+    assert_eq!(
+        db.schema_version().await?.as_deref(),
+        Some(Database::latest_schema_version())
+    );
+    let status = db.migration_status().await?;
+    assert_eq!(
+        Database::latest_schema_version(),
+        status.last().unwrap().name
+    );
+
+    // Should still work
+    let app = db.applications().create("test").await?;
+    assert_eq!(app.name, "test");
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_close_checkpoints_wal() -> Result<(), Box<dyn std::error::Error>> {
+    let temp_dir = tempdir()?;
+    let db_path = temp_dir.path().join("test.db");
+    let wal_path = temp_dir.path().join("test.db-wal");
+
+    let db = Database::new(&db_path).await?;
+    db.migrate().await?;
+    let app = db.applications().create("persisted").await?;
+    assert!(wal_path.exists());
+
+    db.close().await?;
+
+    // WAL is either removed or truncated to zero bytes
+    let wal_len = std::fs::metadata(&wal_path).map_or(0, |m| m.len());
+    assert_eq!(wal_len, 0);
+
+    // Data survives a reopen
+    let db = Database::new(&db_path).await?;
+    let fetched = db.applications().get(&app.id).await?;
+    assert_eq!(fetched.unwrap().name, "persisted");
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_read_only_mode() -> Result<(), Box<dyn std::error::Error>> {
+    let temp_dir = tempdir()?;
+    let db_path = temp_dir.path().join("test.db");
+
+    let db = Database::new(&db_path).await?;
+    db.migrate().await?;
+    let app = db.applications().create("existing").await?;
+    db.close().await?;
+
+    let config = DatabaseConfig {
+        read_only: true,
+        ..Default::default()
+    };
+    let db = Database::new_with_config(&db_path, config.clone()).await?;
+    assert!(db.is_read_only());
+
+    // Reads succeed
+    let fetched = db.applications().get(&app.id).await?;
+    assert_eq!(fetched.unwrap().name, "existing");
+    assert_eq!(db.applications().list().await?.len(), 1);
+
+    // Writes fail fast
+    let result = db.applications().create("new-app").await;
+    assert!(matches!(result.unwrap_err(), DbError::ReadOnly));
+    let result = db.applications().delete(&app.id).await;
+    assert!(matches!(result.unwrap_err(), DbError::ReadOnly));
+    let mut tx = db.begin().await?;
+    let result = tx.applications().create("new-app").await;
+    assert!(matches!(result.unwrap_err(), DbError::ReadOnly));
+    drop(tx);
+    assert!(matches!(db.migrate().await.unwrap_err(), DbError::ReadOnly));
+
+    // A missing file is not created in read-only mode
+    let missing = temp_dir.path().join("missing.db");
+    assert!(Database::new_with_config(&missing, config).await.is_err());
+    assert!(!missing.exists());
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_from_file_url() -> Result<(), Box<dyn std::error::Error>> {
+    let temp_dir = tempdir()?;
+    let db_path = temp_dir.path().join("test.db");
+    let url = format!("sqlite://{}?mode=rwc", db_path.display());
+
+    let db = Database::from_url(&url, DatabaseConfig::default()).await?;
+    db.migrate().await?;
+    db.applications().create("from-url").await?;
+    // WAL is still applied by default
+    assert!(db.checkpoint(CheckpointMode::Passive).await?.is_some());
+    db.close().await?;
+
+    // mode=ro opens read-only
+    let db = Database::from_url(
+        &format!("sqlite://{}?mode=ro", db_path.display()),
+        DatabaseConfig::default(),
+    )
+    .await?;
+    assert!(db.is_read_only());
+    assert!(db.applications().get_by_name("from-url").await?.is_some());
+    let result = db.applications().create("other").await;
+    assert!(matches!(result.unwrap_err(), DbError::ReadOnly));
+    db.close().await?;
+
+    // journal_mode in the URL overrides the WAL default, and is URL-decoded
+    let db = Database::from_url(
+        &format!(
+            "sqlite://{}?journal_mode=DELETE&busy_timeout=100",
+            db_path.display()
+        ),
+        DatabaseConfig::default(),
+    )
+    .await?;
+    assert!(db.checkpoint(CheckpointMode::Passive).await?.is_none());
+    db.close().await?;
+
+    let db = Database::from_url(
+        &format!("sqlite://{}?journal%5Fmode=%77al", db_path.display()),
+        DatabaseConfig::default(),
+    )
+    .await?;
+    assert!(db.checkpoint(CheckpointMode::Passive).await?.is_some());
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_from_memory_url() -> Result<(), Box<dyn std::error::Error>> {
+    for url in [
+        "sqlite::memory:",
+        "sqlite:file:from-memory-url?mode=memory&cache=shared",
+    ] {
+        let db = Database::from_url(url, DatabaseConfig::default()).await?;
+        db.migrate().await?;
+        db.applications().create("in-memory").await?;
+
+        // Every pooled connection sees the same in-memory database
+        assert_eq!(db.applications().count().await?, 1);
+        assert!(db.applications().exists_by_name("in-memory").await?);
+        db.close().await?;
+    }
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_in_memory_persists_across_calls() -> Result<(), Box<dyn std::error::Error>> {
+    let db = Database::new_in_memory().await?;
+    db.migrate().await?;
+
+    let created = db.applications().create("remembered").await?;
+    tokio::task::yield_now().await;
+
+    let apps = db.applications().list().await?;
+    assert_eq!(apps.len(), 1);
+    assert_eq!(apps[0].id, created.id);
+
+    // A clone shares the same database
+    let clone = db.clone();
+    assert!(clone.applications().exists_by_name("remembered").await?);
+
+    // A plain ":memory:" path gets the same treatment
+    let db = Database::new(":memory:").await?;
+    db.migrate().await?;
+    db.applications().create("path-memory").await?;
+    assert_eq!(db.applications().list().await?.len(), 1);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_from_invalid_url() {
+    for url in [
+        "sqlite://test.db?bogus=1",
+        "sqlite://test.db?busy_timeout=soon",
+        "sqlite://test.db?mode=sideways",
+        "sqlite://test.db?journal_mode=sideways",
+        "sqlite://test.db?journal_mode=WAL;%20PRAGMA%20foreign_keys=OFF",
+    ] {
+        let result = Database::from_url(url, DatabaseConfig::default()).await;
+        assert!(
+            matches!(result, Err(DbError::InvalidArgument(_))),
+            "{url} should be rejected"
+        );
+    }
+}
+
+#[tokio::test]
+async fn test_concurrent_migrations() -> Result<(), Box<dyn std::error::Error>> {
+    let temp_dir = tempdir()?;
+    let db_path = temp_dir.path().join("test.db");
+
+    // Two independent pools, as if two server processes started together
+    let first = Database::new(&db_path).await?;
+    let second = Database::new(&db_path).await?;
+
+    let (a, b) = tokio::join!(first.migrate(), second.migrate());
+    a?;
+    b?;
+
+    // Every migration was recorded exactly once
+    let status = first.migration_status().await?;
+    assert!(status.iter().all(|m| m.applied_at.is_some()));
+    assert_eq!(first.migration_count().await?, i64::try_from(status.len())?);
+    second.applications().create("after-race").await?;
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_concurrent_creates() -> Result<(), Box<dyn std::error::Error>> {
+    let temp_dir = tempdir()?;
+    let db_path = temp_dir.path().join("test.db");
+
+    let db = Database::new(&db_path).await?;
+    db.migrate().await?;
+
+    // Create multiple applications concurrently
+    let handles: Vec<_> = (0..10)
+        .map(|i| {
+            let db_clone = db.clone();
+            tokio::spawn(async move { db_clone.applications().create(&format!("app-{i}")).await })
+        })
+        .collect();
+
+    // Wait for all to complete
+    let results: Vec<_> = futures::future::join_all(handles).await;
+
+    // All should succeed
+    let success_count = results.iter().filter(|r| r.is_ok()).count();
+    assert_eq!(success_count, 10);
+
+    // Verify all 10 apps exist
+    let apps = db.applications().list().await?;
+    assert_eq!(apps.len(), 10);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_concurrent_create_if_absent() -> Result<(), Box<dyn std::error::Error>> {
+    let temp_dir = tempdir()?;
+    let db_path = temp_dir.path().join("test.db");
+
+    let db = Database::new(&db_path).await?;
+    db.migrate().await?;
+
+    let handles: Vec<_> = (0..10)
+        .map(|_| {
+            let db_clone = db.clone();
+            tokio::spawn(async move { db_clone.applications().create_if_absent("shared").await })
+        })
+        .collect();
+
+    let mut results = Vec::new();
+    for handle in futures::future::join_all(handles).await {
+        results.push(handle??);
+    }
+
+    // Exactly one call inserted, and everyone got the same row
+    assert_eq!(results.iter().filter(|(_, inserted)| *inserted).count(), 1);
+    let first = &results[0].0;
+    assert!(results.iter().all(|(app, _)| app.id == first.id));
+    assert_eq!(db.applications().count().await?, 1);
+
+    // Only the insert is audited
+    assert_eq!(db.applications().audit_trail(&first.id).await?.len(), 1);
+
+    Ok(())
+}
+
+#[test]
+fn test_application_view_formats_created_at() {
+    let app = Application {
+        id: "id-1".to_string(),
+        name: "viewed".to_string(),
+        created_at: 1_706_702_400_123,
+        description: Some("shown".to_string()),
+        status: AppStatus::Archived,
+        updated_at: 1_706_702_401_000,
+        owner: None,
+    };
+
+    let view = ApplicationView::from(&app);
+    assert_eq!(view.created_at, "2024-01-31T12:00:00.123Z");
+    assert_eq!(view.updated_at, "2024-01-31T12:00:01.000Z");
+    assert_eq!(view.name, "viewed");
+    assert_eq!(view.description.as_deref(), Some("shown"));
+    assert_eq!(view.status, AppStatus::Archived);
+
+    let epoch = ApplicationView::from(&Application {
+        created_at: 0,
+        ..app
+    });
+    assert_eq!(epoch.created_at, "1970-01-01T00:00:00.000Z");
+}
+
+#[tokio::test]
+async fn test_touch_raises_updated_at() -> Result<(), Box<dyn std::error::Error>> {
+    let temp_dir = tempdir()?;
+    let clock = Arc::new(MockClock::new(1_000));
+    let db = Database::new(temp_dir.path().join("test.db"))
+        .await?
+        .with_clock(clock.clone());
+    db.migrate().await?;
+
+    let app = db.applications().create("heartbeat").await?;
+    assert_eq!(app.updated_at, app.created_at);
+
+    clock.set(5_000);
+    assert!(db.applications().touch(&app.id).await?);
+    let touched = db.applications().get_or_err(&app.id).await?;
+    assert_eq!(touched.updated_at, 5_000);
+    assert_eq!(touched.created_at, 1_000);
+    assert_eq!(touched.name, "heartbeat");
+    // Touches aren't audited
+    assert_eq!(db.applications().audit_trail(&app.id).await?.len(), 1);
+
+    // Real changes move it too
+    clock.set(9_000);
+    let renamed = db.applications().rename(&app.id, "heartbeat-2").await?;
+    assert_eq!(renamed.updated_at, 9_000);
+
+    assert!(!db.applications().touch("no-such-id").await?);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_mock_clock_controls_timestamps() -> Result<(), Box<dyn std::error::Error>> {
+    let temp_dir = tempdir()?;
+    let db_path = temp_dir.path().join("test.db");
+
+    let clock = Arc::new(MockClock::new(1_000));
+    let db = Database::new(&db_path).await?.with_clock(clock.clone());
+    db.migrate().await?;
+
+    let older = db.applications().create("zebra").await?;
+    clock.set(2_000);
+    let newer = db.applications().create("aardvark").await?;
+    assert_eq!(older.created_at, 1_000);
+    assert_eq!(newer.created_at, 2_000);
+
+    // Newest first, even though the name order says otherwise
+    let names: Vec<_> = db
+        .applications()
+        .list()
+        .await?
+        .into_iter()
+        .map(|app| app.name)
+        .collect();
+    assert_eq!(names, ["aardvark", "zebra"]);
+
+    // Transactions and the audit log use the same clock
+    clock.set(3_000);
+    let mut tx = db.begin().await?;
+    let in_tx = tx.applications().create("in-tx").await?;
+    tx.commit().await?;
+    assert_eq!(in_tx.created_at, 3_000);
+    assert_eq!(db.applications().audit_trail(&in_tx.id).await?[0].at, 3_000);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_archived_apps_hidden_from_listings() -> Result<(), Box<dyn std::error::Error>> {
+    let temp_dir = tempdir()?;
+    let db_path = temp_dir.path().join("test.db");
+
+    let db = Database::new(&db_path).await?;
+    db.migrate().await?;
+
+    let shelved = db.applications().create("shelved").await?;
+    db.applications().create("in-use").await?;
+    assert_eq!(shelved.status, AppStatus::Active);
+
+    let archived = db
+        .applications()
+        .set_status(&shelved.id, AppStatus::Archived)
+        .await?;
+    assert_eq!(archived.status, AppStatus::Archived);
+    assert_eq!(db.applications().audit_trail(&shelved.id).await?.len(), 2);
+
+    // Hidden only when asked, and still gettable
+    assert_eq!(db.applications().count().await?, 2);
+    assert_eq!(db.applications().hide_archived().count().await?, 1);
+    let visible = db.applications().hide_archived().list().await?;
+    assert_eq!(visible.len(), 1);
+    assert_eq!(visible[0].name, "in-use");
+    assert_eq!(
+        db.applications()
+            .hide_archived()
+            .list_page(None, 0)
+            .await?
+            .len(),
+        1
+    );
+    assert_eq!(
+        db.applications()
+            .hide_archived()
+            .list_after(None, 10)
+            .await?
+            .len(),
+        1
+    );
+    assert!(db.applications().get(&shelved.id).await?.is_some());
+
+    // Setting the current status again is a no-op
+    db.applications()
+        .set_status(&shelved.id, AppStatus::Archived)
+        .await?;
+    assert_eq!(db.applications().audit_trail(&shelved.id).await?.len(), 2);
+
+    let result = db
+        .applications()
+        .set_status("missing", AppStatus::Archived)
+        .await;
+    assert!(matches!(result, Err(DbError::NotFound(_))));
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_change_marker() -> Result<(), Box<dyn std::error::Error>> {
+    let temp_dir = tempdir()?;
+    let db_path = temp_dir.path().join("test.db");
+
+    let clock = Arc::new(MockClock::new(1_000));
+    let db = Database::new(&db_path).await?.with_clock(clock.clone());
+    db.migrate().await?;
+    assert_eq!(db.applications().change_marker().await?, (0, 0, 0));
+
+    let api = db.applications().create("api").await?;
+    clock.set(2_000);
+    let web = db.applications().create("web").await?;
+    let created = db.applications().change_marker().await?;
+    assert_eq!((created.0, created.1), (2, 2_000));
+
+    // Updates move the latest change, deletes the count
+    clock.set(3_000);
+    db.applications().rename(&api.id, "api-v2").await?;
+    let renamed = db.applications().change_marker().await?;
+    assert_eq!((renamed.0, renamed.1), (2, 3_000));
+    db.applications().delete(&web.id).await?;
+    let deleted = db.applications().change_marker().await?;
+    assert_eq!((deleted.0, deleted.1), (1, 3_000));
+
+    // A change within the same millisecond still moves the marker
+    db.applications().rename(&api.id, "api-v3").await?;
+    assert_ne!(db.applications().change_marker().await?, deleted);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_count_by_status() -> Result<(), Box<dyn std::error::Error>> {
+    let temp_dir = tempdir()?;
+    let db_path = temp_dir.path().join("test.db");
+
+    let db = Database::new(&db_path).await?;
+    db.migrate().await?;
+
+    // Statuses nobody has are still reported
+    assert_eq!(
+        db.applications().count_by_status().await?,
+        [(AppStatus::Active, 0), (AppStatus::Archived, 0)]
+    );
+
+    for name in ["a", "b", "c", "d", "e"] {
+        let app = db.applications().create(name).await?;
+        if matches!(name, "b" | "d") {
+            db.applications()
+                .set_status(&app.id, AppStatus::Archived)
+                .await?;
+        }
+    }
+    assert_eq!(
+        db.applications().count_by_status().await?,
+        [(AppStatus::Active, 3), (AppStatus::Archived, 2)]
+    );
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_rename_and_list_with_prefix() -> Result<(), Box<dyn std::error::Error>> {
+    let temp_dir = tempdir()?;
+    let db_path = temp_dir.path().join("test.db");
+
+    let db = Database::new(&db_path).await?;
+    db.migrate().await?;
+
+    let api = db.applications().create("svc-api").await?;
+    db.applications().create("svc-web").await?;
+    db.applications().create("other-svc-db").await?;
+
+    let names: Vec<_> = db
+        .applications()
+        .list_with_prefix("svc-")
+        .await?
+        .into_iter()
+        .map(|app| app.name)
+        .collect();
+    assert_eq!(names, ["svc-api", "svc-web"]);
+    // Glob characters are not special in a prefix
+    assert!(db.applications().list_with_prefix("*").await?.is_empty());
+
+    let renamed = db.applications().rename(&api.id, "team-a-api").await?;
+    assert_eq!(renamed.id, api.id);
+    assert_eq!(renamed.name, "team-a-api");
+    assert_eq!(renamed.created_at, api.created_at);
+    assert_eq!(db.applications().audit_trail(&api.id).await?.len(), 2);
+
+    let result = db.applications().rename(&api.id, "svc-web").await;
+    assert!(matches!(result, Err(DbError::DuplicateName(_))));
+    let result = db.applications().rename(&api.id, "-bad").await;
+    assert!(matches!(result, Err(DbError::InvalidName(_))));
+    let result = db.applications().rename("missing", "anything").await;
+    assert!(matches!(result, Err(DbError::NotFound(_))));
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_names_unique_across_owners_by_default() -> Result<(), Box<dyn std::error::Error>> {
+    let temp_dir = tempdir()?;
+    let db = Database::new(temp_dir.path().join("test.db")).await?;
+    db.migrate().await?;
+    assert!(!db.names_scoped_by_owner().await?);
+
+    let api = db.applications().with_owner("team-a").create("api").await?;
+    assert_eq!(api.owner.as_deref(), Some("team-a"));
+
+    let result = db.applications().with_owner("team-b").create("api").await;
+    assert!(matches!(result, Err(DbError::DuplicateName(name)) if name == "api"));
+    let result = db
+        .applications()
+        .with_owner("team-b")
+        .create_if_absent("api")
+        .await;
+    assert!(matches!(result, Err(DbError::DuplicateName(_))));
+
+    // A name identifies one application whoever owns it, so lookups agree
+    // with what create reports
+    for owner in ["", "team-a", "team-b"] {
+        let repo = db.applications().with_owner(owner);
+        assert!(repo.exists_by_name("api").await?, "{owner:?}");
+        let found = repo.get_by_name("api").await?;
+        assert_eq!(found.map(|app| app.id), Some(api.id.clone()), "{owner:?}");
+    }
+    assert!(
+        db.applications()
+            .with_owner("team-b")
+            .delete_by_name("api")
+            .await?
+    );
+    assert!(db.applications().get(&api.id).await?.is_none());
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_scope_names_by_owner() -> Result<(), Box<dyn std::error::Error>> {
+    let temp_dir = tempdir()?;
+    let db = Database::new(temp_dir.path().join("test.db")).await?;
+    db.migrate().await?;
+
+    let existing = db.applications().create("billing").await?;
+    db.applications()
+        .set_meta(&existing.id, "tier", "1")
+        .await?;
+    let trail = db.applications().audit_trail(&existing.id).await?.len();
+
+    assert!(db.scope_names_by_owner().await?);
+    assert!(db.names_scoped_by_owner().await?);
+    assert!(!db.scope_names_by_owner().await?, "already scoped");
+
+    // The rebuild keeps rows, their metadata, and their audit trail
+    let kept = db.applications().get_by_name_or_err("billing").await?;
+    assert_eq!(kept.id, existing.id);
+    assert_eq!(
+        db.applications()
+            .get_meta(&kept.id, "tier")
+            .await?
+            .as_deref(),
+        Some("1")
+    );
+    assert_eq!(db.applications().audit_trail(&kept.id).await?.len(), trail);
+
+    let a = db.applications().with_owner("team-a").create("api").await?;
+    let b = db.applications().with_owner("team-b").create("api").await?;
+    assert_ne!(a.id, b.id);
+    assert_eq!(b.owner.as_deref(), Some("team-b"));
+
+    let result = db.applications().with_owner("team-a").create("api").await;
+    assert!(matches!(result, Err(DbError::DuplicateName(name)) if name == "api"));
+    let result = db
+        .applications()
+        .with_owner("team-a")
+        .rename(&kept.id, "api")
+        .await;
+    assert!(result.is_ok(), "unowned names don't collide with team-a's");
+
+    let (same, inserted) = db
+        .applications()
+        .with_owner("team-b")
+        .create_if_absent("api")
+        .await?;
+    assert!(!inserted);
+    assert_eq!(same.id, b.id);
+
+    let found = db
+        .applications()
+        .with_owner("team-b")
+        .get_by_name("api")
+        .await?;
+    assert_eq!(found.map(|app| app.id), Some(b.id));
+    assert!(
+        db.applications()
+            .with_owner("team-a")
+            .delete_by_name("api")
+            .await?
+    );
+    assert!(db.applications().get_or_err(&a.id).await.is_err());
+
+    // Deleting still cascades to metadata once foreign keys are back on
+    db.applications().delete(&kept.id).await?;
+    assert!(db
+        .applications()
+        .get_meta(&kept.id, "tier")
+        .await?
+        .is_none());
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_query_filters_sorts_and_pages() -> Result<(), Box<dyn std::error::Error>> {
+    let temp_dir = tempdir()?;
+    let clock = Arc::new(MockClock::new(1_000));
+    let db = Database::new(temp_dir.path().join("test.db"))
+        .await?
+        .with_clock(clock.clone());
+    db.migrate().await?;
+
+    let api = db.applications().create("svc-api").await?;
+    let web = db.applications().create("svc-web").await?;
+    db.applications().create("tool").await?;
+    clock.advance(std::time::Duration::from_secs(1));
+    db.applications().touch(&api.id).await?;
+
+    let by_update = AppQuery {
+        name_contains: Some("svc-".to_string()),
+        sort_by: SortField::UpdatedAt,
+        direction: SortDirection::Desc,
+        ..AppQuery::default()
+    };
+    let apps = db.applications().query(&by_update).await?;
+    let names: Vec<_> = apps.iter().map(|app| app.name.as_str()).collect();
+    assert_eq!(names, ["svc-api", "svc-web"]);
+    assert_eq!(db.applications().query_count(&by_update).await?, 2);
+
+    // Equal sort keys fall back to the ID, so consecutive pages never overlap
+    let page = |offset| AppQuery {
+        limit: 1,
+        offset,
+        ..AppQuery::default()
+    };
+    let mut seen = Vec::new();
+    for offset in 0..3 {
+        seen.extend(db.applications().query(&page(offset)).await?);
+    }
+    seen.sort_by(|a, b| a.id.cmp(&b.id));
+    seen.dedup_by(|a, b| a.id == b.id);
+    assert_eq!(seen.len(), 3);
+
+    // A limit below 1 still returns a page of one, as the service reports it
+    let zero_limit = AppQuery {
+        limit: 0,
+        ..AppQuery::default()
+    };
+    assert_eq!(zero_limit.page_limit(), 1);
+    assert_eq!(db.applications().query(&zero_limit).await?.len(), 1);
+
+    db.applications()
+        .set_status(&web.id, AppStatus::Archived)
+        .await?;
+    let archived = AppQuery {
+        status: Some(AppStatus::Archived),
+        ..AppQuery::default()
+    };
+    assert_eq!(db.applications().query_count(&archived).await?, 1);
+
+    let too_long = AppQuery {
+        name_contains: Some("x".repeat(257)),
+        ..AppQuery::default()
+    };
+    let result = db.applications().query(&too_long).await;
+    assert!(matches!(result, Err(DbError::InvalidArgument(_))));
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_search_all() -> Result<(), Box<dyn std::error::Error>> {
+    let temp_dir = tempdir()?;
+    let db = Database::new(temp_dir.path().join("test.db")).await?;
+    db.migrate().await?;
+    let repo = db.applications();
+
+    let billing = repo.create("billing-api").await?;
+    let web = repo
+        .create_with_description("web", Some("Storefront for the billing team"))
+        .await?;
+    let worker = repo.create("worker").await?;
+    repo.set_meta(&worker.id, "team", "Billing").await?;
+    // Matches by name, description, and metadata, but is listed once
+    let ledger = repo
+        .create_with_description("billing-ledger", Some("billing records"))
+        .await?;
+    repo.set_meta(&ledger.id, "owner", "billing").await?;
+    repo.set_meta(&ledger.id, "team", "billing").await?;
+    repo.create("unrelated").await?;
+
+    // Name matches first, then description, then metadata; case is ignored
+    let names: Vec<_> = repo
+        .search_all("BILLING")
+        .await?
+        .into_iter()
+        .map(|app| app.id)
+        .collect();
+    assert_eq!(names, [billing.id, ledger.id, web.id, worker.id]);
+
+    assert_eq!(repo.search_all("storefront").await?.len(), 1);
+    assert_eq!(repo.search_all("nothing-like-this").await?.len(), 0);
+
+    // LIKE wildcards match literally
+    repo.create("pct_100").await?;
+    assert!(repo.search_all("%").await?.is_empty());
+    let underscored = repo.search_all("_").await?;
+    assert_eq!(underscored.len(), 1);
+    assert_eq!(underscored[0].name, "pct_100");
+    assert!(repo.search_all("\\").await?.is_empty());
+
+    for text in ["", "   ", &"x".repeat(257)] {
+        let result = repo.search_all(text).await;
+        assert!(
+            matches!(result, Err(DbError::InvalidArgument(_))),
+            "{text:?}"
+        );
+    }
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_search_fts() -> Result<(), Box<dyn std::error::Error>> {
+    let temp_dir = tempdir()?;
+    let db = Database::new(temp_dir.path().join("test.db")).await?;
+    db.migrate().await?;
+    let repo = db.applications();
+
+    // Rows created before the index exists are indexed when it is built
+    let web = repo
+        .create_with_description("web", Some("Storefront for the billing team"))
+        .await?;
+    assert!(!db.full_text_search_enabled().await?);
+    assert!(db.enable_full_text_search().await?);
+    assert!(!db.enable_full_text_search().await?, "already enabled");
+    assert!(db.full_text_search_enabled().await?);
+
+    let billing = repo.create("billing-api").await?;
+    repo.create_with_description("reports", Some("Monthly summaries"))
+        .await?;
+
+    // Name matches outrank description matches
+    let ids: Vec<_> = repo
+        .search_fts("BILLING")
+        .await?
+        .into_iter()
+        .map(|app| app.id)
+        .collect();
+    assert_eq!(ids, [billing.id.clone(), web.id.clone()]);
+
+    // Every word must match; a trailing * matches by prefix
+    assert_eq!(repo.search_fts("billing team").await?.len(), 1);
+    assert_eq!(repo.search_fts("summ*").await?[0].name, "reports");
+    assert!(repo.search_fts("summ").await?.is_empty());
+    // Punctuation is not query syntax
+    assert_eq!(repo.search_fts("billing-api").await?[0].id, billing.id);
+    assert!(repo.search_fts("\"NEAR(").await?.is_empty());
+
+    // Triggers keep the index current
+    repo.rename(&billing.id, "invoicing-api").await?;
+    assert_eq!(repo.search_fts("billing").await?.len(), 1);
+    assert_eq!(repo.search_fts("invoicing").await?[0].id, billing.id);
+    repo.delete(&web.id).await?;
+    assert!(repo.search_fts("storefront").await?.is_empty());
+
+    // Rebuilding the table for owner-scoped names keeps the index working
+    assert!(db.scope_names_by_owner().await?);
+    let ledger = repo.create("ledger").await?;
+    assert_eq!(repo.search_fts("ledger").await?[0].id, ledger.id);
+    repo.rename(&ledger.id, "journal").await?;
+    assert!(repo.search_fts("ledger").await?.is_empty());
+
+    // Without the index, words are matched as substrings
+    assert!(db.disable_full_text_search().await?);
+    assert!(!db.full_text_search_enabled().await?);
+    assert_eq!(repo.search_fts("voic").await?[0].id, billing.id);
+    assert_eq!(repo.search_fts("100%").await?.len(), 0);
+
+    for text in ["", "  *  ", &"x".repeat(257)] {
+        let result = repo.search_fts(text).await;
+        assert!(
+            matches!(result, Err(DbError::InvalidArgument(_))),
+            "{text:?}"
+        );
+    }
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_connection_strategies_agree() -> Result<(), Box<dyn std::error::Error>> {
+    use futures::TryStreamExt;
+
+    let temp_dir = tempdir()?;
+
+    let mut listings = Vec::new();
+    for strategy in [ConnectionStrategy::Pooled, ConnectionStrategy::PerOperation] {
+        let config = DatabaseConfig {
+            connection_strategy: strategy,
+            ..Default::default()
+        };
+        let db =
+            Database::new_with_config(temp_dir.path().join(format!("{strategy:?}.db")), config)
+                .await?
+                .with_clock(Arc::new(MockClock::new(1_000)));
+        db.migrate().await?;
+
+        let repo = db.applications();
+        repo.create("alpha").await?;
+        repo.create_with_description("beta", Some("second")).await?;
+        let gamma = repo.create("gamma").await?;
+        repo.set_status(&gamma.id, AppStatus::Archived).await?;
+        assert!(matches!(
+            repo.create("alpha").await.unwrap_err(),
+            DbError::DuplicateName(_)
+        ));
+
+        let listed: Vec<_> = repo
+            .list()
+            .await?
+            .into_iter()
+            .map(|app| (app.name, app.description, app.status, app.created_at))
+            .collect();
+        let streamed: Vec<_> = db.applications().stream().try_collect().await?;
+        assert_eq!(streamed.len(), listed.len());
+        assert_eq!(repo.count().await?, 3);
+        listings.push(listed);
+    }
+
+    assert_eq!(listings[0], listings[1]);
 
     Ok(())
 }
@@ -0,0 +1,117 @@
+use ottershipper_db::{AuditAction, Database};
+use tempfile::{tempdir, TempDir};
+
+async fn setup_db() -> Result<(Database, TempDir), Box<dyn std::error::Error>> {
+    let temp_dir = tempdir()?;
+    let db = Database::new(temp_dir.path().join("test.db")).await?;
+    db.migrate().await?;
+    Ok((db, temp_dir))
+}
+
+#[tokio::test]
+async fn test_create_appends_one_audit_row() -> Result<(), Box<dyn std::error::Error>> {
+    let (db, _temp_dir) = setup_db().await?;
+
+    let before = chrono::Utc::now().timestamp_millis();
+    let app = db
+        .applications()
+        .with_actor("alice")
+        .create("audited-app")
+        .await?;
+
+    let trail = db.applications().audit_trail(&app.id).await?;
+    assert_eq!(trail.len(), 1);
+    assert_eq!(trail[0].action, AuditAction::Create);
+    assert_eq!(trail[0].app_id, app.id);
+    assert_eq!(trail[0].app_name, "audited-app");
+    assert_eq!(trail[0].actor.as_deref(), Some("alice"));
+    assert!(trail[0].at >= before);
+
+    // Reads don't produce audit rows
+    db.applications().get(&app.id).await?;
+    db.applications().list().await?;
+    assert_eq!(db.applications().recent_audit(100).await?.len(), 1);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_delete_appends_one_audit_row() -> Result<(), Box<dyn std::error::Error>> {
+    let (db, _temp_dir) = setup_db().await?;
+
+    let app = db.applications().create("doomed-app").await?;
+    assert!(db.applications().delete(&app.id).await?);
+
+    let trail = db.applications().audit_trail(&app.id).await?;
+    assert_eq!(trail.len(), 2);
+    assert_eq!(trail[1].action, AuditAction::Delete);
+    assert_eq!(trail[1].app_id, app.id);
+    assert_eq!(trail[1].app_name, "doomed-app");
+    assert_eq!(trail[1].actor, None);
+
+    // Deleting a missing application records nothing
+    assert!(!db.applications().delete(&app.id).await?);
+    assert!(!db.applications().delete_by_name("doomed-app").await?);
+    assert_eq!(db.applications().audit_trail(&app.id).await?.len(), 2);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_delete_many_audits_each_application() -> Result<(), Box<dyn std::error::Error>> {
+    let (db, _temp_dir) = setup_db().await?;
+
+    let a = db.applications().create("app-a").await?;
+    let b = db.applications().create("app-b").await?;
+    let deleted = db
+        .applications()
+        .delete_many(&[&a.id, &b.id, "missing"])
+        .await?;
+    assert_eq!(deleted, 2);
+
+    let recent = db.applications().recent_audit(10).await?;
+    assert_eq!(recent.len(), 4);
+    let deleted_names: Vec<_> = recent
+        .iter()
+        .filter(|entry| entry.action == AuditAction::Delete)
+        .map(|entry| entry.app_name.as_str())
+        .collect();
+    assert_eq!(deleted_names.len(), 2);
+    assert!(deleted_names.contains(&"app-a") && deleted_names.contains(&"app-b"));
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_failed_create_leaves_no_audit_row() -> Result<(), Box<dyn std::error::Error>> {
+    let (db, _temp_dir) = setup_db().await?;
+
+    db.applications().create("taken").await?;
+    assert!(db.applications().create("taken").await.is_err());
+    assert_eq!(db.applications().recent_audit(10).await?.len(), 1);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_audit_log_is_append_only() -> Result<(), Box<dyn std::error::Error>> {
+    let (db, temp_dir) = setup_db().await?;
+    db.applications().create("app").await?;
+    db.close().await?;
+
+    let pool = sqlx::SqlitePool::connect(&format!(
+        "sqlite://{}",
+        temp_dir.path().join("test.db").display()
+    ))
+    .await?;
+    assert!(sqlx::query("UPDATE audit_log SET actor = 'mallory'")
+        .execute(&pool)
+        .await
+        .is_err());
+    assert!(sqlx::query("DELETE FROM audit_log")
+        .execute(&pool)
+        .await
+        .is_err());
+
+    Ok(())
+}
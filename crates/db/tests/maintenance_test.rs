@@ -0,0 +1,484 @@
+use ottershipper_db::{CheckpointMode, Database, DatabaseConfig, DbError};
+use std::time::Duration;
+use tempfile::tempdir;
+
+#[tokio::test]
+async fn test_checkpoint_after_writes() -> Result<(), Box<dyn std::error::Error>> {
+    let temp_dir = tempdir()?;
+    let db_path = temp_dir.path().join("test.db");
+
+    let db = Database::new(&db_path).await?;
+    db.migrate().await?;
+
+    for i in 0..20 {
+        db.applications().create(&format!("app-{i}")).await?;
+    }
+
+    // Passive checkpoint reports the frames written so far
+    let result = db.checkpoint(CheckpointMode::Passive).await?.unwrap();
+    assert!(!result.busy);
+    assert!(result.log_frames > 0);
+    assert!(result.checkpointed_frames >= 0);
+    assert!(result.checkpointed_frames <= result.log_frames);
+
+    // Truncate empties the WAL file
+    let result = db.checkpoint(CheckpointMode::Truncate).await?.unwrap();
+    assert!(!result.busy);
+    let wal_len = std::fs::metadata(temp_dir.path().join("test.db-wal"))?.len();
+    assert_eq!(wal_len, 0);
+
+    // Data is intact
+    assert_eq!(db.applications().count().await?, 20);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_checkpoint_periodically() -> Result<(), Box<dyn std::error::Error>> {
+    let temp_dir = tempdir()?;
+    let db_path = temp_dir.path().join("test.db");
+
+    let db = Database::new(&db_path).await?;
+    db.migrate().await?;
+
+    // Stay well below SQLite's automatic checkpoint threshold (1000 pages)
+    for i in 0..40 {
+        db.applications().create(&format!("app-{i}")).await?;
+    }
+
+    // Let the background task checkpoint, then write once more: after a
+    // complete checkpoint the next write restarts the WAL from the top
+    let periodic = db.checkpoint_periodically(std::time::Duration::from_millis(20));
+    let _ = tokio::time::timeout(std::time::Duration::from_millis(200), periodic).await;
+    db.applications().create("after").await?;
+
+    // Without the background task every one of the 41 writes is still in the WAL
+    let result = db.checkpoint(CheckpointMode::Passive).await?.unwrap();
+    assert!(
+        result.log_frames < 40,
+        "WAL has {} frames",
+        result.log_frames
+    );
+    assert_eq!(db.applications().count().await?, 41);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_checkpoint_periodically_skips_without_wal() -> Result<(), Box<dyn std::error::Error>>
+{
+    let temp_dir = tempdir()?;
+    let config = DatabaseConfig {
+        enable_wal: false,
+        ..Default::default()
+    };
+    let db = Database::new_with_config(temp_dir.path().join("test.db"), config).await?;
+    db.migrate().await?;
+
+    // Ends on its own after the first tick instead of running forever
+    tokio::time::timeout(
+        std::time::Duration::from_secs(5),
+        db.checkpoint_periodically(std::time::Duration::from_millis(10)),
+    )
+    .await?;
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_checkpoint_without_wal() -> Result<(), Box<dyn std::error::Error>> {
+    let temp_dir = tempdir()?;
+    let db_path = temp_dir.path().join("test.db");
+
+    let config = DatabaseConfig {
+        enable_wal: false,
+        ..Default::default()
+    };
+    let db = Database::new_with_config(&db_path, config).await?;
+    db.migrate().await?;
+    db.applications().create("no-wal").await?;
+
+    // Gracefully does nothing
+    assert!(db.checkpoint(CheckpointMode::Full).await?.is_none());
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_checkpoint_read_only() -> Result<(), Box<dyn std::error::Error>> {
+    let temp_dir = tempdir()?;
+    let db_path = temp_dir.path().join("test.db");
+
+    Database::new(&db_path).await?.close().await?;
+    let config = DatabaseConfig {
+        read_only: true,
+        ..Default::default()
+    };
+    let db = Database::new_with_config(&db_path, config).await?;
+
+    let result = db.checkpoint(CheckpointMode::Passive).await;
+    assert!(matches!(result.unwrap_err(), DbError::ReadOnly));
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_open_non_sqlite_file_is_corrupt() -> Result<(), Box<dyn std::error::Error>> {
+    let temp_dir = tempdir()?;
+    let db_path = temp_dir.path().join("garbage.db");
+
+    // Deterministic junk that is long enough to contain a (bogus) header page
+    let junk: Vec<u8> = (0..8192u32)
+        .map(|i| (i.wrapping_mul(31) % 251) as u8)
+        .collect();
+    std::fs::write(&db_path, junk)?;
+
+    for enable_wal in [true, false] {
+        let config = DatabaseConfig {
+            enable_wal,
+            ..Default::default()
+        };
+        let result = Database::new_with_config(&db_path, config).await;
+        match result {
+            Err(DbError::Corrupt(path)) => assert!(path.contains("garbage.db")),
+            Err(e) => panic!("expected Corrupt, got {e:?}"),
+            Ok(_) => panic!("expected Corrupt, got a working database"),
+        }
+    }
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_disk_usage_tracks_wal() -> Result<(), Box<dyn std::error::Error>> {
+    let temp_dir = tempdir()?;
+    let db_path = temp_dir.path().join("test.db");
+
+    let db = Database::new(&db_path).await?;
+    db.migrate().await?;
+
+    for i in 0..200 {
+        db.applications().create(&format!("app-{i}")).await?;
+    }
+
+    let before = db.disk_usage().await?;
+    assert_eq!(before.path.as_deref(), Some(db_path.to_str().unwrap()));
+    assert!(before.main_bytes > 0);
+    assert!(before.wal_bytes > 0);
+    assert!(before.shm_bytes > 0);
+
+    // A truncating checkpoint shrinks the WAL
+    db.checkpoint(CheckpointMode::Truncate).await?;
+    let after = db.disk_usage().await?;
+    assert!(after.wal_bytes < before.wal_bytes);
+    assert_eq!(after.wal_bytes, 0);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_disk_usage_in_memory() -> Result<(), Box<dyn std::error::Error>> {
+    let db = Database::from_url("sqlite::memory:", DatabaseConfig::default()).await?;
+    db.migrate().await?;
+
+    let usage = db.disk_usage().await?;
+    assert_eq!(usage.path, None);
+    assert_eq!(usage.total_bytes(), 0);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_pool_stats_and_migration_status() -> Result<(), Box<dyn std::error::Error>> {
+    let temp_dir = tempdir()?;
+    let db_path = temp_dir.path().join("test.db");
+
+    let db = Database::new(&db_path).await?;
+    let pending = db.migration_status().await?;
+    assert!(!pending.is_empty());
+    assert!(pending.iter().all(|m| m.applied_at.is_none()));
+
+    db.migrate().await?;
+    let applied = db.migration_status().await?;
+    assert_eq!(applied.len(), pending.len());
+    assert!(applied.iter().all(|m| m.applied_at.is_some()));
+    assert_eq!(applied[0].name, "001_initial_schema");
+
+    let stats = db.pool_stats();
+    assert_eq!(
+        stats.max_connections,
+        DatabaseConfig::default().max_connections
+    );
+    assert!(stats.size >= 1);
+    assert!(stats.idle <= stats.size as usize);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_migration_plan() -> Result<(), Box<dyn std::error::Error>> {
+    let temp_dir = tempdir()?;
+    let db_path = temp_dir.path().join("test.db");
+
+    let db = Database::new(&db_path).await?;
+    let plan = db.migration_plan().await?;
+    let all: Vec<String> = db
+        .migration_status()
+        .await?
+        .into_iter()
+        .map(|m| m.name)
+        .collect();
+    assert_eq!(plan, all);
+    assert_eq!(plan.last().unwrap(), Database::latest_schema_version());
+
+    // Planning applies nothing
+    assert_eq!(db.migration_count().await?, 0);
+    assert_eq!(db.migration_plan().await?, plan);
+
+    db.migrate().await?;
+    assert!(db.migration_plan().await?.is_empty());
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_pool_warmup() -> Result<(), Box<dyn std::error::Error>> {
+    let temp_dir = tempdir()?;
+    let db_path = temp_dir.path().join("test.db");
+
+    let config = DatabaseConfig {
+        min_connections: 3,
+        warmup: true,
+        ..Default::default()
+    };
+    let db = Database::new_with_config(&db_path, config).await?;
+
+    let stats = db.pool_stats();
+    assert!(stats.size >= 3, "pool has {} connections", stats.size);
+
+    let config = DatabaseConfig {
+        max_connections: 2,
+        min_connections: 3,
+        warmup: true,
+        ..Default::default()
+    };
+    let result = Database::new_with_config(&db_path, config).await;
+    assert!(matches!(result, Err(DbError::InvalidArgument(_))));
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_optimize() -> Result<(), Box<dyn std::error::Error>> {
+    let temp_dir = tempdir()?;
+    let db_path = temp_dir.path().join("test.db");
+
+    let db = Database::new(&db_path).await?;
+    db.migrate().await?;
+    for i in 0..50 {
+        db.applications().create(&format!("app-{i}")).await?;
+    }
+
+    db.optimize().await?;
+
+    // Statistics were gathered and queries still work
+    let pool = sqlx::SqlitePool::connect(&format!("sqlite:{}", db_path.display())).await?;
+    let (stats,): (i64,) = sqlx::query_as("SELECT COUNT(*) FROM sqlite_stat1")
+        .fetch_one(&pool)
+        .await?;
+    assert!(stats > 0);
+    pool.close().await;
+    assert_eq!(db.applications().count().await?, 50);
+    assert!(db.applications().get_by_name("app-7").await?.is_some());
+
+    // Read-only databases can't store statistics
+    db.close().await?;
+    let config = DatabaseConfig {
+        read_only: true,
+        ..Default::default()
+    };
+    let db = Database::new_with_config(&db_path, config).await?;
+    assert!(matches!(db.optimize().await, Err(DbError::ReadOnly)));
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_statement_timeout() -> Result<(), Box<dyn std::error::Error>> {
+    let temp_dir = tempdir()?;
+    let db_path = temp_dir.path().join("test.db");
+
+    let setup = Database::new(&db_path).await?;
+    setup.migrate().await?;
+
+    // Another process holds the write lock for longer than the statement timeout
+    let mut blocker = setup.begin_immediate().await?;
+    blocker.applications().create("holding-lock").await?;
+
+    let config = DatabaseConfig {
+        statement_timeout: Some(Duration::from_millis(200)),
+        ..Default::default()
+    };
+    let db = Database::new_with_config(&db_path, config).await?;
+
+    let started = std::time::Instant::now();
+    let result = db.applications().create("blocked").await;
+    assert!(
+        matches!(result, Err(DbError::Timeout(limit)) if limit == Duration::from_millis(200)),
+        "unexpected result: {result:?}"
+    );
+    // Gave up well before the 5s busy timeout
+    assert!(started.elapsed() < Duration::from_secs(2));
+
+    // Once the lock is released the same database works again
+    blocker.rollback().await?;
+    db.applications().create("unblocked").await?;
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_acquire_latency_under_saturated_pool() -> Result<(), Box<dyn std::error::Error>> {
+    let temp_dir = tempdir()?;
+    let db_path = temp_dir.path().join("test.db");
+
+    let config = DatabaseConfig {
+        max_connections: 1,
+        ..Default::default()
+    };
+    let db = Database::new_with_config(&db_path, config).await?;
+    db.migrate().await?;
+
+    // The only connection is checked out, so the count waits for it
+    let tx = db.begin().await?;
+    let release = async {
+        tokio::time::sleep(Duration::from_millis(50)).await;
+        tx.rollback().await
+    };
+    let apps = db.applications();
+    let (count, released) = tokio::join!(apps.count(), release);
+    assert_eq!(count?, 0);
+    released?;
+
+    let acquire = db.pool_stats().acquire;
+    assert!(acquire.count >= 2, "recorded {} acquires", acquire.count);
+    assert!(
+        acquire.max_micros >= 40_000,
+        "longest wait was {}us",
+        acquire.max_micros
+    );
+    assert_eq!(
+        acquire
+            .buckets
+            .iter()
+            .map(|bucket| bucket.count)
+            .sum::<u64>(),
+        acquire.count
+    );
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_reconnect_after_file_replaced() -> Result<(), Box<dyn std::error::Error>> {
+    let temp_dir = tempdir()?;
+    let db_path = temp_dir.path().join("test.db");
+    let restored_path = temp_dir.path().join("restored.db");
+
+    let db = Database::new(&db_path).await?;
+    db.migrate().await?;
+    db.applications().create("before-restore").await?;
+
+    let restored = Database::new(&restored_path).await?;
+    restored.migrate().await?;
+    restored.applications().create("from-backup").await?;
+    restored.close().await?;
+
+    // Swap the file out from under the open pool, then pick up the new one
+    std::fs::rename(&restored_path, &db_path)?;
+    db.reconnect().await?;
+
+    let clone = db.clone();
+    let names: Vec<String> = clone
+        .applications()
+        .list()
+        .await?
+        .into_iter()
+        .map(|app| app.name)
+        .collect();
+    assert_eq!(names, ["from-backup"]);
+    db.applications().create("after-restore").await?;
+    assert_eq!(clone.applications().count().await?, 2);
+
+    // Callers getting connections while the pool is swapped are not turned away
+    let readers: Vec<_> = (0..4)
+        .map(|_| {
+            let db = db.clone();
+            tokio::spawn(async move {
+                for _ in 0..25 {
+                    db.applications().count().await?;
+                }
+                Ok::<_, DbError>(())
+            })
+        })
+        .collect();
+    for _ in 0..3 {
+        db.reconnect().await?;
+    }
+    for reader in readers {
+        reader.await??;
+    }
+
+    // An in-memory database would be lost by reconnecting
+    let memory = Database::new_in_memory().await?;
+    assert!(matches!(
+        memory.reconnect().await,
+        Err(DbError::InvalidArgument(_))
+    ));
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_begin_across_reconnect() -> Result<(), Box<dyn std::error::Error>> {
+    let temp_dir = tempdir()?;
+    let db_path = temp_dir.path().join("test.db");
+
+    let writable = Database::new(&db_path).await?;
+    writable.migrate().await?;
+    writable.applications().create("api").await?;
+    writable.close().await?;
+
+    // One connection, and read-only so reconnecting doesn't need a second
+    // one to checkpoint
+    let config = DatabaseConfig {
+        max_connections: 1,
+        read_only: true,
+        ..Default::default()
+    };
+    let db = Database::new_with_config(&db_path, config).await?;
+    let held = db.begin().await?;
+
+    // Wait for the only connection, then have a reconnect close its pool
+    let waiting = tokio::spawn({
+        let db = db.clone();
+        async move {
+            let mut tx = db.begin().await?;
+            let count = tx.applications().count().await?;
+            tx.commit().await?;
+            Ok::<_, DbError>(count)
+        }
+    });
+    tokio::time::sleep(Duration::from_millis(50)).await;
+    let reconnecting = tokio::spawn({
+        let db = db.clone();
+        async move { db.reconnect().await }
+    });
+    tokio::time::sleep(Duration::from_millis(50)).await;
+    held.commit().await?;
+
+    reconnecting.await??;
+    assert_eq!(waiting.await??, 1);
+
+    Ok(())
+}
@@ -1,9 +1,21 @@
 use anyhow::{Context, Result};
+use ottershipper_db::{ConnectionStrategy, NameCasing, NameFormat, NamePolicy};
 use serde::{Deserialize, Serialize};
 use std::path::{Path, PathBuf};
 
+/// Config file extensions understood by [`Config::load`]
+const SUPPORTED_EXTENSIONS: &[&str] = &[
+    ".toml",
+    #[cfg(feature = "json-config")]
+    ".json",
+    #[cfg(feature = "yaml-config")]
+    ".yaml",
+    #[cfg(feature = "yaml-config")]
+    ".yml",
+];
+
 /// `OtterShipper` server configuration
-#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, Default)]
 pub struct Config {
     /// Server configuration
     #[serde(default)]
@@ -12,34 +24,321 @@ pub struct Config {
     /// Database configuration
     #[serde(default)]
     pub database: DatabaseConfig,
+
+    /// Application name validation rules
+    #[serde(default)]
+    pub names: NamePolicy,
+
+    /// Which MCP tools are exposed
+    #[serde(default)]
+    pub tools: ToolsConfig,
+
+    /// Cross-origin access to the HTTP transport
+    #[serde(default)]
+    pub cors: CorsConfig,
+
+    /// Log output configuration
+    #[serde(default)]
+    pub logging: LoggingConfig,
+
+    /// Outbound notifications of application changes
+    #[serde(default)]
+    pub webhooks: WebhookConfig,
+
+    /// Request rate limits
+    #[serde(default)]
+    pub limits: LimitsConfig,
+
+    /// In-process caching of application lookups
+    #[serde(default)]
+    pub cache: CacheConfig,
 }
 
 /// Server transport and binding configuration
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct ServerConfig {
-    /// Transport mode: "stdio" or "http"
+    /// Transport mode: "stdio", "http", or both as "stdio+http"
+    ///
+    /// Overridden by `OTTERSHIPPER_DEFAULT_TRANSPORT` and `--transport`.
     #[serde(default = "default_transport")]
     pub transport: String,
 
-    /// HTTP bind address (only used when transport = "http")
+    /// HTTP bind address (only used with the http transport)
     #[serde(default = "default_bind_address")]
     pub bind_address: String,
 
-    /// HTTP port (only used when transport = "http")
+    /// HTTP port (only used with the http transport)
     #[serde(default = "default_port")]
     pub port: u16,
+
+    /// Largest accepted HTTP request body, in bytes (only used with the http transport)
+    #[serde(default = "default_max_request_bytes")]
+    pub max_request_bytes: usize,
+
+    /// How long an idempotency key on create dedupes retried calls, in seconds
+    #[serde(default = "default_idempotency_window_secs")]
+    pub idempotency_window_secs: u64,
+
+    /// Maximum number of applications that may exist (unlimited when unset)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_applications: Option<u64>,
+
+    /// Instructions sent to MCP clients, replacing the built-in guidance
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub instructions: Option<String>,
+
+    /// File whose contents replace the built-in instructions sent to MCP clients
+    ///
+    /// Read once at startup. If it can't be read or isn't UTF-8, the built-in
+    /// instructions are used and a warning is logged.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub instructions_file: Option<PathBuf>,
+
+    /// Markdown file served as the `otter://help` resource, replacing the built-in help
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub help_file: Option<PathBuf>,
+
+    /// Page size used by `otter_list_apps` and `otter_query_apps` when a call
+    /// omits `limit` (when unset, `otter_list_apps` lists everything and
+    /// `otter_query_apps` returns 50)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub default_page_size: Option<u32>,
+
+    /// Largest page those tools return; bigger or unlimited requests are
+    /// clamped to it (no cap when unset)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_page_size: Option<u32>,
+}
+
+/// CORS configuration for the HTTP transport
+#[derive(Debug, Clone, PartialEq, Default, Serialize, Deserialize)]
+pub struct CorsConfig {
+    /// Origins allowed to call the HTTP endpoints from a browser, e.g.
+    /// `"https://dashboard.example.com"`
+    ///
+    /// Empty (the default) sends no CORS headers. `"*"` allows any origin, which
+    /// lets every website a user visits talk to the server; only use it when
+    /// the server is not reachable from untrusted networks.
+    #[serde(default)]
+    pub allowed_origins: Vec<String>,
+}
+
+/// Outbound webhook configuration
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct WebhookConfig {
+    /// URL to POST change notifications to; unset disables webhooks
+    #[serde(default)]
+    pub url: Option<String>,
+
+    /// Shared secret used to sign payloads in the `X-Otter-Signature` header
+    #[serde(default)]
+    pub secret: Option<String>,
+
+    /// Delivery attempts after the first before a notification is dropped
+    #[serde(default = "default_webhook_retries")]
+    pub max_retries: u32,
+}
+
+/// Request rate limit configuration
+#[derive(Debug, Clone, PartialEq, Default, Serialize, Deserialize)]
+pub struct LimitsConfig {
+    /// Tool calls allowed per minute across all clients (unlimited when unset)
+    ///
+    /// Calls beyond the limit fail immediately with a "retry after" error
+    /// instead of waiting. Short bursts of up to this many calls are allowed.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub tool_calls_per_minute: Option<u32>,
+}
+
+/// Cache of application lookups by name
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct CacheConfig {
+    /// Cache lookups by name (off by default)
+    ///
+    /// Entries are dropped when this server changes the application, but
+    /// changes made by another process are only seen once an entry expires.
+    #[serde(default)]
+    pub enabled: bool,
+
+    /// Most names cached at once; the least recently used are evicted first
+    #[serde(default = "default_cache_max_entries")]
+    pub max_entries: usize,
+
+    /// How long a cached lookup is reused, in seconds
+    #[serde(default = "default_cache_ttl_secs")]
+    pub ttl_secs: u64,
+}
+
+/// Log output configuration
+#[derive(Debug, Clone, PartialEq, Default, Serialize, Deserialize)]
+pub struct LoggingConfig {
+    /// Output format: "pretty" (human-readable, the default) or "json"
+    #[serde(default)]
+    pub format: LogFormat,
+}
+
+/// Log line format
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum LogFormat {
+    /// Human-readable lines
+    #[default]
+    Pretty,
+    /// One JSON object per line
+    Json,
+}
+
+/// A transport the MCP server can be served over
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Transport {
+    /// Standard input/output, for a local client that spawns the server
+    Stdio,
+    /// SSE over HTTP
+    Http,
+}
+
+impl ServerConfig {
+    /// Parse `transport` into the transports to serve, in order and without duplicates
+    ///
+    /// Transports may be combined with `+` or `,`, e.g. `"stdio+http"`.
+    pub fn transports(&self) -> Result<Vec<Transport>> {
+        let mut transports = Vec::new();
+        for name in self.transport.split(['+', ',']).map(str::trim) {
+            let transport = match name {
+                "stdio" => Transport::Stdio,
+                "http" => Transport::Http,
+                other => anyhow::bail!(
+                    "Invalid transport type: {other}. Must be 'stdio', 'http', or 'stdio+http'"
+                ),
+            };
+            if !transports.contains(&transport) {
+                transports.push(transport);
+            }
+        }
+        Ok(transports)
+    }
 }
 
 /// Database configuration
-#[derive(Debug, Clone, Serialize, Deserialize)]
+// Each flag is an independent setting from the config file, not encoded state
+#[allow(clippy::struct_excessive_bools)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct DatabaseConfig {
-    /// Path to `SQLite` database file
+    /// Path to `SQLite` database file, `:memory:`, or a `sqlite:` URL such as
+    /// `sqlite::memory:` or `sqlite://data/otter.db?mode=ro`
     #[serde(default = "default_database_path")]
     pub path: PathBuf,
+
+    /// Open the database read-only (e.g. for a reporting replica)
+    #[serde(default)]
+    pub read_only: bool,
+
+    /// Most connections open at once
+    #[serde(default = "default_max_connections")]
+    pub max_connections: u32,
+
+    /// Connections kept open even when idle; at most `max_connections`
+    #[serde(default)]
+    pub min_connections: u32,
+
+    /// Open `min_connections` connections before serving instead of in the
+    /// background
+    #[serde(default)]
+    pub warmup: bool,
+
+    /// Abort database operations running longer than this many milliseconds
+    /// (unlimited when unset)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub statement_timeout_ms: Option<u64>,
+
+    /// Give up waiting for a free connection after this many milliseconds,
+    /// failing the call with a `busy` error (30 seconds when unset)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub acquire_timeout_ms: Option<u64>,
+
+    /// How each operation gets a connection: `pooled` shares pooled
+    /// connections, `per_operation` opens and closes a fresh one every time
+    ///
+    /// `per_operation` isolates requests from each other's connection state at
+    /// a large cost in throughput; use it for debugging, not production load.
+    #[serde(default)]
+    pub connection_strategy: ConnectionStrategy,
+
+    /// Run a passive WAL checkpoint every this many seconds while serving
+    /// over HTTP (disabled when unset)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub checkpoint_interval_secs: Option<u64>,
+
+    /// Make application names unique per owner rather than across all owners
+    ///
+    /// Applied at startup by rebuilding the applications table. This is
+    /// one-way: setting it back to `false` afterwards changes nothing.
+    #[serde(default)]
+    pub unique_names_per_owner: bool,
+
+    /// Keep an FTS5 index of application names and descriptions for
+    /// `otter_search`, instead of scanning every row with `LIKE`
+    ///
+    /// Built at startup, and dropped again at startup once set back to
+    /// `false`. Requires `SQLite` with FTS5 (the bundled build has it).
+    #[serde(default)]
+    pub full_text_search: bool,
+}
+
+/// MCP tool exposure configuration
+///
+/// Tools not exposed are left out of the tool list entirely, and calling
+/// them fails as if they didn't exist.
+#[derive(Debug, Clone, PartialEq, Default, Serialize, Deserialize)]
+pub struct ToolsConfig {
+    /// Only expose these tools (all tools when unset)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub enabled: Option<Vec<String>>,
+
+    /// Never expose these tools, even if listed in `enabled`
+    #[serde(default)]
+    pub disabled: Vec<String>,
+
+    /// Make `otter_delete_app` and `otter_delete_apps` require both the IDs
+    /// and the matching names
+    #[serde(default)]
+    pub require_confirmation: bool,
+
+    /// Expose `otter_reset`, which deletes every application
+    ///
+    /// The server has no authentication, so any connected client could call
+    /// it; it is hidden unless this is set, whatever `enabled` lists.
+    #[serde(default)]
+    pub allow_reset: bool,
+}
+
+/// Tool exposed only with [`ToolsConfig::allow_reset`]
+const RESET_TOOL: &str = "otter_reset";
+
+impl ToolsConfig {
+    /// Whether the tool called `name` should be exposed
+    #[must_use]
+    pub fn is_enabled(&self, name: &str) -> bool {
+        if name == RESET_TOOL && !self.allow_reset {
+            return false;
+        }
+        let allowed = self
+            .enabled
+            .as_ref()
+            .is_none_or(|enabled| enabled.iter().any(|tool| tool == name));
+        allowed && !self.disabled.iter().any(|tool| tool == name)
+    }
 }
 
+/// Transport used when nothing else sets one
+///
+/// Builds can bake in a different default with `OTTERSHIPPER_DEFAULT_TRANSPORT`
+/// at compile time (e.g. `http` for container images).
 fn default_transport() -> String {
-    "stdio".to_string()
+    option_env!("OTTERSHIPPER_DEFAULT_TRANSPORT")
+        .filter(|transport| !transport.is_empty())
+        .unwrap_or("stdio")
+        .to_string()
 }
 
 fn default_bind_address() -> String {
@@ -50,6 +349,14 @@ fn default_port() -> u16 {
     3000
 }
 
+fn default_max_request_bytes() -> usize {
+    1024 * 1024
+}
+
+fn default_idempotency_window_secs() -> u64 {
+    24 * 60 * 60
+}
+
 fn default_database_path() -> PathBuf {
     if cfg!(debug_assertions) {
         PathBuf::from("./ottershipper.db")
@@ -58,12 +365,36 @@ fn default_database_path() -> PathBuf {
     }
 }
 
+fn default_max_connections() -> u32 {
+    ottershipper_db::DatabaseConfig::default().max_connections
+}
+
+fn default_webhook_retries() -> u32 {
+    3
+}
+
+fn default_cache_max_entries() -> usize {
+    1024
+}
+
+fn default_cache_ttl_secs() -> u64 {
+    60
+}
+
 impl Default for ServerConfig {
     fn default() -> Self {
         Self {
             transport: default_transport(),
             bind_address: default_bind_address(),
             port: default_port(),
+            max_request_bytes: default_max_request_bytes(),
+            idempotency_window_secs: default_idempotency_window_secs(),
+            max_applications: None,
+            instructions: None,
+            instructions_file: None,
+            help_file: None,
+            default_page_size: None,
+            max_page_size: None,
         }
     }
 }
@@ -72,15 +403,100 @@ impl Default for DatabaseConfig {
     fn default() -> Self {
         Self {
             path: default_database_path(),
+            read_only: false,
+            max_connections: default_max_connections(),
+            min_connections: 0,
+            warmup: false,
+            statement_timeout_ms: None,
+            acquire_timeout_ms: None,
+            connection_strategy: ConnectionStrategy::Pooled,
+            checkpoint_interval_secs: None,
+            unique_names_per_owner: false,
+            full_text_search: false,
+        }
+    }
+}
+
+impl Default for CacheConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            max_entries: default_cache_max_entries(),
+            ttl_secs: default_cache_ttl_secs(),
+        }
+    }
+}
+
+impl Default for WebhookConfig {
+    fn default() -> Self {
+        Self {
+            url: None,
+            secret: None,
+            max_retries: default_webhook_retries(),
+        }
+    }
+}
+
+/// Expand a leading `~` to the home directory and `$VAR`/`${VAR}` references
+/// to environment variables
+///
+/// Paths without either marker (and non-UTF-8 paths) are returned unchanged.
+/// Referencing an unset variable is an error rather than silently producing a
+/// different path.
+pub fn expand_path(path: &Path) -> Result<PathBuf> {
+    let Some(raw) = path.to_str() else {
+        return Ok(path.to_path_buf());
+    };
+
+    let raw = if raw == "~" || raw.starts_with("~/") {
+        let home = std::env::var("HOME").context("Cannot expand '~': HOME is not set")?;
+        format!("{home}{}", &raw[1..])
+    } else {
+        raw.to_string()
+    };
+
+    let mut expanded = String::with_capacity(raw.len());
+    let mut chars = raw.chars().peekable();
+    while let Some(c) = chars.next() {
+        if c != '$' {
+            expanded.push(c);
+            continue;
+        }
+
+        let name: String = if chars.next_if_eq(&'{').is_some() {
+            let name: String = chars.by_ref().take_while(|&c| c != '}').collect();
+            anyhow::ensure!(!name.is_empty(), "Empty '${{}}' in path {raw}");
+            name
+        } else {
+            std::iter::from_fn(|| chars.next_if(|c| c.is_ascii_alphanumeric() || *c == '_'))
+                .collect()
+        };
+
+        if name.is_empty() {
+            // A lone '$' is kept as-is
+            expanded.push('$');
+            continue;
         }
+        let value = std::env::var(&name)
+            .with_context(|| format!("Environment variable {name} in path {raw} is not set"))?;
+        expanded.push_str(&value);
     }
+
+    Ok(PathBuf::from(expanded))
 }
 
 impl Config {
     /// Load configuration from file, falling back to defaults
+    ///
+    /// Environment overrides (see [`Config::apply_env_overrides`]) are applied
+    /// on top of the file.
     pub fn load<P: AsRef<Path>>(path: P) -> Result<Self> {
-        let path = path.as_ref();
+        let mut config = Self::load_file(path.as_ref())?;
+        config.apply_env_overrides();
+        Ok(config)
+    }
 
+    fn load_file(path: &Path) -> Result<Self> {
         if !path.exists() {
             tracing::info!(
                 "Config file not found at {}, using defaults",
@@ -92,31 +508,192 @@ impl Config {
         let contents = std::fs::read_to_string(path)
             .with_context(|| format!("Failed to read config file: {}", path.display()))?;
 
-        let config: Config = toml::from_str(&contents)
+        let mut config = Self::parse(path, &contents)
             .with_context(|| format!("Failed to parse config file: {}", path.display()))?;
+        config.database.path = expand_path(&config.database.path)
+            .with_context(|| format!("Invalid database path in {}", path.display()))?;
+        if let Some(instructions_file) = &config.server.instructions_file {
+            config.server.instructions_file =
+                Some(expand_path(instructions_file).with_context(|| {
+                    format!("Invalid instructions file path in {}", path.display())
+                })?);
+        }
+        if let Some(help_file) = &config.server.help_file {
+            config.server.help_file = Some(
+                expand_path(help_file)
+                    .with_context(|| format!("Invalid help file path in {}", path.display()))?,
+            );
+        }
 
         tracing::info!("Loaded configuration from {}", path.display());
         Ok(config)
     }
 
+    /// Parse `contents` in the format given by `path`'s extension
+    ///
+    /// `.toml` and extensionless files are TOML; `.json` and `.yaml`/`.yml` are
+    /// accepted when the `json-config` and `yaml-config` features are enabled.
+    fn parse(path: &Path, contents: &str) -> Result<Self> {
+        let extension = path
+            .extension()
+            .and_then(|ext| ext.to_str())
+            .map(str::to_ascii_lowercase);
+
+        match extension.as_deref() {
+            None | Some("toml") => Ok(toml::from_str(contents)?),
+            #[cfg(feature = "json-config")]
+            Some("json") => Ok(serde_json::from_str(contents)?),
+            #[cfg(feature = "yaml-config")]
+            Some("yaml" | "yml") => Ok(serde_yaml::from_str(contents)?),
+            Some(other) => anyhow::bail!(
+                "unsupported config file extension '.{other}' (expected {})",
+                SUPPORTED_EXTENSIONS.join(", ")
+            ),
+        }
+    }
+
     /// Load from default locations in order:
     /// 1. ./ottershipper.toml (current directory)
     /// 2. /etc/ottershipper/config.toml (system-wide)
     /// 3. Built-in defaults
+    ///
+    /// Each location is also tried with the other supported extensions
+    /// (`.json`, `.yaml`, `.yml`, per enabled features) after `.toml`.
     pub fn load_default() -> Result<Self> {
-        let paths = vec![
-            PathBuf::from("./ottershipper.toml"),
-            PathBuf::from("/etc/ottershipper/config.toml"),
-        ];
-
-        for path in paths {
+        for path in Self::default_paths() {
             if path.exists() {
                 return Self::load(&path);
             }
         }
 
         tracing::info!("No config file found, using built-in defaults");
-        Ok(Self::default())
+        let mut config = Self::default();
+        config.apply_env_overrides();
+        Ok(config)
+    }
+
+    /// Config file locations searched by [`Self::load_default`], in order
+    fn default_paths() -> Vec<PathBuf> {
+        ["./ottershipper", "/etc/ottershipper/config"]
+            .into_iter()
+            .flat_map(|stem| {
+                SUPPORTED_EXTENSIONS
+                    .iter()
+                    .map(move |ext| PathBuf::from(format!("{stem}{ext}")))
+            })
+            .collect()
+    }
+
+    /// Apply settings from environment variables over the file's
+    ///
+    /// The transport is resolved as: `--transport` on the command line, then
+    /// the `OTTERSHIPPER_DEFAULT_TRANSPORT` environment variable, then the
+    /// config file, then the compiled-in default (`stdio` unless the build set
+    /// `OTTERSHIPPER_DEFAULT_TRANSPORT`).
+    pub fn apply_env_overrides(&mut self) {
+        self.apply_overrides(|name| std::env::var(name).ok());
+    }
+
+    fn apply_overrides(&mut self, var: impl Fn(&str) -> Option<String>) {
+        if let Some(transport) =
+            var("OTTERSHIPPER_DEFAULT_TRANSPORT").filter(|transport| !transport.is_empty())
+        {
+            self.server.transport = transport;
+        }
+    }
+
+    /// Check settings that parse but can't work, such as an unknown transport
+    ///
+    /// Reports every problem found, one per line, rather than only the first.
+    pub fn validate(&self) -> Result<()> {
+        let mut problems = Vec::new();
+
+        if let Err(e) = self.server.transports() {
+            problems.push(e.to_string());
+        }
+        if self.server.max_request_bytes == 0 {
+            problems.push("server.max_request_bytes must be greater than 0".to_string());
+        }
+        if self.names.max_len == 0 {
+            problems.push("names.max_len must be greater than 0".to_string());
+        }
+        if self.names.min_len > self.names.max_len {
+            problems.push(format!(
+                "names.min_len ({}) cannot exceed names.max_len ({})",
+                self.names.min_len, self.names.max_len
+            ));
+        }
+        if self.names.casing == NameCasing::Upper && self.names.format == NameFormat::DnsLabel {
+            problems.push(
+                "names.casing = \"upper\" cannot be used with names.format = \"dns_label\", which only allows lowercase"
+                    .to_string(),
+            );
+        }
+        if self.server.instructions.is_some() && self.server.instructions_file.is_some() {
+            problems.push(
+                "server.instructions and server.instructions_file cannot both be set".to_string(),
+            );
+        }
+        if self.server.default_page_size == Some(0) {
+            problems.push(
+                "server.default_page_size must be greater than 0 (omit it for no default)"
+                    .to_string(),
+            );
+        }
+        if self.server.max_page_size == Some(0) {
+            problems.push(
+                "server.max_page_size must be greater than 0 (omit it for no cap)".to_string(),
+            );
+        }
+        if let (Some(default), Some(max)) =
+            (self.server.default_page_size, self.server.max_page_size)
+        {
+            if default > max {
+                problems.push(format!(
+                    "server.default_page_size ({default}) cannot exceed server.max_page_size ({max})"
+                ));
+            }
+        }
+        if self.database.max_connections == 0 {
+            problems.push("database.max_connections must be greater than 0".to_string());
+        }
+        if self.database.min_connections > self.database.max_connections {
+            problems.push(format!(
+                "database.min_connections ({}) cannot exceed database.max_connections ({})",
+                self.database.min_connections, self.database.max_connections
+            ));
+        }
+        if self.database.checkpoint_interval_secs == Some(0) {
+            problems.push(
+                "database.checkpoint_interval_secs must be greater than 0 (omit it to disable)"
+                    .to_string(),
+            );
+        }
+        if self.limits.tool_calls_per_minute == Some(0) {
+            problems.push(
+                "limits.tool_calls_per_minute must be greater than 0 (omit it for no limit)"
+                    .to_string(),
+            );
+        }
+        if self.cache.enabled && self.cache.max_entries == 0 {
+            problems.push("cache.max_entries must be greater than 0".to_string());
+        }
+        if self.cache.enabled && self.cache.ttl_secs == 0 {
+            problems.push("cache.ttl_secs must be greater than 0".to_string());
+        }
+        if let Some(url) = &self.webhooks.url {
+            match reqwest::Url::parse(url) {
+                Ok(parsed) if matches!(parsed.scheme(), "http" | "https") => {}
+                Ok(_) => problems.push(format!("webhooks.url must be http or https: {url}")),
+                Err(e) => problems.push(format!("webhooks.url is invalid ({e}): {url}")),
+            }
+        }
+
+        if problems.is_empty() {
+            Ok(())
+        } else {
+            anyhow::bail!("Invalid configuration:\n  {}", problems.join("\n  "))
+        }
     }
 
     /// Generate example configuration file
@@ -126,3 +703,184 @@ impl Config {
         toml::to_string_pretty(&example).expect("Failed to serialize example config")
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_default_paths() {
+        let paths = Config::default_paths();
+        assert_eq!(paths.len(), 2 * SUPPORTED_EXTENSIONS.len());
+        // TOML wins within a location, and the current directory wins overall
+        assert_eq!(paths[0], PathBuf::from("./ottershipper.toml"));
+        assert_eq!(
+            paths[SUPPORTED_EXTENSIONS.len()],
+            PathBuf::from("/etc/ottershipper/config.toml")
+        );
+        #[cfg(feature = "json-config")]
+        assert!(paths.contains(&PathBuf::from("./ottershipper.json")));
+        #[cfg(feature = "yaml-config")]
+        assert!(paths.contains(&PathBuf::from("/etc/ottershipper/config.yml")));
+    }
+
+    #[cfg(all(feature = "json-config", feature = "yaml-config"))]
+    #[test]
+    fn test_load_json_and_yaml() {
+        let dir = tempfile::tempdir().unwrap();
+        let write = |name: &str, contents: &str| {
+            let path = dir.path().join(name);
+            std::fs::write(&path, contents).unwrap();
+            path
+        };
+
+        let toml = write(
+            "otter.toml",
+            r#"
+[server]
+transport = "http"
+port = 8080
+
+[database]
+path = "/srv/otter.db"
+
+[tools]
+disabled = ["otter_checkpoint"]
+"#,
+        );
+        let json = write(
+            "otter.json",
+            r#"{
+  "server": { "transport": "http", "port": 8080 },
+  "database": { "path": "/srv/otter.db" },
+  "tools": { "disabled": ["otter_checkpoint"] }
+}"#,
+        );
+        let yaml = write(
+            "otter.YML",
+            r"
+server:
+  transport: http
+  port: 8080
+database:
+  path: /srv/otter.db
+tools:
+  disabled: [otter_checkpoint]
+",
+        );
+
+        let expected = Config::load(&toml).unwrap();
+        assert_eq!(expected.server.port, 8080);
+        assert_eq!(Config::load(&json).unwrap(), expected);
+        assert_eq!(Config::load(&yaml).unwrap(), expected);
+
+        // Unknown extensions are rejected rather than guessed at
+        let ini = write("otter.ini", "port = 8080");
+        let err = format!("{:#}", Config::load(&ini).unwrap_err());
+        assert!(
+            err.contains("unsupported config file extension '.ini'"),
+            "{err}"
+        );
+    }
+
+    #[test]
+    fn test_env_overrides_transport() {
+        let mut config = Config::default();
+        assert_eq!(config.server.transport, default_transport());
+
+        // Unset or empty variables leave the config alone
+        config.apply_overrides(|_| None);
+        assert_eq!(config.server.transport, default_transport());
+        config.apply_overrides(|_| Some(String::new()));
+        assert_eq!(config.server.transport, default_transport());
+
+        config.apply_overrides(|name| {
+            (name == "OTTERSHIPPER_DEFAULT_TRANSPORT").then(|| "http".to_string())
+        });
+        assert_eq!(config.server.transport, "http");
+    }
+
+    #[test]
+    fn test_validate() {
+        assert!(Config::default().validate().is_ok());
+
+        let mut config = Config::default();
+        config.server.transport = "carrier-pigeon".to_string();
+        config.names.min_len = 10;
+        config.names.max_len = 5;
+        config.webhooks.url = Some("ftp://example.com/hook".to_string());
+        config.limits.tool_calls_per_minute = Some(0);
+        config.database.checkpoint_interval_secs = Some(0);
+        config.database.min_connections = 8;
+        config.server.instructions = Some("Be nice.".to_string());
+        config.server.instructions_file = Some(PathBuf::from("instructions.md"));
+        config.names.casing = NameCasing::Upper;
+        config.names.format = NameFormat::DnsLabel;
+        config.server.default_page_size = Some(500);
+        config.server.max_page_size = Some(100);
+        config.cache = CacheConfig {
+            enabled: true,
+            max_entries: 0,
+            ttl_secs: 0,
+        };
+
+        let err = config.validate().unwrap_err().to_string();
+        assert!(err.contains("carrier-pigeon"), "{err}");
+        assert!(err.contains("names.min_len (10)"), "{err}");
+        assert!(err.contains("webhooks.url must be http or https"), "{err}");
+        assert!(err.contains("limits.tool_calls_per_minute"), "{err}");
+        assert!(err.contains("database.checkpoint_interval_secs"), "{err}");
+        assert!(
+            err.contains("database.min_connections (8) cannot exceed database.max_connections (5)"),
+            "{err}"
+        );
+        assert!(err.contains("cache.max_entries"), "{err}");
+        assert!(err.contains("server.instructions_file"), "{err}");
+        assert!(err.contains("cache.ttl_secs"), "{err}");
+        assert!(err.contains("names.casing"), "{err}");
+        assert!(err.contains("server.default_page_size (500)"), "{err}");
+    }
+
+    #[test]
+    fn test_expand_home() {
+        let home = std::env::var("HOME").unwrap();
+
+        assert_eq!(
+            expand_path(Path::new("~/otter/db.sqlite")).unwrap(),
+            PathBuf::from(format!("{home}/otter/db.sqlite"))
+        );
+        assert_eq!(expand_path(Path::new("~")).unwrap(), PathBuf::from(&home));
+
+        // Only a leading ~ refers to the home directory
+        assert_eq!(
+            expand_path(Path::new("./data/~backup.db")).unwrap(),
+            PathBuf::from("./data/~backup.db")
+        );
+    }
+
+    #[test]
+    fn test_expand_env_vars() {
+        std::env::set_var("OTTERSHIPPER_TEST_DATA_DIR", "/srv/otter");
+
+        assert_eq!(
+            expand_path(Path::new("$OTTERSHIPPER_TEST_DATA_DIR/db.sqlite")).unwrap(),
+            PathBuf::from("/srv/otter/db.sqlite")
+        );
+        assert_eq!(
+            expand_path(Path::new("${OTTERSHIPPER_TEST_DATA_DIR}_old/db.sqlite")).unwrap(),
+            PathBuf::from("/srv/otter_old/db.sqlite")
+        );
+        assert!(expand_path(Path::new("$OTTERSHIPPER_TEST_UNSET_VAR/db.sqlite")).is_err());
+    }
+
+    #[test]
+    fn test_expand_plain_paths_unchanged() {
+        for path in [
+            "/var/lib/ottershipper/ottershipper.db",
+            "./ottershipper.db",
+            "a$",
+        ] {
+            assert_eq!(expand_path(Path::new(path)).unwrap(), PathBuf::from(path));
+        }
+    }
+}
@@ -0,0 +1,229 @@
+use crate::{classify_serve_error, serve_with_retry, CorsConfig, McpServer};
+use axum::body::Body;
+use axum::extract::{DefaultBodyLimit, State};
+use axum::http::{header, HeaderMap, HeaderName, HeaderValue, Method, StatusCode};
+use axum::response::{IntoResponse, Response};
+use axum::routing::get;
+use axum::Router;
+use futures::StreamExt;
+use ottershipper_db::{ApplicationView, Database};
+use rmcp::transport::sse_server::{SseServer, SseServerConfig};
+use tokio::net::TcpListener;
+use tokio_util::sync::CancellationToken;
+use tower_http::cors::{AllowOrigin, Any, CorsLayer};
+use tracing::Instrument;
+
+/// Build the CORS layer for `cors`, or `None` when no origins are allowed
+fn cors_layer(cors: &CorsConfig) -> std::io::Result<Option<CorsLayer>> {
+    if cors.allowed_origins.is_empty() {
+        return Ok(None);
+    }
+
+    let origins = if cors.allowed_origins.iter().any(|origin| origin == "*") {
+        AllowOrigin::any()
+    } else {
+        let origins = cors
+            .allowed_origins
+            .iter()
+            .map(|origin| {
+                HeaderValue::from_str(origin).map_err(|e| {
+                    std::io::Error::new(
+                        std::io::ErrorKind::InvalidInput,
+                        format!("Invalid CORS origin '{origin}': {e}"),
+                    )
+                })
+            })
+            .collect::<std::io::Result<Vec<_>>>()?;
+        AllowOrigin::list(origins)
+    };
+
+    Ok(Some(
+        CorsLayer::new()
+            .allow_origin(origins)
+            .allow_methods([Method::GET, Method::POST, Method::OPTIONS])
+            .allow_headers(Any),
+    ))
+}
+
+/// Path of the newline-delimited JSON export of every application
+pub const EXPORT_PATH: &str = "/export.ndjson";
+
+/// Response header carrying the database's schema version, e.g. `010_app_owner`
+pub const SCHEMA_VERSION_HEADER: HeaderName = HeaderName::from_static("x-schema-version");
+
+/// Export lines buffered ahead of a slow client
+const EXPORT_BUFFER_LINES: usize = 64;
+
+/// Routes served over HTTP next to the MCP endpoints: `GET` [`EXPORT_PATH`]
+pub fn export_routes(db: Database) -> Router {
+    Router::new()
+        .route(EXPORT_PATH, get(export_ndjson))
+        .with_state(db)
+}
+
+/// Weak `ETag` of the export and the schema version it is tagged with
+///
+/// The tag combines the schema version with the repository's change marker,
+/// so any create, update, or delete changes it.
+async fn export_tag(db: &Database) -> ottershipper_db::Result<(String, String)> {
+    let version = db.schema_version().await?.unwrap_or_default();
+    let (count, updated_at, audit_id) = db.applications().change_marker().await?;
+    Ok((
+        format!("W/\"{version}-{count}-{updated_at}-{audit_id}\""),
+        version,
+    ))
+}
+
+/// Whether an `If-None-Match` header value lists `etag`, compared weakly
+fn etag_matches(if_none_match: &HeaderValue, etag: &str) -> bool {
+    let Ok(tags) = if_none_match.to_str() else {
+        return false;
+    };
+    let opaque = |tag: &str| tag.trim().trim_start_matches("W/").to_string();
+    tags.split(',')
+        .any(|tag| tag.trim() == "*" || opaque(tag) == opaque(etag))
+}
+
+/// Stream every application as one [`ApplicationView`] per line, newest first
+///
+/// Rows are read from the repository stream as the client consumes them, so
+/// memory use doesn't grow with the catalog. A database error after the first
+/// line can't change the status any more, so it ends the body early instead.
+/// The response carries a weak `ETag`; a request whose `If-None-Match` lists
+/// it gets `304 Not Modified` without a body.
+async fn export_ndjson(State(db): State<Database>, headers: HeaderMap) -> Response {
+    let (etag, version) = match export_tag(&db).await {
+        Ok(tag) => tag,
+        Err(e) => {
+            tracing::error!("Failed to export applications: {e}");
+            return (
+                StatusCode::INTERNAL_SERVER_ERROR,
+                format!("Failed to export applications: {e}"),
+            )
+                .into_response();
+        }
+    };
+    if headers
+        .get(header::IF_NONE_MATCH)
+        .is_some_and(|if_none_match| etag_matches(if_none_match, &etag))
+    {
+        return (
+            StatusCode::NOT_MODIFIED,
+            [(header::ETAG, etag), (SCHEMA_VERSION_HEADER, version)],
+        )
+            .into_response();
+    }
+
+    let (lines, mut rx) = tokio::sync::mpsc::channel(EXPORT_BUFFER_LINES);
+    tokio::spawn(async move {
+        let mut apps = db.applications().stream();
+        while let Some(app) = apps.next().await {
+            let line = app.map_err(std::io::Error::other).and_then(|app| {
+                let mut line = serde_json::to_vec(&ApplicationView::from(&app))?;
+                line.push(b'\n');
+                Ok(line)
+            });
+            if let Err(e) = &line {
+                tracing::error!("Export stopped early: {e}");
+            }
+            let failed = line.is_err();
+            // A send error means the client went away
+            if lines.send(line).await.is_err() || failed {
+                break;
+            }
+        }
+    });
+    let body = Body::from_stream(futures::stream::poll_fn(move |cx| rx.poll_recv(cx)));
+
+    (
+        [
+            (header::CONTENT_TYPE, "application/x-ndjson".to_string()),
+            (header::ETAG, etag),
+            (SCHEMA_VERSION_HEADER, version),
+        ],
+        body,
+    )
+        .into_response()
+}
+
+/// Serve the MCP SSE transport (`/sse` and `/message`) on `listener`, plus `extra_routes`
+///
+/// Request bodies larger than `max_request_bytes` are rejected with
+/// `413 Payload Too Large` before they are buffered. CORS headers are sent
+/// only for the origins allowed by `cors`. Pass [`export_routes`] to serve the
+/// export, or an empty [`Router`] for the MCP endpoints alone.
+pub async fn serve_sse(
+    listener: TcpListener,
+    max_request_bytes: usize,
+    cors: &CorsConfig,
+    extra_routes: Router,
+) -> std::io::Result<SseServer> {
+    let (sse_server, router) = SseServer::new(SseServerConfig {
+        bind: listener.local_addr()?,
+        sse_path: "/sse".to_string(),
+        post_path: "/message".to_string(),
+        ct: CancellationToken::new(),
+        sse_keep_alive: None,
+    });
+    let mut router = router
+        .merge(extra_routes)
+        .layer(DefaultBodyLimit::max(max_request_bytes));
+    if let Some(cors) = cors_layer(cors)? {
+        router = router.layer(cors);
+    }
+
+    let ct = sse_server.config.ct.child_token();
+    let server = axum::serve(listener, router).with_graceful_shutdown(async move {
+        ct.cancelled().await;
+        tracing::info!("SSE server cancelled");
+    });
+    tokio::spawn(
+        async move {
+            if let Err(e) = server.await {
+                tracing::error!("SSE server shut down with error: {}", e);
+            }
+        }
+        .instrument(tracing::info_span!("sse-server", bind_address = %sse_server.config.bind)),
+    );
+
+    Ok(sse_server)
+}
+
+/// Serve `mcp_server` to every SSE client of `sse_server` until `shutdown` is cancelled
+///
+/// Cancelling `shutdown` also stops the SSE server and ends open sessions.
+pub async fn run_sse(
+    mcp_server: McpServer,
+    mut sse_server: SseServer,
+    shutdown: CancellationToken,
+) {
+    loop {
+        let transport = tokio::select! {
+            transport = sse_server.next_transport() => transport,
+            () = shutdown.cancelled() => None,
+        };
+        let Some(transport) = transport else {
+            break;
+        };
+
+        let server = mcp_server.clone();
+        let session_ct = shutdown.child_token();
+        tokio::spawn(async move {
+            // An SSE session can't be reopened; the client reconnects instead
+            match serve_with_retry(server, transport, || None, session_ct, classify_serve_error)
+                .await
+            {
+                Ok(service) => {
+                    if let Err(e) = service.waiting().await {
+                        tracing::error!("Service error: {}", e);
+                    }
+                }
+                Err(e) => {
+                    tracing::error!("Failed to serve transport: {}", e);
+                }
+            }
+        });
+    }
+
+    sse_server.cancel();
+}
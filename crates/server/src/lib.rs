@@ -1,7 +1,19 @@
 mod config;
+mod http;
+mod logging;
 mod mcp;
+mod rate_limit;
 mod schemas;
+mod serve;
+mod webhooks;
 
-pub use config::Config;
-pub use mcp::McpServer;
+pub use config::{
+    CacheConfig, Config, CorsConfig, LimitsConfig, LogFormat, LoggingConfig, ServerConfig,
+    ToolsConfig, Transport, WebhookConfig,
+};
+pub use http::{export_routes, run_sse, serve_sse, EXPORT_PATH, SCHEMA_VERSION_HEADER};
+pub use logging::build_subscriber;
+pub use mcp::{McpServer, ToolDescriptor, HELP_URI};
 pub use schemas::*;
+pub use serve::{classify_serve_error, serve_with_retry, Retry, ServeError};
+pub use webhooks::{WebhookNotifier, SIGNATURE_HEADER};
@@ -0,0 +1,40 @@
+use crate::config::LogFormat;
+use tracing::Subscriber;
+use tracing_subscriber::EnvFilter;
+
+/// Build the subscriber used for the process, filtered by `filter`
+///
+/// `Json` emits one object per line with event fields flattened to the top level
+/// and the current span stack under `spans`, ready for log aggregators.
+#[must_use]
+pub fn build_subscriber(format: LogFormat, filter: EnvFilter) -> Box<dyn Subscriber + Send + Sync> {
+    let builder = tracing_subscriber::fmt().with_env_filter(filter);
+    match format {
+        LogFormat::Pretty => Box::new(builder.finish()),
+        LogFormat::Json => Box::new(
+            builder
+                .json()
+                .flatten_event(true)
+                .with_current_span(false)
+                .with_span_list(true)
+                .finish(),
+        ),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_build_subscriber() {
+        for format in [LogFormat::Pretty, LogFormat::Json] {
+            let subscriber = build_subscriber(format, EnvFilter::new("info"));
+            tracing::subscriber::with_default(subscriber, || {
+                let span = tracing::info_span!("request", tool = "otter_ping");
+                let _entered = span.enter();
+                tracing::info!(app_count = 3, "logged with {:?}", format);
+            });
+        }
+    }
+}
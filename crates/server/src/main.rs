@@ -1,45 +1,592 @@
-use anyhow::Result;
-use ottershipper_server::Config;
-use rmcp::transport::sse_server::SseServer;
+use anyhow::{Context, Result};
+use ottershipper_server::{Config, McpServer, Transport, WebhookNotifier};
 use rmcp::transport::stdio;
-use rmcp::ServiceExt;
+use std::path::{Path, PathBuf};
+use std::process::ExitCode;
+use tokio::task::JoinSet;
+use tokio_util::sync::CancellationToken;
+
+const USAGE: &str =
+    "Usage: ottershipper [--transport <stdio|http|stdio+http>] [--check-config [path]] [--migrate-plan] [--migrate]
+
+Options:
+  --transport <mode>     serve over stdio, http, or both (overrides the config file)
+  --check-config [path]  validate the configuration, print it, and exit
+  --migrate-plan         print the migrations that would be applied, and exit
+  --migrate              apply pending migrations, and exit without serving
+
+Signals:
+  SIGUSR1                reopen the database, e.g. after restoring its file from a backup
+
+Exit codes:
+  1  any other error (bad arguments, invalid configuration, transport failure)
+  3  the database can't be opened (permissions, missing disk space, locked)
+  4  the database file is corrupt or not a SQLite database
+  5  the database schema can't be migrated
+  6  the HTTP port can't be bound";
+
+/// Startup failures that exit with their own status code (see [`USAGE`])
+#[derive(Debug, thiserror::Error)]
+enum StartupError {
+    #[error("cannot open database at {}: {source}; check permissions and free disk space", path.display())]
+    DatabaseDir {
+        path: PathBuf,
+        source: std::io::Error,
+    },
+
+    #[error(
+        "database directory {} is not writable by the current user; run ottershipper as a user that can write to it or fix its permissions",
+        dir.display()
+    )]
+    DatabaseDirNotWritable { dir: PathBuf },
+
+    #[error("{}", open_error_message(path, source))]
+    DatabaseOpen {
+        path: PathBuf,
+        source: ottershipper_db::DbError,
+    },
+
+    #[error("cannot migrate database at {}: {source}", path.display())]
+    Migrate {
+        path: PathBuf,
+        source: ottershipper_db::DbError,
+    },
+
+    #[error("cannot listen on {address}:{port}: {source}")]
+    Bind {
+        address: String,
+        port: u16,
+        source: std::io::Error,
+    },
+}
+
+impl StartupError {
+    fn exit_code(&self) -> u8 {
+        match self {
+            Self::DatabaseDir { .. } | Self::DatabaseDirNotWritable { .. } => 3,
+            Self::DatabaseOpen { source, .. } => open_exit_code(source),
+            Self::Migrate { .. } => 5,
+            Self::Bind { .. } => 6,
+        }
+    }
+}
+
+/// Exit status for a database that failed to open
+fn open_exit_code(e: &ottershipper_db::DbError) -> u8 {
+    match e {
+        ottershipper_db::DbError::Corrupt(_) => 4,
+        _ => 3,
+    }
+}
+
+/// Actionable message for a database that failed to open
+///
+/// Corruption already says what to do about it; anything else is usually a
+/// permissions, disk, or locking problem.
+fn open_error_message(path: &Path, e: &ottershipper_db::DbError) -> String {
+    match e {
+        ottershipper_db::DbError::Corrupt(_) => e.to_string(),
+        // Already say what to check
+        ottershipper_db::DbError::StorageFull(_) | ottershipper_db::DbError::Io(_) => {
+            format!("cannot open database at {}: {e}", path.display())
+        }
+        ottershipper_db::DbError::DatabaseError(cause) => format!(
+            "cannot open database at {}: {cause}; check permissions and free disk space",
+            path.display()
+        ),
+        _ => format!(
+            "cannot open database at {}: {e}; check permissions and free disk space",
+            path.display()
+        ),
+    }
+}
 
 #[tokio::main]
-async fn main() -> Result<()> {
+async fn main() -> ExitCode {
+    match run().await {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(e) => {
+            eprintln!("Error: {e:?}");
+            e.downcast_ref::<StartupError>()
+                .map_or(ExitCode::FAILURE, |e| ExitCode::from(e.exit_code()))
+        }
+    }
+}
+
+async fn run() -> Result<()> {
+    let mut args = std::env::args().skip(1).peekable();
+    let mut transport = None;
+    let mut migrate = false;
+    let mut check = None;
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--check-config" => {
+                // The path is optional, so a flag after it isn't taken for one
+                check = Some(args.next_if(|next| !next.starts_with("--")));
+            }
+            "--migrate-plan" => return migrate_plan().await,
+            "--migrate" => migrate = true,
+            "--transport" => match args.next() {
+                Some(value) => transport = Some(value),
+                None => anyhow::bail!("--transport requires a value"),
+            },
+            other => anyhow::bail!("Unknown argument: {other}. {USAGE}"),
+        }
+    }
+    if let Some(path) = check {
+        return check_config(path.as_deref().map(Path::new), transport);
+    }
+
+    // Load configuration first: it decides the log format, so anything logged
+    // while loading is dropped and errors surface through the returned Result
+    let mut config = Config::load_default()?;
+    apply_overrides(&mut config, transport);
+    config.validate()?;
+
     // Initialize tracing with INFO level by default
-    tracing_subscriber::fmt()
-        .with_env_filter(
-            tracing_subscriber::EnvFilter::try_from_default_env()
-                .unwrap_or_else(|_| tracing_subscriber::EnvFilter::new("info")),
-        )
-        .init();
-
-    // Load configuration
-    let config = Config::load_default()?;
+    let filter = tracing_subscriber::EnvFilter::try_from_default_env()
+        .unwrap_or_else(|_| tracing_subscriber::EnvFilter::new("info"));
+    tracing::subscriber::set_global_default(ottershipper_server::build_subscriber(
+        config.logging.format,
+        filter,
+    ))?;
 
-    tracing::info!("OtterShipper server starting...");
+    if migrate {
+        migrate_only(&config).await?;
+        return Ok(());
+    }
+
+    let transports = config.server.transports()?;
+
+    tracing::info!(
+        "OtterShipper server v{} starting...",
+        env!("CARGO_PKG_VERSION")
+    );
     tracing::info!("Transport: {}", config.server.transport);
     tracing::info!("Database: {}", config.database.path.display());
 
+    let db = open_database(&config).await?;
+    tracing::info!("Database initialized successfully");
+    let latest = ottershipper_db::Database::latest_schema_version();
+    match db.schema_version().await? {
+        Some(version) if version == latest => tracing::info!("Schema version: {version}"),
+        Some(version) => tracing::warn!("Schema version: {version} (this build expects {latest})"),
+        None => tracing::warn!("Schema version: none, the database has not been migrated"),
+    }
+    tracing::info!("{}", startup_summary(db.applications().count().await?));
+
+    let app_service = application_service(&db, &config);
+    spawn_reconnect_handler(&app_service);
+
+    if let Some(notifier) = WebhookNotifier::from_config(&config.webhooks) {
+        // Runs until the service (and with it the event channel) is dropped
+        let _webhooks = notifier.spawn(app_service.subscribe());
+        tracing::info!("Webhook notifications enabled");
+    }
+
+    let mcp_server = mcp_server(app_service, &config)?;
+    tracing::info!("MCP server initialized successfully");
+
+    // All transports share one MCP server and database pool, and stop together:
+    // Ctrl-C or any transport ending cancels the rest
+    let shutdown = CancellationToken::new();
+    tokio::spawn({
+        let shutdown = shutdown.clone();
+        async move {
+            if tokio::signal::ctrl_c().await.is_ok() {
+                tracing::info!("Shutdown signal received");
+            }
+            shutdown.cancel();
+        }
+    });
+
+    let checkpointer = transports
+        .contains(&Transport::Http)
+        .then(|| spawn_checkpointer(&db, &config, &shutdown))
+        .flatten();
+
+    let mut tasks = JoinSet::new();
+    for transport in transports {
+        spawn_transport(&mut tasks, transport, &config, &db, &mcp_server, &shutdown).await?;
+    }
+
+    // Wait for every transport, keeping the first error
+    let mut result = Ok(());
+    while let Some(joined) = tasks.join_next().await {
+        shutdown.cancel();
+        if let Err(e) = joined.map_err(anyhow::Error::from).and_then(|r| r) {
+            tracing::error!("Transport failed: {}", e);
+            if result.is_ok() {
+                result = Err(e);
+            }
+        }
+    }
+
+    if let Some(checkpointer) = checkpointer {
+        checkpointer.await?;
+    }
+
+    // Checkpoint the WAL so no -wal/-shm files linger after exit
+    db.close().await?;
+
+    result
+}
+
+/// Start the periodic WAL checkpoint task, if an interval is configured
+///
+/// The task stops once `shutdown` is cancelled, or by itself when the
+/// database doesn't use a WAL.
+fn spawn_checkpointer(
+    db: &ottershipper_db::Database,
+    config: &Config,
+    shutdown: &CancellationToken,
+) -> Option<tokio::task::JoinHandle<()>> {
+    let interval = std::time::Duration::from_secs(config.database.checkpoint_interval_secs?);
+    tracing::info!("Checkpointing the WAL every {}s", interval.as_secs());
+
+    let db = db.clone();
+    let shutdown = shutdown.clone();
+    Some(tokio::spawn(async move {
+        tokio::select! {
+            () = db.checkpoint_periodically(interval) => {}
+            () = shutdown.cancelled() => {}
+        }
+    }))
+}
+
+/// Reopen the database whenever the process receives SIGUSR1
+///
+/// Send the signal after replacing the database file (e.g. restoring a
+/// backup) so the server stops using connections to the old file and
+/// forgets lookups cached from it.
+#[cfg(unix)]
+fn spawn_reconnect_handler(service: &ottershipper_core::ApplicationService) {
+    use tokio::signal::unix::{signal, SignalKind};
+
+    let mut signals = match signal(SignalKind::user_defined1()) {
+        Ok(signals) => signals,
+        Err(e) => {
+            tracing::warn!("Cannot listen for SIGUSR1, reconnecting is disabled: {}", e);
+            return;
+        }
+    };
+    let service = service.clone();
+    tokio::spawn(async move {
+        while signals.recv().await.is_some() {
+            tracing::info!("SIGUSR1 received, reconnecting to the database");
+            if let Err(e) = service.reconnect().await {
+                tracing::error!("Failed to reconnect to the database: {}", e);
+            }
+        }
+    });
+}
+
+#[cfg(not(unix))]
+fn spawn_reconnect_handler(_service: &ottershipper_core::ApplicationService) {}
+
+/// Build the application service from the configuration
+fn application_service(
+    db: &ottershipper_db::Database,
+    config: &Config,
+) -> ottershipper_core::ApplicationService {
+    let service = ottershipper_core::ApplicationService::new(db.clone())
+        .with_name_policy(config.names.clone())
+        .with_idempotency_window(std::time::Duration::from_secs(
+            config.server.idempotency_window_secs,
+        ))
+        .with_max_applications(config.server.max_applications);
+    if !config.cache.enabled {
+        return service;
+    }
+
+    tracing::info!(
+        "Caching up to {} name lookups for {}s",
+        config.cache.max_entries,
+        config.cache.ttl_secs
+    );
+    service.with_name_cache(
+        config.cache.max_entries,
+        std::time::Duration::from_secs(config.cache.ttl_secs),
+    )
+}
+
+/// Build the MCP server from the configuration
+fn mcp_server(
+    app_service: ottershipper_core::ApplicationService,
+    config: &Config,
+) -> Result<McpServer> {
+    let mut mcp_server = McpServer::new(app_service)
+        .with_tools(&config.tools)
+        .with_limits(&config.limits)
+        .with_page_sizes(config.server.default_page_size, config.server.max_page_size)
+        .with_transport(&config.server.transport);
+    if let Some(instructions) = &config.server.instructions {
+        mcp_server = mcp_server.with_instructions(instructions);
+    }
+    if let Some(instructions_file) = &config.server.instructions_file {
+        mcp_server = mcp_server.with_instructions_file(instructions_file);
+    }
+    if let Some(help_file) = &config.server.help_file {
+        let help = std::fs::read_to_string(help_file)
+            .with_context(|| format!("Failed to read help file: {}", help_file.display()))?;
+        mcp_server = mcp_server.with_help(help);
+    }
+    Ok(mcp_server)
+}
+
+/// Open the configured database, creating its directory and applying migrations
+async fn open_database(config: &Config) -> Result<ottershipper_db::Database> {
+    let db = connect_database(config).await?;
+    if db.is_read_only() {
+        tracing::info!("Database opened read-only, skipping migrations");
+    } else {
+        db.migrate().await.map_err(|source| StartupError::Migrate {
+            path: config.database.path.clone(),
+            source,
+        })?;
+        apply_name_scope(&db, config).await?;
+        apply_full_text_search(&db, config).await?;
+    }
+    Ok(db)
+}
+
+/// Make names unique per owner if configured, and warn if an earlier run did
+/// so but the setting has since been turned off
+async fn apply_name_scope(db: &ottershipper_db::Database, config: &Config) -> Result<()> {
+    if config.database.unique_names_per_owner {
+        if db.scope_names_by_owner().await? {
+            tracing::info!("Rebuilt the applications table so names are unique per owner");
+        }
+    } else if db.names_scoped_by_owner().await? {
+        tracing::warn!(
+            "Application names are unique per owner in this database; \
+             database.unique_names_per_owner = false can't undo that"
+        );
+    }
+    Ok(())
+}
+
+/// Build or drop the full-text index to match the configuration
+async fn apply_full_text_search(db: &ottershipper_db::Database, config: &Config) -> Result<()> {
+    if config.database.full_text_search {
+        if db.enable_full_text_search().await? {
+            tracing::info!("Built the full-text search index");
+        }
+    } else if db.disable_full_text_search().await? {
+        tracing::info!("Dropped the full-text search index");
+    }
+    Ok(())
+}
+
+/// Whether files can be created in `dir`
+///
+/// A directory with no write permission bits counts as unwritable even for
+/// users that could bypass them, such as root; otherwise a probe file is
+/// created and removed.
+fn is_dir_writable(dir: &Path) -> bool {
+    match std::fs::metadata(dir) {
+        Ok(metadata) if metadata.permissions().readonly() => return false,
+        Ok(_) => {}
+        Err(_) => return false,
+    }
+
+    let probe = dir.join(format!(".ottershipper-write-test-{}", std::process::id()));
+    match std::fs::OpenOptions::new()
+        .write(true)
+        .create_new(true)
+        .open(&probe)
+    {
+        Ok(_) => {
+            let _ = std::fs::remove_file(&probe);
+            true
+        }
+        Err(e) => e.kind() == std::io::ErrorKind::AlreadyExists,
+    }
+}
+
+/// The configured database path as a `sqlite:` URL, if it is one
+fn database_url(path: &Path) -> Option<&str> {
+    path.to_str().filter(|path| path.starts_with("sqlite:"))
+}
+
+/// Open the configured database, creating its directory, without migrating it
+///
+/// In-memory databases and `sqlite:` URLs have no directory of ours to create
+/// or check, so both steps are skipped for them.
+async fn connect_database(config: &Config) -> Result<ottershipper_db::Database> {
+    let path = &config.database.path;
+    let url = database_url(path);
+    let on_disk = url.is_none() && path != Path::new(":memory:");
+
     // Create parent directory for database if it doesn't exist
-    if let Some(parent) = config.database.path.parent() {
-        std::fs::create_dir_all(parent)?;
+    if let Some(parent) = path.parent().filter(|_| on_disk) {
+        std::fs::create_dir_all(parent).map_err(|source| StartupError::DatabaseDir {
+            path: config.database.path.clone(),
+            source,
+        })?;
+
+        // SQLite creates its journal and WAL files next to the database, so
+        // catch this before the open fails with a bare OS error
+        let dir = if parent.as_os_str().is_empty() {
+            Path::new(".")
+        } else {
+            parent
+        };
+        if !config.database.read_only && !is_dir_writable(dir) {
+            return Err(StartupError::DatabaseDirNotWritable {
+                dir: dir.to_path_buf(),
+            }
+            .into());
+        }
     }
 
     // Initialize database
-    let db = ottershipper_db::Database::new(&config.database.path).await?;
-    db.migrate().await?;
-    tracing::info!("Database initialized successfully");
+    let defaults = ottershipper_db::DatabaseConfig::default();
+    let db_config = ottershipper_db::DatabaseConfig {
+        read_only: config.database.read_only,
+        max_connections: config.database.max_connections,
+        min_connections: config.database.min_connections,
+        warmup: config.database.warmup,
+        statement_timeout: config
+            .database
+            .statement_timeout_ms
+            .map(std::time::Duration::from_millis),
+        acquire_timeout: config
+            .database
+            .acquire_timeout_ms
+            .map_or(defaults.acquire_timeout, std::time::Duration::from_millis),
+        connection_strategy: config.database.connection_strategy,
+        ..defaults
+    };
+    let db = match url {
+        Some(url) => ottershipper_db::Database::from_url(url, db_config).await,
+        None => ottershipper_db::Database::new_with_config(path, db_config).await,
+    }
+    .map_err(|source| {
+        if matches!(source, ottershipper_db::DbError::Corrupt(_)) {
+            tracing::error!(path = %path.display(), "{source}");
+        }
+        StartupError::DatabaseOpen {
+            path: path.clone(),
+            source,
+        }
+    })?;
+    Ok(db)
+}
 
-    // Initialize application service
-    let app_service = ottershipper_core::ApplicationService::new(db);
+/// One-line summary of the data found at startup
+fn startup_summary(app_count: i64) -> String {
+    match app_count {
+        0 => "Database is empty: no applications yet".to_string(),
+        1 => "Database contains 1 application".to_string(),
+        n => format!("Database contains {n} applications"),
+    }
+}
+
+/// Apply command line settings, which take precedence over the environment
+/// and config file
+fn apply_overrides(config: &mut Config, transport: Option<String>) {
+    if let Some(transport) = transport {
+        config.server.transport = transport;
+    }
+}
 
-    // Create MCP server
-    let mcp_server = ottershipper_server::McpServer::new(app_service);
+/// Load and validate the configuration, print it, and exit without serving
+///
+/// Loads `path`, or the default locations when omitted, and applies the
+/// command line's `transport` like a normal start would. Returning an error
+/// makes the process exit with a nonzero status.
+fn check_config(path: Option<&Path>, transport: Option<String>) -> Result<()> {
+    let mut config = match path {
+        Some(path) if !path.exists() => {
+            anyhow::bail!("Config file not found: {}", path.display())
+        }
+        Some(path) => Config::load(path)?,
+        None => Config::load_default()?,
+    };
+    apply_overrides(&mut config, transport);
+    config.validate()?;
 
-    match config.server.transport.as_str() {
-        "http" => {
-            tracing::info!("MCP server initialized successfully");
+    print!("{}", toml::to_string_pretty(&config)?);
+    eprintln!("Configuration OK");
+    Ok(())
+}
+
+/// Apply pending migrations to the configured database without serving,
+/// returning the names of the migrations applied
+async fn migrate_only(config: &Config) -> Result<Vec<String>> {
+    let db = connect_database(config).await?;
+    let pending = db.migration_plan().await?;
+    db.migrate().await.map_err(|source| StartupError::Migrate {
+        path: config.database.path.clone(),
+        source,
+    })?;
+    apply_name_scope(&db, config).await?;
+    apply_full_text_search(&db, config).await?;
+
+    if pending.is_empty() {
+        tracing::info!("No pending migrations");
+    } else {
+        tracing::info!(
+            "Applied {} migration(s): {}",
+            pending.len(),
+            pending.join(", ")
+        );
+    }
+    db.close().await?;
+    Ok(pending)
+}
+
+/// Names of the migrations the configured database is missing, changing nothing
+///
+/// A database that doesn't exist yet needs every migration, and is not
+/// created to find that out. An existing one is opened read-only.
+async fn pending_migrations(config: &Config) -> Result<Vec<String>> {
+    let path = &config.database.path;
+    let fresh = database_url(path).is_none() && (path == Path::new(":memory:") || !path.exists());
+    if fresh {
+        return Ok(ottershipper_db::Database::migration_names()
+            .map(str::to_string)
+            .collect());
+    }
+
+    let mut config = config.clone();
+    config.database.read_only = true;
+    let db = connect_database(&config).await?;
+    let plan = db.migration_plan().await?;
+    db.close().await?;
+    Ok(plan)
+}
+
+/// Print the migrations the configured database is missing, and exit without
+/// applying them
+async fn migrate_plan() -> Result<()> {
+    let config = Config::load_default()?;
+    config.validate()?;
+
+    let plan = pending_migrations(&config).await?;
+    for name in &plan {
+        println!("{name}");
+    }
+    match plan.len() {
+        0 => eprintln!("No pending migrations"),
+        1 => eprintln!("1 pending migration"),
+        n => eprintln!("{n} pending migrations"),
+    }
+    Ok(())
+}
+
+/// Start serving `transport` on a task in `tasks`
+async fn spawn_transport(
+    tasks: &mut JoinSet<Result<()>>,
+    transport: Transport,
+    config: &Config,
+    db: &ottershipper_db::Database,
+    mcp_server: &McpServer,
+    shutdown: &CancellationToken,
+) -> Result<()> {
+    match transport {
+        Transport::Http => {
             tracing::info!(
                 "OtterShipper ready to accept MCP requests via HTTP on {}:{}",
                 config.server.bind_address,
@@ -50,43 +597,231 @@ async fn main() -> Result<()> {
                 config.server.port,
                 config.server.port
             );
+            tracing::info!(
+                "Export endpoint: http://localhost:{}{}",
+                config.server.port,
+                ottershipper_server::EXPORT_PATH
+            );
 
             // Run HTTP server with SSE transport
-            let bind_addr =
-                format!("{}:{}", config.server.bind_address, config.server.port).parse()?;
-            let mut sse_server = SseServer::serve(bind_addr).await?;
-
-            // Process incoming SSE transports
-            while let Some(transport) = sse_server.next_transport().await {
-                let server = mcp_server.clone();
-                tokio::spawn(async move {
-                    match server.serve(transport).await {
-                        Ok(service) => {
-                            if let Err(e) = service.waiting().await {
-                                tracing::error!("Service error: {}", e);
-                            }
-                        }
-                        Err(e) => {
-                            tracing::error!("Failed to serve transport: {}", e);
-                        }
-                    }
-                });
-            }
+            let listener = tokio::net::TcpListener::bind((
+                config.server.bind_address.as_str(),
+                config.server.port,
+            ))
+            .await
+            .map_err(|source| StartupError::Bind {
+                address: config.server.bind_address.clone(),
+                port: config.server.port,
+                source,
+            })?;
+            let sse_server = ottershipper_server::serve_sse(
+                listener,
+                config.server.max_request_bytes,
+                &config.cors,
+                ottershipper_server::export_routes(db.clone()),
+            )
+            .await?;
+
+            let server = mcp_server.clone();
+            let shutdown = shutdown.clone();
+            tasks.spawn(async move {
+                ottershipper_server::run_sse(server, sse_server, shutdown).await;
+                anyhow::Ok(())
+            });
         }
-        "stdio" => {
-            tracing::info!("MCP server initialized successfully");
+        Transport::Stdio => {
             tracing::info!(
                 "OtterShipper ready to accept MCP requests via stdio (for local Claude Code)"
             );
 
             // Run the MCP server (stdio transport for local Claude Code)
-            let service = mcp_server.serve(stdio()).await?;
-            service.waiting().await?;
-        }
-        other => {
-            anyhow::bail!("Invalid transport type: {other}. Must be 'stdio' or 'http'");
+            let server = mcp_server.clone();
+            let ct = shutdown.child_token();
+            tasks.spawn(async move {
+                let service = ottershipper_server::serve_with_retry(
+                    server,
+                    stdio(),
+                    || Some(stdio()),
+                    ct,
+                    ottershipper_server::classify_serve_error,
+                )
+                .await?;
+                service.waiting().await?;
+                anyhow::Ok(())
+            });
         }
     }
 
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_startup_summary() {
+        assert_eq!(startup_summary(0), "Database is empty: no applications yet");
+        assert_eq!(startup_summary(1), "Database contains 1 application");
+        assert_eq!(startup_summary(42), "Database contains 42 applications");
+    }
+
+    #[tokio::test]
+    async fn test_migrate_only() -> Result<()> {
+        let dir = tempfile::tempdir()?;
+        let mut config = Config::default();
+        config.database.path = dir.path().join("data").join("otter.db");
+
+        let applied = migrate_only(&config).await?;
+        assert_eq!(
+            applied.last().map(String::as_str),
+            Some(ottershipper_db::Database::latest_schema_version())
+        );
+
+        let db = ottershipper_db::Database::new(&config.database.path).await?;
+        assert!(db.migration_plan().await?.is_empty());
+        db.close().await?;
+
+        // Running it again applies nothing
+        assert!(migrate_only(&config).await?.is_empty());
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_pending_migrations() -> Result<()> {
+        let dir = tempfile::tempdir()?;
+        let mut config = Config::default();
+        config.database.path = dir.path().join("data").join("otter.db");
+
+        // Planning against a path with no database creates nothing
+        let all: Vec<_> = ottershipper_db::Database::migration_names()
+            .map(str::to_string)
+            .collect();
+        assert_eq!(pending_migrations(&config).await?, all);
+        assert!(!dir.path().join("data").exists());
+
+        migrate_only(&config).await?;
+        assert!(pending_migrations(&config).await?.is_empty());
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_database_open_errors() {
+        // A directory can't be opened as a database file
+        let dir = tempfile::tempdir().unwrap();
+        let Err(source) = ottershipper_db::Database::new(dir.path()).await else {
+            panic!("opening a directory as a database should fail");
+        };
+        let unopenable = StartupError::DatabaseOpen {
+            path: dir.path().to_path_buf(),
+            source,
+        };
+        let message = unopenable.to_string();
+        assert!(message.starts_with(&format!(
+            "cannot open database at {}: ",
+            dir.path().display()
+        )));
+        assert!(message.ends_with("; check permissions and free disk space"));
+        assert!(!message.contains("Database error:"));
+        assert_eq!(unopenable.exit_code(), 3);
+
+        let path = Path::new("/data/otter.db");
+
+        let timeout = StartupError::DatabaseOpen {
+            path: path.to_path_buf(),
+            source: ottershipper_db::DbError::Timeout(std::time::Duration::from_secs(5)),
+        };
+        assert!(timeout
+            .to_string()
+            .starts_with("cannot open database at /data/otter.db: Database operation timed out"));
+        assert_eq!(timeout.exit_code(), 3);
+
+        let corrupt = StartupError::DatabaseOpen {
+            path: path.to_path_buf(),
+            source: ottershipper_db::DbError::Corrupt("/data/otter.db".to_string()),
+        };
+        assert!(corrupt.to_string().contains("restore it from a backup"));
+        assert_eq!(corrupt.exit_code(), 4);
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn test_unwritable_database_dir() -> Result<()> {
+        use std::os::unix::fs::PermissionsExt;
+
+        let dir = tempfile::tempdir()?;
+        assert!(is_dir_writable(dir.path()));
+        // The probe file is cleaned up
+        assert_eq!(std::fs::read_dir(dir.path())?.count(), 0);
+
+        std::fs::set_permissions(dir.path(), std::fs::Permissions::from_mode(0o555))?;
+        assert!(!is_dir_writable(dir.path()));
+
+        let mut config = Config::default();
+        config.database.path = dir.path().join("otter.db");
+        let result = connect_database(&config).await;
+        std::fs::set_permissions(dir.path(), std::fs::Permissions::from_mode(0o755))?;
+
+        let Err(err) = result else {
+            panic!("opened a database in a read-only directory");
+        };
+        let err = err.downcast::<StartupError>()?;
+        assert!(matches!(err, StartupError::DatabaseDirNotWritable { .. }));
+        assert_eq!(
+            err.to_string(),
+            format!(
+                "database directory {} is not writable by the current user; run ottershipper as a user that can write to it or fix its permissions",
+                dir.path().display()
+            )
+        );
+        assert_eq!(err.exit_code(), 3);
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_connect_in_memory_database() -> Result<()> {
+        assert_eq!(
+            database_url(Path::new("sqlite::memory:")),
+            Some("sqlite::memory:")
+        );
+        assert_eq!(database_url(Path::new(":memory:")), None);
+        assert_eq!(database_url(Path::new("data/otter.db")), None);
+
+        // Neither probes the working directory, which may be read-only
+        for path in [":memory:", "sqlite::memory:"] {
+            let mut config = Config::default();
+            config.database.path = PathBuf::from(path);
+            let db = connect_database(&config).await?;
+            db.migrate().await?;
+            db.applications().create("in-memory").await?;
+            db.close().await?;
+        }
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_startup_exit_codes_are_distinct() {
+        let io = || std::io::Error::from(std::io::ErrorKind::PermissionDenied);
+        let dir = StartupError::DatabaseDir {
+            path: PathBuf::from("/data/otter.db"),
+            source: io(),
+        };
+        let migrate = StartupError::Migrate {
+            path: PathBuf::from("/data/otter.db"),
+            source: ottershipper_db::DbError::Internal("bad migration".to_string()),
+        };
+        let bind = StartupError::Bind {
+            address: "0.0.0.0".to_string(),
+            port: 8080,
+            source: io(),
+        };
+        assert_eq!(dir.exit_code(), 3);
+        assert_eq!(migrate.exit_code(), 5);
+        assert_eq!(bind.exit_code(), 6);
+        assert!(bind
+            .to_string()
+            .starts_with("cannot listen on 0.0.0.0:8080"));
+    }
+}
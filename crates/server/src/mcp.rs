@@ -1,20 +1,397 @@
-use super::schemas::CreateAppInput;
+use super::schemas::{
+    ApplyAppInput, ArchiveAppInput, AuditLogInput, CheckpointInput, CloneAppInput,
+    CountMatchingInput, CreateAppInput, CreatedBetweenInput, DeleteAppInput, DeleteAppsInput,
+    DeleteMetadataInput, DescribeAppInput, ErrorEnvelope, ErrorReason, ExistingApplication,
+    FilterAppsInput, FindAppsInput, FindByMetaInput, GetAppInput, GetMetadataInput, ListAppsInput,
+    QueryAppsInput, RecentAppsInput, RenameAppInput, RenamePrefixInput, ResetInput, SearchAllInput,
+    SearchInput, SetMetadataInput, SuccessEnvelope, TimestampInput, TouchAppInput,
+    ValidateNameInput,
+};
+use crate::config::{LimitsConfig, ToolsConfig};
+use crate::rate_limit::RateLimiter;
 use ottershipper_core::ApplicationService;
+use ottershipper_db::{
+    AppQuery, Application, ApplicationView, CheckpointMode, DbError, NameFilter,
+};
+use rmcp::handler::server::tool::ToolCallContext;
 use rmcp::handler::server::{router::tool::ToolRouter, tool::Parameters, ServerHandler};
 use rmcp::model::{
-    CallToolResult, Content, ErrorCode, ErrorData as McpError, Implementation, InitializeResult,
-    ProtocolVersion, ServerCapabilities,
+    AnnotateAble, CallToolRequestParam, CallToolResult, Content, ErrorCode, ErrorData as McpError,
+    Implementation, InitializeResult, ListResourcesResult, ListToolsResult, PaginatedRequestParam,
+    ProtocolVersion, RawResource, ReadResourceRequestParam, ReadResourceResult, ResourceContents,
+    ServerCapabilities,
 };
-use rmcp::{tool, tool_handler, tool_router};
+use rmcp::service::RequestContext;
+use rmcp::{tool, tool_router, RoleServer};
+use serde::Serialize;
 use serde_json::json;
+use std::path::Path;
+use std::sync::Arc;
 use std::{borrow::Cow, future::Future};
-use tracing::info;
+use tracing::{info, warn};
+
+/// Default number of applications returned by `otter_recent_apps`
+const DEFAULT_RECENT_LIMIT: i64 = 10;
+
+/// Default page size for `otter_list_apps` with a cursor
+const DEFAULT_CURSOR_LIMIT: i64 = 100;
+
+/// Default page size for `otter_query_apps`
+const DEFAULT_QUERY_LIMIT: i64 = 50;
+
+/// Default number of entries returned by `otter_audit_log`
+const DEFAULT_AUDIT_LIMIT: i64 = 10;
+
+/// Literal `otter_reset` requires in `confirm`
+const RESET_CONFIRMATION: &str = "DELETE ALL";
+
+/// Suggested wait before retrying a call that failed because the database was busy
+const BUSY_RETRY_AFTER_MS: u64 = 250;
+
+/// URI of the markdown help resource
+pub const HELP_URI: &str = "otter://help";
+
+/// Built-in help served at [`HELP_URI`]; `{name_rules}` is filled in per server
+const DEFAULT_HELP: &str = include_str!("help.md");
+
+/// A tool's definition as advertised to clients
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct ToolDescriptor {
+    /// Tool name, e.g. `otter_create_app`
+    pub name: String,
+    /// What the tool does
+    pub description: Option<String>,
+    /// JSON Schema of the tool's arguments
+    pub input_schema: serde_json::Value,
+    /// JSON Schema of the result body on success ([`SuccessEnvelope`])
+    pub output_schema: serde_json::Value,
+    /// JSON Schema of the `data` attached to errors ([`ErrorEnvelope`])
+    pub error_schema: serde_json::Value,
+}
+
+/// JSON representation of an application, shared by every tool
+///
+/// All tools go through this so they report the same fields in the same shape.
+fn serialize_application(app: &Application) -> serde_json::Value {
+    json!(ApplicationView::from(app))
+}
+
+/// Wrap a tool's response fields in a [`SuccessEnvelope`] and serialize it as the result
+///
+/// Serialization failures become an `INTERNAL_ERROR` instead of a panic, which
+/// would otherwise kill the task serving the connection.
+fn json_result(response: serde_json::Value) -> Result<CallToolResult, McpError> {
+    let serde_json::Value::Object(fields) = response else {
+        return Err(tool_error(
+            ErrorCode::INTERNAL_ERROR,
+            ErrorReason::Internal,
+            "Tool response is not a JSON object",
+        ));
+    };
+    let envelope = SuccessEnvelope {
+        success: true,
+        fields,
+    };
+    let text = serde_json::to_string_pretty(&envelope).map_err(|e| {
+        tool_error(
+            ErrorCode::INTERNAL_ERROR,
+            ErrorReason::Internal,
+            format!("Failed to serialize response: {e}"),
+        )
+    })?;
+
+    Ok(CallToolResult::success(vec![Content::text(text)]))
+}
+
+/// Build a tool error carrying an [`ErrorEnvelope`] as its `data`
+fn tool_error(code: ErrorCode, reason: ErrorReason, message: impl Into<String>) -> McpError {
+    error_with_envelope(
+        code,
+        ErrorEnvelope {
+            success: false,
+            reason,
+            message: message.into(),
+            retry_after_secs: None,
+            retry_after_ms: None,
+            existing: None,
+        },
+    )
+}
+
+/// Error for a database failure not handled more specifically by the tool
+///
+/// Reported as `busy`, with a suggested `retry_after_ms`, if the database was
+/// only overloaded, so clients can back off instead of treating it as fatal,
+/// and as `storage_full` if the disk is full, so operators can alert on it.
+fn db_error(e: &DbError, message: impl Into<String>) -> McpError {
+    let busy = e.is_busy();
+    error_with_envelope(
+        ErrorCode::INTERNAL_ERROR,
+        ErrorEnvelope {
+            success: false,
+            reason: match e {
+                _ if busy => ErrorReason::Busy,
+                DbError::StorageFull(_) => ErrorReason::StorageFull,
+                _ => ErrorReason::Internal,
+            },
+            message: message.into(),
+            retry_after_secs: None,
+            retry_after_ms: busy.then_some(BUSY_RETRY_AFTER_MS),
+            existing: None,
+        },
+    )
+}
+
+fn error_with_envelope(code: ErrorCode, envelope: ErrorEnvelope) -> McpError {
+    let data = serde_json::to_value(&envelope).ok();
+    McpError {
+        code,
+        message: Cow::from(envelope.message),
+        data,
+    }
+}
+
+impl From<&DbError> for ErrorReason {
+    fn from(e: &DbError) -> Self {
+        match e {
+            DbError::NotFound(_) => Self::NotFound,
+            DbError::InvalidName(_) => Self::InvalidName,
+            DbError::DuplicateName(_) => Self::DuplicateName,
+            DbError::InvalidArgument(_) => Self::InvalidParams,
+            DbError::LimitExceeded(_) => Self::LimitExceeded,
+            DbError::ReadOnly => Self::InvalidRequest,
+            DbError::StorageFull(_) => Self::StorageFull,
+            e if e.is_busy() => Self::Busy,
+            _ => Self::Internal,
+        }
+    }
+}
+
+impl From<&Application> for ExistingApplication {
+    fn from(app: &Application) -> Self {
+        let view = ApplicationView::from(app);
+        Self {
+            id: view.id,
+            name: view.name,
+            created_at: view.created_at,
+        }
+    }
+}
 
 /// MCP Server for `OtterShipper`
 #[derive(Clone)]
 pub struct McpServer {
     service: ApplicationService,
     tool_router: ToolRouter<Self>,
+    transport: Option<String>,
+    instructions: Option<String>,
+    help: Option<String>,
+    rate_limiter: Option<Arc<RateLimiter>>,
+    require_confirmation: bool,
+    default_page_size: Option<i64>,
+    max_page_size: Option<i64>,
+}
+
+impl McpServer {
+    /// Guidance sent to clients unless overridden with [`McpServer::with_instructions`]
+    fn default_instructions(&self) -> String {
+        format!(
+            "OtterShipper manages applications, each with a unique name and ID.\n\
+             \n\
+             Tools:\n\
+             - otter_create_app: create an application (omit name to generate one)\n\
+             - otter_validate_name: check a name against the naming rules without creating anything\n\
+             - otter_apply_app: create or update an application by name\n\
+             - otter_clone_app: copy an application under a new name\n\
+             - otter_get_app: get one application by ID or name\n\
+             - otter_describe_app: an application with its metadata and recent audit entries\n\
+             - otter_list_apps / otter_recent_apps / otter_apps_created_between: browse applications\n\
+             - otter_recent_activity: applications created in the last 24 hours, 7 days, and 30 days\n\
+             - otter_status_summary: number of applications with each status\n\
+             - otter_find_apps: find applications by name pattern (e.g. app-prod-*)\n\
+             - otter_filter_apps: find applications by name length and pattern\n\
+             - otter_query_apps: filter, sort, and page through applications in one call\n\
+             - otter_count_matching: count applications matching otter_query_apps filters\n\
+             - otter_search: find applications by words in their name and description, best match first\n\
+             - otter_search_all: search names, descriptions, and metadata values at once\n\
+             - otter_set_metadata / otter_get_metadata / otter_delete_metadata: key-value metadata\n\
+             - otter_find_by_meta: find the applications with a metadata key set to a value\n\
+             - otter_rename_app: rename an application by ID or current name\n\
+             - otter_rename_prefix: re-prefix many application names at once\n\
+             - otter_touch_app: mark an application as active now (updates updated_at)\n\
+             - otter_archive_app / otter_unarchive_app: hide applications from listings without deleting them\n\
+             - otter_delete_app / otter_delete_apps: delete applications by ID or name\n\
+             - otter_reset: delete every application (admin; only if the server allows it; requires confirmation)\n\
+             - otter_audit_log: see who created, updated, or deleted what\n\
+             - otter_ping: check that the server is responsive\n\
+             - otter_server_info: server version and state\n\
+             - otter_diagnostics: database size, connection pool, and migrations (admin)\n\
+             - otter_checkpoint: checkpoint the database write-ahead log (admin)\n\
+             \n\
+             {}\n\
+             \n\
+             Read the {HELP_URI} resource for full documentation and examples.\n\
+             \n\
+             Before creating, call otter_create_app with dry_run=true to check that a name \
+             is valid and available. When retrying a create, pass the same idempotency_key \
+             so the application isn't created twice.",
+            self.service.name_policy().describe()
+        )
+    }
+
+    /// Markdown served at [`HELP_URI`] unless overridden with [`McpServer::with_help`]
+    fn default_help(&self) -> String {
+        DEFAULT_HELP.replace("{name_rules}", &self.service.name_policy().describe())
+    }
+
+    /// The service, acting for `owner` if one is given
+    fn service_for(&self, owner: Option<String>) -> Cow<'_, ApplicationService> {
+        match owner {
+            Some(owner) => Cow::Owned(self.service.for_owner(owner)),
+            None => Cow::Borrowed(&self.service),
+        }
+    }
+
+    /// Validate a name and check availability for `otter_create_app` with `dry_run`
+    async fn dry_run_create(
+        &self,
+        service: &ApplicationService,
+        name: Option<&str>,
+    ) -> Result<CallToolResult, McpError> {
+        let Some(name) = name else {
+            return Err(tool_error(
+                ErrorCode::INVALID_PARAMS,
+                ErrorReason::InvalidParams,
+                "dry_run requires a name",
+            ));
+        };
+        info!("Dry-run create for application: {}", name);
+
+        match service.preflight_create(name).await {
+            Ok(check) => {
+                let response = json!({
+                    "dry_run": true,
+                    "name": name,
+                    "valid": check.valid,
+                    "would_create": check.would_create,
+                    "reason": check.reason
+                });
+
+                json_result(response)
+            }
+            Err(e) => Err(db_error(
+                &e,
+                format!("Failed to check application name: {e}"),
+            )),
+        }
+    }
+
+    /// Archive (`archived == true`) or unarchive the application named by `input`
+    async fn set_archived(
+        &self,
+        input: ArchiveAppInput,
+        archived: bool,
+    ) -> Result<CallToolResult, McpError> {
+        let verb = if archived { "archive" } else { "unarchive" };
+        let service = self.service_for(input.owner);
+        let id = match (input.id, input.name) {
+            (Some(id), None) => Ok(id),
+            (None, Some(name)) => service
+                .get_app_by_name_or_err(&name)
+                .await
+                .map(|app| app.id),
+            _ => {
+                return Err(tool_error(
+                    ErrorCode::INVALID_PARAMS,
+                    ErrorReason::InvalidParams,
+                    "Provide exactly one of 'id' or 'name'",
+                ))
+            }
+        };
+        info!("Setting archived = {} for application {:?}", archived, id);
+
+        let result = match id {
+            Ok(id) if archived => service.archive_app(&id).await,
+            Ok(id) => service.unarchive_app(&id).await,
+            Err(e) => Err(e),
+        };
+        match result {
+            Ok(app) => {
+                let response = json!({
+                    "application": serialize_application(&app),
+                    "message": format!("Successfully {verb}d application '{}'", app.name)
+                });
+
+                json_result(response)
+            }
+            Err(DbError::NotFound(target)) => Err(tool_error(
+                ErrorCode::RESOURCE_NOT_FOUND,
+                ErrorReason::NotFound,
+                format!("No application found matching '{target}'"),
+            )),
+            Err(e) => Err(db_error(&e, format!("Failed to {verb} application: {e}"))),
+        }
+    }
+
+    /// Keyset-paginated `otter_list_apps`
+    async fn list_apps_after(
+        &self,
+        cursor: &str,
+        limit: Option<i64>,
+        include_archived: bool,
+    ) -> Result<CallToolResult, McpError> {
+        let cursor = parse_cursor(cursor)?;
+        let (limit, limit_clamped) = self.page_limit(limit, Some(DEFAULT_CURSOR_LIMIT));
+        let limit = limit.unwrap_or(DEFAULT_CURSOR_LIMIT);
+        info!(
+            "Listing applications (limit: {}, cursor: {:?})",
+            limit, cursor
+        );
+
+        match self
+            .service
+            .list_apps_after(
+                cursor.as_ref().map(|(at, id)| (*at, id.as_str())),
+                limit,
+                include_archived,
+            )
+            .await
+        {
+            Ok(page) => {
+                let response = json!({
+                    "applications": page.applications.iter().map(serialize_application).collect::<Vec<_>>(),
+                    "count": page.applications.len(),
+                    "limit": limit,
+                    "limit_clamped": limit_clamped,
+                    "next_cursor": page.next_cursor.map(|(at, id)| format!("{at}:{id}"))
+                });
+
+                json_result(response)
+            }
+            Err(e) => Err(db_error(&e, format!("Failed to list applications: {e}"))),
+        }
+    }
+}
+
+/// Decode an `otter_list_apps` cursor of the form `<created_at>:<id>`
+///
+/// An empty cursor means the first page.
+fn parse_cursor(cursor: &str) -> Result<Option<(i64, String)>, McpError> {
+    if cursor.is_empty() {
+        return Ok(None);
+    }
+
+    cursor
+        .split_once(':')
+        .and_then(|(at, id)| Some((at.parse().ok()?, id.to_string())))
+        .map(Some)
+        .ok_or_else(|| {
+            tool_error(
+                ErrorCode::INVALID_PARAMS,
+                ErrorReason::InvalidParams,
+                format!("Invalid cursor: {cursor}"),
+            )
+        })
 }
 
 #[tool_router]
@@ -24,89 +401,1558 @@ impl McpServer {
     pub fn new(service: ApplicationService) -> Self {
         Self {
             service,
-            tool_router: Self::tool_router(),
+            tool_router: Self::default_tools(),
+            transport: None,
+            instructions: None,
+            help: None,
+            rate_limiter: None,
+            require_confirmation: false,
+            default_page_size: None,
+            max_page_size: None,
+        }
+    }
+
+    /// Replace the default instructions sent to clients on initialization
+    #[must_use]
+    pub fn with_instructions(mut self, instructions: impl Into<String>) -> Self {
+        self.instructions = Some(instructions.into());
+        self
+    }
+
+    /// Replace the default instructions with the contents of the file at `path`
+    ///
+    /// The file is read once, here. If it can't be read or isn't UTF-8, a
+    /// warning is logged and the instructions are left unchanged.
+    #[must_use]
+    pub fn with_instructions_file(self, path: &Path) -> Self {
+        let contents = std::fs::read(path)
+            .map_err(|e| e.to_string())
+            .and_then(|bytes| String::from_utf8(bytes).map_err(|_| "not valid UTF-8".to_string()));
+        match contents {
+            Ok(instructions) => self.with_instructions(instructions),
+            Err(e) => {
+                warn!(
+                    "Ignoring instructions file {}: {}; using the default instructions",
+                    path.display(),
+                    e
+                );
+                self
+            }
+        }
+    }
+
+    /// Replace the built-in markdown served as the [`HELP_URI`] resource
+    #[must_use]
+    pub fn with_help(mut self, help: impl Into<String>) -> Self {
+        self.help = Some(help.into());
+        self
+    }
+
+    /// Record the configured transport, as reported by `otter_server_info`
+    #[must_use]
+    pub fn with_transport(mut self, transport: impl Into<String>) -> Self {
+        self.transport = Some(transport.into());
+        self
+    }
+
+    /// Apply the rate limits in `limits`, shared by every clone of this server
+    #[must_use]
+    pub fn with_limits(mut self, limits: &LimitsConfig) -> Self {
+        self.rate_limiter = limits
+            .tool_calls_per_minute
+            .map(|per_minute| Arc::new(RateLimiter::new(per_minute)));
+        self
+    }
+
+    /// Page size for listing tools when a call omits `limit`, and the largest
+    /// page they return (no default and no cap when `None`)
+    #[must_use]
+    pub fn with_page_sizes(mut self, default: Option<u32>, max: Option<u32>) -> Self {
+        self.default_page_size = default.map(i64::from);
+        self.max_page_size = max.map(i64::from);
+        self
+    }
+
+    /// Page size for a call that asked for `requested`, and whether the
+    /// configured maximum cut it down
+    ///
+    /// An omitted limit falls back to the configured default, then to
+    /// `fallback`; `None` and negative limits mean "unlimited".
+    fn page_limit(&self, requested: Option<i64>, fallback: Option<i64>) -> (Option<i64>, bool) {
+        let limit = requested.or(self.default_page_size).or(fallback);
+        match (limit, self.max_page_size) {
+            (Some(limit), Some(max)) if limit > max || limit < 0 => (Some(max), true),
+            (None, Some(max)) => (Some(max), true),
+            (limit, _) => (limit, false),
+        }
+    }
+
+    /// Reject the call with a "retry after" error if the rate limit is exhausted
+    fn check_rate_limit(&self, tool: &str) -> Result<(), McpError> {
+        let Some(limiter) = &self.rate_limiter else {
+            return Ok(());
+        };
+
+        limiter.try_acquire().map_err(|retry_after| {
+            // Round up so retrying after this many seconds succeeds
+            let retry_after_secs =
+                retry_after.as_secs() + u64::from(retry_after.subsec_nanos() > 0);
+            warn!("Rate limited call to {}", tool);
+            error_with_envelope(
+                ErrorCode::INVALID_REQUEST,
+                ErrorEnvelope {
+                    success: false,
+                    reason: ErrorReason::RateLimited,
+                    message: format!(
+                        "Rate limited: at most {} tool calls per minute, retry after {retry_after_secs}s",
+                        limiter.per_minute()
+                    ),
+                    retry_after_secs: Some(retry_after_secs),
+                    retry_after_ms: None,
+                    existing: None,
+                },
+            )
+        })
+    }
+
+    /// Tools exposed without a `[tools]` config: all but the opt-in `otter_reset`
+    fn default_tools() -> ToolRouter<Self> {
+        let mut tool_router = Self::tool_router();
+        let tools = ToolsConfig::default();
+        tool_router.map.retain(|name, _| tools.is_enabled(name));
+        tool_router
+    }
+
+    /// Expose only the tools allowed by `tools`
+    ///
+    /// Names in `tools` that don't match any tool are logged and ignored.
+    #[must_use]
+    pub fn with_tools(mut self, tools: &ToolsConfig) -> Self {
+        let mut tool_router = Self::tool_router();
+        for name in tools.enabled.iter().flatten().chain(&tools.disabled) {
+            if !tool_router.has_route(name) {
+                warn!("Ignoring unknown tool in [tools] config: {}", name);
+            }
         }
+
+        tool_router.map.retain(|name, _| tools.is_enabled(name));
+        self.tool_router = tool_router;
+        self.require_confirmation = tools.require_confirmation;
+        info!(
+            "Exposing {} of {} tools",
+            self.tool_router.map.len(),
+            Self::tool_router().map.len()
+        );
+        self
+    }
+
+    /// Name, description, and schemas of every exposed tool, sorted by name
+    ///
+    /// This is the information clients receive from `tools/list`, plus the
+    /// response envelopes every tool uses, for callers that want it without an
+    /// MCP session.
+    #[must_use]
+    pub fn tool_descriptors(&self) -> Vec<ToolDescriptor> {
+        let output_schema =
+            serde_json::to_value(schemars::schema_for!(SuccessEnvelope)).unwrap_or_default();
+        let error_schema =
+            serde_json::to_value(schemars::schema_for!(ErrorEnvelope)).unwrap_or_default();
+
+        let mut descriptors: Vec<_> = self
+            .tool_router
+            .list_all()
+            .into_iter()
+            .map(|tool| ToolDescriptor {
+                name: tool.name.into_owned(),
+                description: tool.description.map(Cow::into_owned),
+                input_schema: serde_json::Value::Object((*tool.input_schema).clone()),
+                output_schema: output_schema.clone(),
+                error_schema: error_schema.clone(),
+            })
+            .collect();
+        descriptors.sort_by(|a, b| a.name.cmp(&b.name));
+        descriptors
     }
 
     /// Create a new application
     #[tool(
-        description = "Create a new application in OtterShipper. Returns the application ID, name, and creation timestamp."
+        description = "Create a new application in OtterShipper. If no name is given, a random valid name is generated. Returns the application ID, name, and creation timestamp. Set dry_run to check a name without creating anything. If the name is taken, the error data includes the existing application under existing."
     )]
     async fn otter_create_app(
         &self,
         Parameters(input): Parameters<CreateAppInput>,
     ) -> Result<CallToolResult, McpError> {
-        info!("Creating application: {}", input.name);
+        let service = self.service_for(input.owner);
+        if input.dry_run == Some(true) {
+            return self.dry_run_create(&service, input.name.as_deref()).await;
+        }
 
-        match self.service.create_app(input.name.clone()).await {
-            Ok(app) => {
+        let result = match (input.name, input.idempotency_key) {
+            (name, Some(key)) => {
+                info!(
+                    "Creating application {:?} with idempotency key {}",
+                    name, key
+                );
+                service.create_app_idempotent(name, &key).await
+            }
+            (Some(name), None) => {
+                info!("Creating application: {}", name);
+                service.create_app(name).await.map(|app| (app, true))
+            }
+            (None, None) => {
+                info!("Creating application with generated name");
+                service.create_app_autoname().await.map(|app| (app, true))
+            }
+        };
+
+        match result {
+            Ok((app, created)) => {
+                let message = if created {
+                    format!(
+                        "Successfully created application '{}' with ID {}",
+                        app.name, app.id
+                    )
+                } else {
+                    format!(
+                        "Application '{}' with ID {} was already created with this idempotency key",
+                        app.name, app.id
+                    )
+                };
                 let response = json!({
-                    "success": true,
-                    "application": {
-                        "id": app.id,
-                        "name": app.name,
-                        "created_at": app.created_at
+                    "created": created,
+                    "application": serialize_application(&app),
+                    "message": message
+                });
+
+                json_result(response)
+            }
+            Err(DbError::DuplicateName(name)) => {
+                // Best effort: the error stands even if the existing app can't be loaded
+                let existing = match service.get_app_by_name(&name).await {
+                    Ok(existing) => existing,
+                    Err(e) => {
+                        warn!("Failed to load existing application '{}': {}", name, e);
+                        None
+                    }
+                };
+                Err(error_with_envelope(
+                    ErrorCode::INVALID_PARAMS,
+                    ErrorEnvelope {
+                        success: false,
+                        reason: ErrorReason::DuplicateName,
+                        message: format!(
+                            "Failed to create application: {}",
+                            DbError::DuplicateName(name)
+                        ),
+                        retry_after_secs: None,
+                        retry_after_ms: None,
+                        existing: existing.as_ref().map(ExistingApplication::from),
                     },
-                    "message": format!("Successfully created application '{}' with ID {}", app.name, app.id)
+                ))
+            }
+            Err(e @ DbError::InvalidName(_)) => Err(tool_error(
+                ErrorCode::INVALID_PARAMS,
+                ErrorReason::from(&e),
+                format!("Failed to create application: {e}"),
+            )),
+            Err(e @ DbError::LimitExceeded(_)) => Err(tool_error(
+                ErrorCode::INVALID_REQUEST,
+                ErrorReason::from(&e),
+                format!(
+                    "Failed to create application: {e}. Delete unused applications or raise max_applications."
+                ),
+            )),
+            Err(e) => Err(db_error(&e, format!("Failed to create application: {e}"))),
+        }
+    }
+
+    /// Check a name against the naming rules without creating anything
+    #[tool(
+        description = "Check whether a name is acceptable for a new application, using exactly the rules otter_create_app applies. Returns valid, the reason when invalid, and the normalized name (surrounding whitespace removed, and recased if the naming rules say so) that would be stored. Does not check whether the name is already taken and never modifies anything."
+    )]
+    async fn otter_validate_name(
+        &self,
+        Parameters(input): Parameters<ValidateNameInput>,
+    ) -> Result<CallToolResult, McpError> {
+        let normalized = self.service.name_policy().normalize(&input.name);
+        let reason = self
+            .service
+            .name_policy()
+            .validate(&normalized)
+            .err()
+            .map(|e| e.to_string());
+
+        let response = json!({
+            "valid": reason.is_none(),
+            "reason": reason,
+            "normalized": normalized
+        });
+
+        json_result(response)
+    }
+
+    /// List applications, optionally one page at a time
+    #[tool(
+        description = "List applications in OtterShipper, newest first. Returns an array of applications with their IDs, names, and creation timestamps, plus total, limit, offset, and has_more for pagination. Omit limit to list all applications, or the server's default page size if one is configured. If the server caps page sizes, bigger limits are reduced and limit_clamped is true. Pass cursor instead of offset for pages that stay stable while applications change; those responses include next_cursor (null on the last page). Archived applications are left out unless include_archived is true."
+    )]
+    async fn otter_list_apps(
+        &self,
+        Parameters(input): Parameters<ListAppsInput>,
+    ) -> Result<CallToolResult, McpError> {
+        let include_archived = input.include_archived.unwrap_or(false);
+        if let Some(cursor) = &input.cursor {
+            if input.offset.is_some() {
+                return Err(tool_error(
+                    ErrorCode::INVALID_PARAMS,
+                    ErrorReason::InvalidParams,
+                    "cursor and offset cannot be combined",
+                ));
+            }
+            return self
+                .list_apps_after(cursor, input.limit, include_archived)
+                .await;
+        }
+
+        let (limit, limit_clamped) = self.page_limit(input.limit, None);
+        info!(
+            "Listing applications (limit: {:?}, offset: {:?})",
+            limit, input.offset
+        );
+
+        match self
+            .service
+            .list_apps_page(limit, input.offset.unwrap_or(0), include_archived)
+            .await
+        {
+            Ok(page) => {
+                let response = json!({
+                    "applications": page.applications.iter().map(serialize_application).collect::<Vec<_>>(),
+                    "count": page.applications.len(),
+                    "total": page.total,
+                    "limit": page.limit,
+                    "limit_clamped": limit_clamped,
+                    "offset": page.offset,
+                    "has_more": page.has_more()
+                });
+
+                json_result(response)
+            }
+            Err(e) => Err(db_error(&e, format!("Failed to list applications: {e}"))),
+        }
+    }
+
+    /// Create or update an application by name
+    #[tool(
+        description = "Declaratively ensure an application exists: creates it if no application has this name, otherwise updates its description in place (ID and created_at are kept). Returns the application and whether it was created or updated."
+    )]
+    async fn otter_apply_app(
+        &self,
+        Parameters(input): Parameters<ApplyAppInput>,
+    ) -> Result<CallToolResult, McpError> {
+        info!("Applying application: {}", input.name);
+
+        match self
+            .service
+            .apply_app(&input.name, input.description.as_deref())
+            .await
+        {
+            Ok((app, created)) => {
+                let response = json!({
+                    "created": created,
+                    "application": serialize_application(&app),
+                    "message": if created {
+                        format!("Created application '{}'", app.name)
+                    } else {
+                        format!("Updated application '{}'", app.name)
+                    }
+                });
+
+                json_result(response)
+            }
+            Err(e @ DbError::InvalidName(_)) => Err(tool_error(
+                ErrorCode::INVALID_PARAMS,
+                ErrorReason::from(&e),
+                format!("Failed to apply application: {e}"),
+            )),
+            Err(e @ DbError::LimitExceeded(_)) => Err(tool_error(
+                ErrorCode::INVALID_REQUEST,
+                ErrorReason::from(&e),
+                format!(
+                    "Failed to apply application: {e}. Delete unused applications or raise max_applications."
+                ),
+            )),
+            Err(e) => Err(db_error(&e, format!("Failed to apply application: {e}"))),
+        }
+    }
+
+    /// Copy an application under a new name
+    #[tool(
+        description = "Create a new application by copying an existing one: the copy gets the given name, a fresh ID and creation timestamp, and the source's description. The copy is always active, even when the source is archived. Fails if the source doesn't exist or the name is invalid or taken."
+    )]
+    async fn otter_clone_app(
+        &self,
+        Parameters(input): Parameters<CloneAppInput>,
+    ) -> Result<CallToolResult, McpError> {
+        info!("Cloning application {} as {}", input.source_id, input.name);
+
+        match self.service.clone_app(&input.source_id, input.name).await {
+            Ok(app) => {
+                let response = json!({
+                    "application": serialize_application(&app),
+                    "source_id": input.source_id,
+                    "message": format!(
+                        "Cloned application {} as '{}' with ID {}",
+                        input.source_id, app.name, app.id
+                    )
+                });
+
+                json_result(response)
+            }
+            Err(DbError::NotFound(target)) => Err(tool_error(
+                ErrorCode::RESOURCE_NOT_FOUND,
+                ErrorReason::NotFound,
+                format!("No application found matching '{target}'"),
+            )),
+            Err(e @ (DbError::InvalidName(_) | DbError::DuplicateName(_))) => Err(tool_error(
+                ErrorCode::INVALID_PARAMS,
+                ErrorReason::from(&e),
+                format!("Failed to clone application: {e}"),
+            )),
+            Err(e @ DbError::LimitExceeded(_)) => Err(tool_error(
+                ErrorCode::INVALID_REQUEST,
+                ErrorReason::from(&e),
+                format!(
+                    "Failed to clone application: {e}. Delete unused applications or raise max_applications."
+                ),
+            )),
+            Err(e) => Err(db_error(&e, format!("Failed to clone application: {e}"))),
+        }
+    }
+
+    /// Look up an application with everything known about it
+    #[tool(
+        description = "Get everything about one application, by ID or name: the application, its metadata as a key-value object, and its most recent audit entries (newest first). Read consistently in one transaction."
+    )]
+    async fn otter_describe_app(
+        &self,
+        Parameters(input): Parameters<DescribeAppInput>,
+    ) -> Result<CallToolResult, McpError> {
+        let service = self.service_for(input.owner);
+        let id = match (input.id, input.name) {
+            (Some(id), None) => Ok(id),
+            (None, Some(name)) => service
+                .get_app_by_name_or_err(&name)
+                .await
+                .map(|app| app.id),
+            _ => {
+                return Err(tool_error(
+                    ErrorCode::INVALID_PARAMS,
+                    ErrorReason::InvalidParams,
+                    "Provide exactly one of 'id' or 'name'",
+                ))
+            }
+        };
+        info!("Describing application {:?}", id);
+
+        let result = match id {
+            Ok(id) => service.describe_app(&id).await,
+            Err(e) => Err(e),
+        };
+        match result {
+            Ok(details) => {
+                let audit_limit = input
+                    .audit_limit
+                    .unwrap_or(usize::try_from(DEFAULT_AUDIT_LIMIT).unwrap_or(usize::MAX));
+                let metadata: serde_json::Map<_, _> = details
+                    .metadata
+                    .into_iter()
+                    .map(|entry| (entry.key, entry.value.into()))
+                    .collect();
+                let recent_audit: Vec<_> = details
+                    .audit_trail
+                    .into_iter()
+                    .rev()
+                    .take(audit_limit)
+                    .collect();
+                let response = json!({
+                    "application": serialize_application(&details.application),
+                    "metadata": metadata,
+                    "recent_audit": recent_audit
+                });
+
+                json_result(response)
+            }
+            Err(DbError::NotFound(target)) => Err(tool_error(
+                ErrorCode::RESOURCE_NOT_FOUND,
+                ErrorReason::NotFound,
+                format!("No application found matching '{target}'"),
+            )),
+            Err(e) => Err(db_error(&e, format!("Failed to describe application: {e}"))),
+        }
+    }
+
+    /// Look up a single application
+    #[tool(
+        description = "Get a single application by ID or name. Returns its ID, name, and creation timestamp, or a not-found error."
+    )]
+    async fn otter_get_app(
+        &self,
+        Parameters(input): Parameters<GetAppInput>,
+    ) -> Result<CallToolResult, McpError> {
+        let service = self.service_for(input.owner);
+        let result = match (input.id, input.name) {
+            (Some(id), None) => {
+                info!("Getting application by ID: {}", id);
+                service.get_app_or_err(&id).await
+            }
+            (None, Some(name)) => {
+                info!("Getting application by name: {}", name);
+                service.get_app_by_name_or_err(&name).await
+            }
+            _ => {
+                return Err(tool_error(
+                    ErrorCode::INVALID_PARAMS,
+                    ErrorReason::InvalidParams,
+                    "Provide exactly one of 'id' or 'name'",
+                ))
+            }
+        };
+
+        match result {
+            Ok(app) => {
+                let response = json!({
+                    "application": serialize_application(&app)
                 });
 
-                Ok(CallToolResult::success(vec![Content::text(
-                    serde_json::to_string_pretty(&response).unwrap(),
-                )]))
+                json_result(response)
             }
-            Err(e) => Err(McpError {
-                code: ErrorCode::INTERNAL_ERROR,
-                message: Cow::from(format!("Failed to create application: {e}")),
-                data: None,
-            }),
+            Err(DbError::NotFound(target)) => Err(tool_error(
+                ErrorCode::RESOURCE_NOT_FOUND,
+                ErrorReason::NotFound,
+                format!("No application found matching '{target}'"),
+            )),
+            Err(e) => Err(db_error(&e, format!("Failed to get application: {e}"))),
         }
     }
 
-    /// List all applications
+    /// Hide an application from default listings
+    #[tool(
+        description = "Archive an application by ID or by name (provide exactly one). Archived applications are left out of otter_list_apps unless include_archived is set, but can still be fetched with otter_get_app. Nothing is deleted; undo with otter_unarchive_app. Returns the updated application."
+    )]
+    async fn otter_archive_app(
+        &self,
+        Parameters(input): Parameters<ArchiveAppInput>,
+    ) -> Result<CallToolResult, McpError> {
+        self.set_archived(input, true).await
+    }
+
+    /// Return an archived application to default listings
     #[tool(
-        description = "List all applications in OtterShipper. Returns an array of applications with their IDs, names, and creation timestamps."
+        description = "Unarchive an application by ID or by name (provide exactly one), returning it to otter_list_apps. Unarchiving an active application does nothing. Returns the updated application."
     )]
-    async fn otter_list_apps(&self) -> Result<CallToolResult, McpError> {
-        info!("Listing all applications");
+    async fn otter_unarchive_app(
+        &self,
+        Parameters(input): Parameters<ArchiveAppInput>,
+    ) -> Result<CallToolResult, McpError> {
+        self.set_archived(input, false).await
+    }
+
+    /// List the most recently created applications
+    #[tool(
+        description = "List the most recently created applications in OtterShipper, newest first. Defaults to 10 applications (max 100)."
+    )]
+    async fn otter_recent_apps(
+        &self,
+        Parameters(input): Parameters<RecentAppsInput>,
+    ) -> Result<CallToolResult, McpError> {
+        let limit = input.limit.unwrap_or(DEFAULT_RECENT_LIMIT);
+        info!("Listing {} most recent applications", limit);
 
-        match self.service.list_apps().await {
+        match self.service.recent_apps(limit).await {
             Ok(apps) => {
                 let response = json!({
-                    "success": true,
-                    "applications": apps.iter().map(|app| {
-                        json!({
-                            "id": app.id,
-                            "name": app.name,
-                            "created_at": app.created_at
-                        })
-                    }).collect::<Vec<_>>(),
+                    "applications": apps.iter().map(serialize_application).collect::<Vec<_>>(),
                     "count": apps.len()
                 });
 
-                Ok(CallToolResult::success(vec![Content::text(
-                    serde_json::to_string_pretty(&response).unwrap(),
-                )]))
+                json_result(response)
             }
-            Err(e) => Err(McpError {
-                code: ErrorCode::INTERNAL_ERROR,
-                message: Cow::from(format!("Failed to list applications: {e}")),
-                data: None,
-            }),
+            Err(e) => Err(db_error(
+                &e,
+                format!("Failed to list recent applications: {e}"),
+            )),
         }
     }
-}
 
-#[tool_handler]
-impl ServerHandler for McpServer {
-    fn get_info(&self) -> InitializeResult {
-        InitializeResult {
-            protocol_version: ProtocolVersion::V_2024_11_05,
-            capabilities: ServerCapabilities::default(),
-            server_info: Implementation {
-                name: "ottershipper".to_string(),
-                version: "0.1.0".to_string(),
-            },
-            instructions: None,
+    /// Count applications by status
+    #[tool(
+        description = "Count applications by status in one call, e.g. {\"active\": 12, \"archived\": 3}. Every status is always present, with 0 when no application has it."
+    )]
+    async fn otter_status_summary(&self) -> Result<CallToolResult, McpError> {
+        info!("Summarizing application statuses");
+
+        match self.service.count_apps_by_status().await {
+            Ok(counts) => {
+                let response: serde_json::Map<_, _> = counts
+                    .into_iter()
+                    .map(|(status, count)| (status.as_str().to_string(), json!(count)))
+                    .collect();
+
+                json_result(serde_json::Value::Object(response))
+            }
+            Err(e) => Err(db_error(
+                &e,
+                format!("Failed to count applications by status: {e}"),
+            )),
+        }
+    }
+
+    /// Summarize applications created in the last day, week, and month
+    #[tool(
+        description = "Summarize recent activity: the applications created in the last 24 hours, 7 days, and 30 days, with a count for each window. Windows overlap, so an application created today appears in all three."
+    )]
+    async fn otter_recent_activity(&self) -> Result<CallToolResult, McpError> {
+        info!("Summarizing recent activity");
+
+        match self.service.recent_activity().await {
+            Ok(activity) => {
+                let window = |apps: &[Application]| {
+                    json!({
+                        "count": apps.len(),
+                        "apps": apps.iter().map(serialize_application).collect::<Vec<_>>()
+                    })
+                };
+                let response = json!({
+                    "last_24h": window(&activity.last_24h),
+                    "last_7d": window(&activity.last_7d),
+                    "last_30d": window(&activity.last_30d)
+                });
+
+                json_result(response)
+            }
+            Err(e) => Err(db_error(
+                &e,
+                format!("Failed to summarize recent activity: {e}"),
+            )),
         }
     }
+
+    /// List applications created within a time window
+    #[tool(
+        description = "List applications created within a time window (inclusive). Accepts ISO-8601 timestamps (e.g. \"2024-01-31T12:00:00Z\") or epoch milliseconds for start and end."
+    )]
+    async fn otter_apps_created_between(
+        &self,
+        Parameters(input): Parameters<CreatedBetweenInput>,
+    ) -> Result<CallToolResult, McpError> {
+        let parse = |ts: &TimestampInput| {
+            ts.to_epoch_millis()
+                .map_err(|e| tool_error(ErrorCode::INVALID_PARAMS, ErrorReason::InvalidParams, e))
+        };
+        let start_ms = parse(&input.start)?;
+        let end_ms = parse(&input.end)?;
+        info!(
+            "Listing applications created between {} and {}",
+            start_ms, end_ms
+        );
+
+        match self
+            .service
+            .list_apps_created_between(start_ms, end_ms)
+            .await
+        {
+            Ok(apps) => {
+                let response = json!({
+                    "applications": apps.iter().map(serialize_application).collect::<Vec<_>>(),
+                    "count": apps.len(),
+                    "start": start_ms,
+                    "end": end_ms
+                });
+
+                json_result(response)
+            }
+            Err(e @ DbError::InvalidArgument(_)) => Err(tool_error(
+                ErrorCode::INVALID_PARAMS,
+                ErrorReason::from(&e),
+                format!("Failed to list applications: {e}"),
+            )),
+            Err(e) => Err(db_error(&e, format!("Failed to list applications: {e}"))),
+        }
+    }
+
+    /// Find applications by name pattern
+    #[tool(
+        description = "Find applications whose name matches a glob pattern, e.g. \"app-prod-*\". * matches any characters and ? exactly one. Results are ordered by name."
+    )]
+    async fn otter_find_apps(
+        &self,
+        Parameters(input): Parameters<FindAppsInput>,
+    ) -> Result<CallToolResult, McpError> {
+        info!("Finding applications matching: {}", input.pattern);
+
+        match self.service.find_apps(&input.pattern).await {
+            Ok(apps) => {
+                let response = json!({
+                    "applications": apps.iter().map(serialize_application).collect::<Vec<_>>(),
+                    "count": apps.len(),
+                    "pattern": input.pattern
+                });
+
+                json_result(response)
+            }
+            Err(e) => Err(db_error(&e, format!("Failed to find applications: {e}"))),
+        }
+    }
+
+    /// Find applications by name length and pattern
+    #[tool(
+        description = "Find applications by name length and/or glob pattern, e.g. names shorter than 3 characters with max_length=2. All given criteria must match; results are ordered by name."
+    )]
+    async fn otter_filter_apps(
+        &self,
+        Parameters(input): Parameters<FilterAppsInput>,
+    ) -> Result<CallToolResult, McpError> {
+        info!("Filtering applications: {:?}", input);
+
+        let filter = NameFilter {
+            min_len: input.min_length,
+            max_len: input.max_length,
+            glob: input.pattern.clone(),
+            regex: None,
+        };
+        match self.service.filter_apps(&filter).await {
+            Ok(apps) => {
+                let response = json!({
+                    "applications": apps.iter().map(serialize_application).collect::<Vec<_>>(),
+                    "count": apps.len(),
+                    "min_length": input.min_length,
+                    "max_length": input.max_length,
+                    "pattern": input.pattern
+                });
+
+                json_result(response)
+            }
+            Err(e @ DbError::InvalidArgument(_)) => Err(tool_error(
+                ErrorCode::INVALID_PARAMS,
+                ErrorReason::InvalidParams,
+                e.to_string(),
+            )),
+            Err(e) => Err(db_error(&e, format!("Failed to filter applications: {e}"))),
+        }
+    }
+
+    /// Filter, sort, and paginate applications in one call
+    #[tool(
+        description = "Query applications in one call: optionally filter by name_contains (case-sensitive substring) and status, sort by name, created_at, or updated_at in either direction, and page with limit and offset. Returns items plus total (matches across all pages), limit, and offset as actually applied: limit defaults to 50 (or the server's default page size) and is clamped to 1-100 and to the server's maximum page size, with limit_clamped set when that changed it; a negative offset counts as 0. Archived applications are included unless status is set."
+    )]
+    async fn otter_query_apps(
+        &self,
+        Parameters(input): Parameters<QueryAppsInput>,
+    ) -> Result<CallToolResult, McpError> {
+        info!("Querying applications: {:?}", input);
+
+        let (limit, limit_clamped) = self.page_limit(input.limit, Some(DEFAULT_QUERY_LIMIT));
+        let query = AppQuery {
+            name_contains: input.name_contains,
+            status: input.status.map(Into::into),
+            sort_by: input.sort_by.map(Into::into).unwrap_or_default(),
+            direction: input.direction.map(Into::into).unwrap_or_default(),
+            limit: limit.unwrap_or(DEFAULT_QUERY_LIMIT),
+            offset: input.offset.unwrap_or(0),
+        };
+        let requested = query.limit;
+        match self.service.query_apps(query).await {
+            Ok(page) => {
+                let response = json!({
+                    "items": page.applications.iter().map(serialize_application).collect::<Vec<_>>(),
+                    "total": page.total,
+                    "limit": page.limit,
+                    "limit_clamped": limit_clamped || page.limit != Some(requested),
+                    "offset": page.offset
+                });
+
+                json_result(response)
+            }
+            Err(e @ DbError::InvalidArgument(_)) => Err(tool_error(
+                ErrorCode::INVALID_PARAMS,
+                ErrorReason::InvalidParams,
+                e.to_string(),
+            )),
+            Err(e) => Err(db_error(&e, format!("Failed to query applications: {e}"))),
+        }
+    }
+
+    /// Count applications matching the `otter_query_apps` filters
+    #[tool(
+        description = "Count applications matching the same filters as otter_query_apps: name_contains (case-sensitive substring) and status. Returns only the count, without listing any applications. Archived applications are included unless status is set."
+    )]
+    async fn otter_count_matching(
+        &self,
+        Parameters(input): Parameters<CountMatchingInput>,
+    ) -> Result<CallToolResult, McpError> {
+        info!("Counting applications: {:?}", input);
+
+        let query = AppQuery {
+            name_contains: input.name_contains,
+            status: input.status.map(Into::into),
+            ..AppQuery::default()
+        };
+        match self.service.count_apps_matching(&query).await {
+            Ok(count) => json_result(json!({ "count": count })),
+            Err(e @ DbError::InvalidArgument(_)) => Err(tool_error(
+                ErrorCode::INVALID_PARAMS,
+                ErrorReason::InvalidParams,
+                e.to_string(),
+            )),
+            Err(e) => Err(db_error(&e, format!("Failed to count applications: {e}"))),
+        }
+    }
+
+    /// Search names and descriptions, using the full-text index when enabled
+    #[tool(
+        description = "Find applications whose name and description together contain every word of the query, best match first (name matches rank above description matches). A word ending in * matches by prefix. Uses the server's full-text index when enabled; otherwise words match as case-insensitive substrings, ordered by name."
+    )]
+    async fn otter_search(
+        &self,
+        Parameters(input): Parameters<SearchInput>,
+    ) -> Result<CallToolResult, McpError> {
+        info!("Searching application text for {:?}", input.query);
+
+        match self.service.search_text(&input.query).await {
+            Ok(apps) => {
+                let response = json!({
+                    "applications": apps.iter().map(serialize_application).collect::<Vec<_>>(),
+                    "count": apps.len(),
+                    "query": input.query
+                });
+
+                json_result(response)
+            }
+            Err(e @ DbError::InvalidArgument(_)) => Err(tool_error(
+                ErrorCode::INVALID_PARAMS,
+                ErrorReason::InvalidParams,
+                e.to_string(),
+            )),
+            Err(e) => Err(db_error(&e, format!("Failed to search applications: {e}"))),
+        }
+    }
+
+    /// Search every text field of every application
+    #[tool(
+        description = "Search application names, descriptions, and metadata values for the given text (case-insensitive substring; % and _ are not wildcards). Each application is returned once: name matches first, then description matches, then metadata matches, each ordered by name."
+    )]
+    async fn otter_search_all(
+        &self,
+        Parameters(input): Parameters<SearchAllInput>,
+    ) -> Result<CallToolResult, McpError> {
+        info!("Searching applications for {:?}", input.query);
+
+        match self.service.search_all(&input.query).await {
+            Ok(apps) => {
+                let response = json!({
+                    "applications": apps.iter().map(serialize_application).collect::<Vec<_>>(),
+                    "count": apps.len(),
+                    "query": input.query
+                });
+
+                json_result(response)
+            }
+            Err(e @ DbError::InvalidArgument(_)) => Err(tool_error(
+                ErrorCode::INVALID_PARAMS,
+                ErrorReason::InvalidParams,
+                e.to_string(),
+            )),
+            Err(e) => Err(db_error(&e, format!("Failed to search applications: {e}"))),
+        }
+    }
+
+    /// Record that an application is still in use
+    #[tool(
+        description = "Mark an application as active now, by ID or by name (provide exactly one). Only its updated_at changes, and nothing is written to the audit log, so it is cheap enough for heartbeats. Returns the updated application."
+    )]
+    async fn otter_touch_app(
+        &self,
+        Parameters(input): Parameters<TouchAppInput>,
+    ) -> Result<CallToolResult, McpError> {
+        let service = self.service_for(input.owner);
+        let id = match (input.id, input.name) {
+            (Some(id), None) => Ok(id),
+            (None, Some(name)) => service
+                .get_app_by_name_or_err(&name)
+                .await
+                .map(|app| app.id),
+            _ => {
+                return Err(tool_error(
+                    ErrorCode::INVALID_PARAMS,
+                    ErrorReason::InvalidParams,
+                    "Provide exactly one of 'id' or 'name'",
+                ))
+            }
+        };
+        info!("Touching application {:?}", id);
+
+        let result = match id {
+            Ok(id) => match service.touch_app(&id).await {
+                Ok(true) => service.get_app_or_err(&id).await,
+                Ok(false) => Err(DbError::NotFound(id)),
+                Err(e) => Err(e),
+            },
+            Err(e) => Err(e),
+        };
+        match result {
+            Ok(app) => {
+                let response = json!({
+                    "application": serialize_application(&app)
+                });
+
+                json_result(response)
+            }
+            Err(DbError::NotFound(target)) => Err(tool_error(
+                ErrorCode::RESOURCE_NOT_FOUND,
+                ErrorReason::NotFound,
+                format!("No application found matching '{target}'"),
+            )),
+            Err(e) => Err(db_error(&e, format!("Failed to touch application: {e}"))),
+        }
+    }
+
+    /// Rename one application, found by ID or current name
+    #[tool(
+        description = "Rename an application. Identify it by id or by current_name. Fails if the new name is invalid or already taken."
+    )]
+    async fn otter_rename_app(
+        &self,
+        Parameters(input): Parameters<RenameAppInput>,
+    ) -> Result<CallToolResult, McpError> {
+        let service = self.service_for(input.owner);
+        let id = match (input.id, input.current_name) {
+            (Some(id), None) => Ok(id),
+            (None, Some(name)) => service
+                .get_app_by_name_or_err(&name)
+                .await
+                .map(|app| app.id),
+            _ => {
+                return Err(tool_error(
+                    ErrorCode::INVALID_PARAMS,
+                    ErrorReason::InvalidParams,
+                    "Provide exactly one of 'id' or 'current_name'",
+                ))
+            }
+        };
+        info!("Renaming application {:?} to {:?}", id, input.new_name);
+
+        let result = match id {
+            Ok(id) => service.rename_app(&id, &input.new_name).await,
+            Err(e) => Err(e),
+        };
+        match result {
+            Ok(app) => {
+                let response = json!({
+                    "application": serialize_application(&app),
+                    "message": format!("Successfully renamed application to '{}'", app.name)
+                });
+
+                json_result(response)
+            }
+            Err(DbError::NotFound(target)) => Err(tool_error(
+                ErrorCode::RESOURCE_NOT_FOUND,
+                ErrorReason::NotFound,
+                format!("No application found matching '{target}'"),
+            )),
+            Err(e @ (DbError::InvalidName(_) | DbError::DuplicateName(_))) => Err(tool_error(
+                ErrorCode::INVALID_PARAMS,
+                ErrorReason::from(&e),
+                format!("Failed to rename application: {e}"),
+            )),
+            Err(e) => Err(db_error(&e, format!("Failed to rename application: {e}"))),
+        }
+    }
+
+    /// Rename every application with a given name prefix
+    #[tool(
+        description = "Bulk rename: replace old_prefix with new_prefix in the name of every application starting with old_prefix (e.g. svc-api to team-a-api). All or nothing: if any new name is invalid or already taken, no application is renamed. Returns the old and new names."
+    )]
+    async fn otter_rename_prefix(
+        &self,
+        Parameters(input): Parameters<RenamePrefixInput>,
+    ) -> Result<CallToolResult, McpError> {
+        info!(
+            "Renaming applications with prefix {:?} to prefix {:?}",
+            input.old_prefix, input.new_prefix
+        );
+
+        match self
+            .service_for(input.owner)
+            .rename_prefix(&input.old_prefix, &input.new_prefix)
+            .await
+        {
+            Ok(renamed) => {
+                let response = json!({
+                    "renamed": renamed
+                        .iter()
+                        .map(|(old, new)| json!({ "old": old, "new": new }))
+                        .collect::<Vec<_>>(),
+                    "count": renamed.len()
+                });
+
+                json_result(response)
+            }
+            Err(
+                e @ (DbError::InvalidName(_)
+                | DbError::DuplicateName(_)
+                | DbError::InvalidArgument(_)),
+            ) => Err(tool_error(
+                ErrorCode::INVALID_PARAMS,
+                ErrorReason::from(&e),
+                format!("Failed to rename applications, nothing was renamed: {e}"),
+            )),
+            Err(e) => Err(db_error(&e, format!("Failed to rename applications: {e}"))),
+        }
+    }
+
+    /// Set a metadata key on an application
+    #[tool(
+        description = "Attach a key-value pair of metadata (owner, repository URL, cost center, ...) to an application. Overwrites the key if it is already set."
+    )]
+    async fn otter_set_metadata(
+        &self,
+        Parameters(input): Parameters<SetMetadataInput>,
+    ) -> Result<CallToolResult, McpError> {
+        info!("Setting metadata {} on application {}", input.key, input.id);
+
+        match self
+            .service
+            .set_app_meta(&input.id, &input.key, &input.value)
+            .await
+        {
+            Ok(()) => {
+                let response = json!({
+                    "id": input.id,
+                    "key": input.key,
+                    "value": input.value
+                });
+
+                json_result(response)
+            }
+            Err(DbError::NotFound(target)) => Err(tool_error(
+                ErrorCode::RESOURCE_NOT_FOUND,
+                ErrorReason::NotFound,
+                format!("No application found matching '{target}'"),
+            )),
+            Err(e @ DbError::InvalidArgument(_)) => Err(tool_error(
+                ErrorCode::INVALID_PARAMS,
+                ErrorReason::from(&e),
+                format!("Failed to set metadata: {e}"),
+            )),
+            Err(e) => Err(db_error(&e, format!("Failed to set metadata: {e}"))),
+        }
+    }
+
+    /// Read one or all metadata keys of an application
+    #[tool(
+        description = "Read an application's metadata: the value of one key, or all key-value pairs (ordered by key) when no key is given."
+    )]
+    async fn otter_get_metadata(
+        &self,
+        Parameters(input): Parameters<GetMetadataInput>,
+    ) -> Result<CallToolResult, McpError> {
+        let result = if let Some(key) = &input.key {
+            info!("Getting metadata {} of application {}", key, input.id);
+            self.service
+                .get_app_meta(&input.id, key)
+                .await
+                .map(|value| json!({ "id": input.id, "key": key, "value": value }))
+        } else {
+            info!("Listing metadata of application {}", input.id);
+            self.service.list_app_meta(&input.id).await.map(|entries| {
+                let metadata: serde_json::Map<_, _> = entries
+                    .into_iter()
+                    .map(|entry| (entry.key, entry.value.into()))
+                    .collect();
+                json!({ "id": input.id, "metadata": metadata })
+            })
+        };
+
+        match result {
+            Ok(response) => json_result(response),
+            Err(DbError::NotFound(target)) => Err(tool_error(
+                ErrorCode::RESOURCE_NOT_FOUND,
+                ErrorReason::NotFound,
+                format!("No application found matching '{target}'"),
+            )),
+            Err(e) => Err(db_error(&e, format!("Failed to get metadata: {e}"))),
+        }
+    }
+
+    /// Find applications by a metadata value
+    #[tool(
+        description = "Find the applications whose metadata key has exactly the given value, e.g. key \"repo\" and a repository URL. Returns every match, ordered by name."
+    )]
+    async fn otter_find_by_meta(
+        &self,
+        Parameters(input): Parameters<FindByMetaInput>,
+    ) -> Result<CallToolResult, McpError> {
+        info!(
+            "Finding applications with metadata {} = {}",
+            input.key, input.value
+        );
+
+        match self
+            .service
+            .find_apps_by_meta(&input.key, &input.value)
+            .await
+        {
+            Ok(apps) => {
+                let response = json!({
+                    "applications": apps.iter().map(serialize_application).collect::<Vec<_>>(),
+                    "count": apps.len(),
+                    "key": input.key,
+                    "value": input.value
+                });
+
+                json_result(response)
+            }
+            Err(e) => Err(db_error(&e, format!("Failed to find applications: {e}"))),
+        }
+    }
+
+    /// Remove a metadata key from an application
+    #[tool(
+        description = "Remove a metadata key from an application. Returns whether the key was set."
+    )]
+    async fn otter_delete_metadata(
+        &self,
+        Parameters(input): Parameters<DeleteMetadataInput>,
+    ) -> Result<CallToolResult, McpError> {
+        info!(
+            "Deleting metadata {} from application {}",
+            input.key, input.id
+        );
+
+        match self.service.delete_app_meta(&input.id, &input.key).await {
+            Ok(deleted) => {
+                let response = json!({
+                    "deleted": deleted,
+                    "id": input.id,
+                    "key": input.key
+                });
+
+                json_result(response)
+            }
+            Err(e) => Err(db_error(&e, format!("Failed to delete metadata: {e}"))),
+        }
+    }
+
+    /// Delete a single application
+    #[tool(
+        description = "Delete an application by ID or by name (provide exactly one). If the server requires confirmation, provide both the ID and the application's name; a mismatched name is refused. Returns whether an application was deleted."
+    )]
+    async fn otter_delete_app(
+        &self,
+        Parameters(input): Parameters<DeleteAppInput>,
+    ) -> Result<CallToolResult, McpError> {
+        let service = self.service_for(input.owner);
+        let (result, target) = match (input.id, input.name) {
+            (Some(id), Some(name)) if self.require_confirmation => {
+                info!("Deleting application {} confirmed as {}", id, name);
+                (service.delete_app_confirmed(&id, &name).await, id)
+            }
+            _ if self.require_confirmation => {
+                return Err(tool_error(
+                    ErrorCode::INVALID_PARAMS,
+                    ErrorReason::InvalidParams,
+                    "This server requires both 'id' and the application's 'name' to delete it",
+                ))
+            }
+            (Some(id), None) => {
+                info!("Deleting application by ID: {}", id);
+                (service.delete_app(&id).await, id)
+            }
+            (None, Some(name)) => {
+                info!("Deleting application by name: {}", name);
+                (service.delete_app_by_name(&name).await, name)
+            }
+            _ => {
+                return Err(tool_error(
+                    ErrorCode::INVALID_PARAMS,
+                    ErrorReason::InvalidParams,
+                    "Provide exactly one of 'id' or 'name'",
+                ))
+            }
+        };
+
+        match result {
+            Ok(deleted) => {
+                let message = if deleted {
+                    format!("Successfully deleted application '{target}'")
+                } else {
+                    format!("No application found matching '{target}'")
+                };
+                let response = json!({
+                    "deleted": deleted,
+                    "message": message
+                });
+
+                json_result(response)
+            }
+            Err(e @ DbError::InvalidArgument(_)) => Err(tool_error(
+                ErrorCode::INVALID_PARAMS,
+                ErrorReason::InvalidParams,
+                e.to_string(),
+            )),
+            Err(e) => Err(db_error(&e, format!("Failed to delete application: {e}"))),
+        }
+    }
+
+    /// Delete several applications at once
+    #[tool(
+        description = "Delete multiple applications by ID in one call. Returns the number of applications actually deleted; unknown IDs are ignored. If the server requires confirmation, also provide names, the application names in the same order as ids; if any name doesn't match, nothing is deleted."
+    )]
+    async fn otter_delete_apps(
+        &self,
+        Parameters(input): Parameters<DeleteAppsInput>,
+    ) -> Result<CallToolResult, McpError> {
+        info!("Deleting {} applications", input.ids.len());
+
+        let result = match input.names {
+            Some(names) if names.len() == input.ids.len() => {
+                let targets: Vec<(String, String)> = input.ids.iter().cloned().zip(names).collect();
+                self.service.delete_apps_confirmed(&targets).await
+            }
+            Some(_) => {
+                return Err(tool_error(
+                    ErrorCode::INVALID_PARAMS,
+                    ErrorReason::InvalidParams,
+                    "'names' must list one name per ID in 'ids'",
+                ))
+            }
+            None if self.require_confirmation => {
+                return Err(tool_error(
+                    ErrorCode::INVALID_PARAMS,
+                    ErrorReason::InvalidParams,
+                    "This server requires the applications' 'names' alongside 'ids' to delete them",
+                ))
+            }
+            None => self.service.delete_apps(&input.ids).await,
+        };
+
+        match result {
+            Ok(deleted) => {
+                let response = json!({
+                    "requested": input.ids.len(),
+                    "deleted": deleted,
+                    "message": format!("Deleted {deleted} of {} requested applications", input.ids.len())
+                });
+
+                json_result(response)
+            }
+            Err(e @ DbError::InvalidArgument(_)) => Err(tool_error(
+                ErrorCode::INVALID_PARAMS,
+                ErrorReason::InvalidParams,
+                e.to_string(),
+            )),
+            Err(e) => Err(db_error(&e, format!("Failed to delete applications: {e}"))),
+        }
+    }
+
+    /// Delete every application, guarded by a confirmation string
+    #[tool(
+        description = "Admin: permanently delete every application and its metadata in one transaction, e.g. to reset a development instance. Only listed when the server sets [tools] allow_reset. Requires confirm to be exactly \"DELETE ALL\"; anything else is refused and deletes nothing. Returns the number of applications deleted. The audit log is kept and records each deletion."
+    )]
+    async fn otter_reset(
+        &self,
+        Parameters(input): Parameters<ResetInput>,
+    ) -> Result<CallToolResult, McpError> {
+        if input.confirm != RESET_CONFIRMATION {
+            return Err(tool_error(
+                ErrorCode::INVALID_PARAMS,
+                ErrorReason::InvalidParams,
+                format!("Refusing to reset: confirm must be exactly \"{RESET_CONFIRMATION}\""),
+            ));
+        }
+        warn!("Resetting: deleting every application");
+
+        match self.service.reset().await {
+            Ok(deleted) => {
+                let response = json!({
+                    "deleted": deleted,
+                    "message": format!("Deleted all {deleted} applications")
+                });
+
+                json_result(response)
+            }
+            Err(e) => Err(db_error(&e, format!("Failed to reset: {e}"))),
+        }
+    }
+
+    /// Show the audit log of application changes
+    #[tool(
+        description = "Show the audit log of application creates, updates, and deletes. Pass app_id for one application's full trail, or omit it for the most recent entries across all applications. Each entry has the action, application ID and name, actor (if known), and timestamp."
+    )]
+    async fn otter_audit_log(
+        &self,
+        Parameters(input): Parameters<AuditLogInput>,
+    ) -> Result<CallToolResult, McpError> {
+        let result = if let Some(app_id) = &input.app_id {
+            info!("Showing audit trail for application {}", app_id);
+            self.service.audit_trail(app_id).await
+        } else {
+            let limit = input.limit.unwrap_or(DEFAULT_AUDIT_LIMIT);
+            info!("Showing {} most recent audit entries", limit);
+            self.service.recent_audit(limit).await
+        };
+
+        match result {
+            Ok(entries) => {
+                let response = json!({
+                    "entries": entries,
+                    "count": entries.len()
+                });
+
+                json_result(response)
+            }
+            Err(e) => Err(db_error(&e, format!("Failed to read audit log: {e}"))),
+        }
+    }
+
+    /// Cheapest possible round-trip
+    #[tool(
+        description = "Check that the OtterShipper MCP server is responsive. Does not touch the database, so it succeeds even when the database is unavailable. Returns pong and the server's current time in epoch milliseconds."
+    )]
+    async fn otter_ping(&self) -> Result<CallToolResult, McpError> {
+        let response = json!({
+            "pong": true,
+            "server_time": chrono::Utc::now().timestamp_millis()
+        });
+
+        json_result(response)
+    }
+
+    /// Report the running server's version and state
+    #[tool(
+        description = "Show information about the running OtterShipper server: name, version, MCP protocol version, configured transport, exposed tools, number of applied database migrations, schema version (latest applied migration, and the latest this build knows), and number of applications."
+    )]
+    async fn otter_server_info(&self) -> Result<CallToolResult, McpError> {
+        info!("Reporting server info");
+
+        let internal = |what: &str, e: DbError| db_error(&e, format!("Failed to {what}: {e}"));
+        let migration_count = self
+            .service
+            .database()
+            .migration_count()
+            .await
+            .map_err(|e| internal("count migrations", e))?;
+        let schema_version = self
+            .service
+            .database()
+            .schema_version()
+            .await
+            .map_err(|e| internal("read schema version", e))?;
+        let app_count = self
+            .service
+            .count_apps()
+            .await
+            .map_err(|e| internal("count applications", e))?;
+
+        let info = self.get_info();
+        let mut tools: Vec<_> = self
+            .tool_router
+            .list_all()
+            .into_iter()
+            .map(|tool| tool.name)
+            .collect();
+        tools.sort();
+
+        let response = json!({
+            "name": info.server_info.name,
+            "version": info.server_info.version,
+            "protocol_version": info.protocol_version,
+            "transport": self.transport,
+            "tools": tools,
+            "read_only": self.service.database().is_read_only(),
+            "migration_count": migration_count,
+            "schema_version": schema_version,
+            "latest_schema_version": ottershipper_db::Database::latest_schema_version(),
+            "app_count": app_count
+        });
+
+        json_result(response)
+    }
+
+    /// Report database health details
+    #[tool(
+        description = "Admin: show database diagnostics: on-disk size of the database file and its WAL/SHM files, connection pool usage including how long callers waited for a connection, and which schema migrations have been applied."
+    )]
+    async fn otter_diagnostics(&self) -> Result<CallToolResult, McpError> {
+        info!("Reporting database diagnostics");
+
+        let db = self.service.database();
+        let internal = |what: &str, e: DbError| db_error(&e, format!("Failed to {what}: {e}"));
+        let disk_usage = db
+            .disk_usage()
+            .await
+            .map_err(|e| internal("read disk usage", e))?;
+        let migrations = db
+            .migration_status()
+            .await
+            .map_err(|e| internal("read migration status", e))?;
+
+        let response = json!({
+            "disk_usage": {
+                "path": disk_usage.path,
+                "main_bytes": disk_usage.main_bytes,
+                "wal_bytes": disk_usage.wal_bytes,
+                "shm_bytes": disk_usage.shm_bytes,
+                "total_bytes": disk_usage.total_bytes()
+            },
+            "pool": db.pool_stats(),
+            "read_only": db.is_read_only(),
+            "migrations": migrations,
+            "pending_migrations": migrations.iter().filter(|m| m.applied_at.is_none()).count()
+        });
+
+        json_result(response)
+    }
+
+    /// Checkpoint the database write-ahead log
+    #[tool(
+        description = "Admin: checkpoint the database write-ahead log (WAL) into the main database file. Returns the busy flag and WAL/checkpointed frame counts reported by SQLite. Does nothing when WAL is disabled."
+    )]
+    async fn otter_checkpoint(
+        &self,
+        Parameters(input): Parameters<CheckpointInput>,
+    ) -> Result<CallToolResult, McpError> {
+        let mode = input.mode.map_or(CheckpointMode::default(), Into::into);
+        info!("Running WAL checkpoint ({:?})", mode);
+
+        match self.service.database().checkpoint(mode).await {
+            Ok(Some(result)) => {
+                let response = json!({
+                    "wal_enabled": true,
+                    "mode": mode,
+                    "busy": result.busy,
+                    "log_frames": result.log_frames,
+                    "checkpointed_frames": result.checkpointed_frames
+                });
+
+                json_result(response)
+            }
+            Ok(None) => {
+                let response = json!({
+                    "wal_enabled": false,
+                    "mode": mode,
+                    "message": "WAL is not enabled; nothing to checkpoint"
+                });
+
+                json_result(response)
+            }
+            Err(e) => Err(db_error(&e, format!("Failed to checkpoint database: {e}"))),
+        }
+    }
+}
+
+impl ServerHandler for McpServer {
+    async fn call_tool(
+        &self,
+        request: CallToolRequestParam,
+        context: RequestContext<RoleServer>,
+    ) -> Result<CallToolResult, McpError> {
+        self.check_rate_limit(&request.name)?;
+        self.tool_router
+            .call(ToolCallContext::new(self, request, context))
+            .await
+    }
+
+    async fn list_tools(
+        &self,
+        _request: Option<PaginatedRequestParam>,
+        _context: RequestContext<RoleServer>,
+    ) -> Result<ListToolsResult, McpError> {
+        Ok(ListToolsResult::with_all_items(self.tool_router.list_all()))
+    }
+
+    async fn list_resources(
+        &self,
+        _request: Option<PaginatedRequestParam>,
+        _context: RequestContext<RoleServer>,
+    ) -> Result<ListResourcesResult, McpError> {
+        let help = RawResource {
+            description: Some(
+                "How to use OtterShipper: tools, name rules, and examples".to_string(),
+            ),
+            mime_type: Some("text/markdown".to_string()),
+            ..RawResource::new(HELP_URI, "help")
+        };
+        Ok(ListResourcesResult::with_all_items(vec![
+            help.no_annotation()
+        ]))
+    }
+
+    async fn read_resource(
+        &self,
+        request: ReadResourceRequestParam,
+        _context: RequestContext<RoleServer>,
+    ) -> Result<ReadResourceResult, McpError> {
+        if request.uri != HELP_URI {
+            return Err(McpError::resource_not_found(
+                format!("Unknown resource: {}", request.uri),
+                Some(json!({ "uri": request.uri })),
+            ));
+        }
+
+        let text = self.help.clone().unwrap_or_else(|| self.default_help());
+        Ok(ReadResourceResult {
+            contents: vec![ResourceContents::TextResourceContents {
+                uri: HELP_URI.to_string(),
+                mime_type: Some("text/markdown".to_string()),
+                text,
+            }],
+        })
+    }
+
+    fn get_info(&self) -> InitializeResult {
+        InitializeResult {
+            protocol_version: ProtocolVersion::V_2024_11_05,
+            capabilities: ServerCapabilities::builder().enable_resources().build(),
+            server_info: Implementation {
+                name: "ottershipper".to_string(),
+                version: env!("CARGO_PKG_VERSION").to_string(),
+            },
+            instructions: Some(
+                self.instructions
+                    .clone()
+                    .unwrap_or_else(|| self.default_instructions()),
+            ),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_json_result_success() {
+        let result = json_result(json!({ "count": 2 })).expect("serialization should succeed");
+
+        assert_eq!(result.is_error, Some(false));
+        let text = &result.content[0].as_text().unwrap().text;
+        let parsed: serde_json::Value = serde_json::from_str(text).unwrap();
+        assert_eq!(parsed, json!({ "success": true, "count": 2 }));
+    }
+
+    #[test]
+    fn test_tool_error_envelope() {
+        let err = tool_error(
+            ErrorCode::INVALID_PARAMS,
+            ErrorReason::from(&DbError::DuplicateName("taken".to_string())),
+            "Failed to create application: Name 'taken' already exists",
+        );
+
+        assert_eq!(err.code, ErrorCode::INVALID_PARAMS);
+        let envelope: ErrorEnvelope = serde_json::from_value(err.data.unwrap()).unwrap();
+        assert!(!envelope.success);
+        assert_eq!(envelope.reason, ErrorReason::DuplicateName);
+        assert_eq!(envelope.message, err.message);
+    }
 }
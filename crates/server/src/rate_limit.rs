@@ -0,0 +1,99 @@
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+/// Token bucket limiting how often tools may be called
+///
+/// Holds up to `per_minute` tokens, refilled continuously at `per_minute` per
+/// minute; each call takes one. Shared by every connection to the server.
+#[derive(Debug)]
+pub struct RateLimiter {
+    per_minute: u32,
+    bucket: Mutex<Bucket>,
+}
+
+#[derive(Debug)]
+struct Bucket {
+    tokens: f64,
+    refilled_at: Instant,
+}
+
+impl RateLimiter {
+    /// Allow `per_minute` calls per minute, starting with a full bucket
+    pub fn new(per_minute: u32) -> Self {
+        Self {
+            per_minute,
+            bucket: Mutex::new(Bucket {
+                tokens: f64::from(per_minute),
+                refilled_at: Instant::now(),
+            }),
+        }
+    }
+
+    /// Calls allowed per minute
+    pub fn per_minute(&self) -> u32 {
+        self.per_minute
+    }
+
+    /// Take a token for one call, or return how long until one is available
+    pub fn try_acquire(&self) -> Result<(), Duration> {
+        self.try_acquire_at(Instant::now())
+    }
+
+    fn try_acquire_at(&self, now: Instant) -> Result<(), Duration> {
+        let capacity = f64::from(self.per_minute);
+        let per_second = capacity / 60.0;
+
+        // A poisoned lock only means another caller panicked mid-update; the
+        // bucket is still usable
+        let mut bucket = self
+            .bucket
+            .lock()
+            .unwrap_or_else(std::sync::PoisonError::into_inner);
+        let elapsed = now.saturating_duration_since(bucket.refilled_at);
+        bucket.tokens = (bucket.tokens + elapsed.as_secs_f64() * per_second).min(capacity);
+        bucket.refilled_at = now;
+
+        if bucket.tokens >= 1.0 {
+            bucket.tokens -= 1.0;
+            Ok(())
+        } else {
+            Err(Duration::from_secs_f64((1.0 - bucket.tokens) / per_second))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_allows_up_to_cap_then_rejects() {
+        let limiter = RateLimiter::new(3);
+        let start = Instant::now();
+
+        for _ in 0..3 {
+            assert!(limiter.try_acquire_at(start).is_ok());
+        }
+        let retry_after = limiter.try_acquire_at(start).unwrap_err();
+        assert!(
+            (retry_after.as_secs_f64() - 20.0).abs() < 1e-6,
+            "{retry_after:?}"
+        );
+
+        // One token comes back every 20 seconds
+        let later = start + Duration::from_secs(20);
+        assert!(limiter.try_acquire_at(later).is_ok());
+        assert!(limiter.try_acquire_at(later).is_err());
+    }
+
+    #[test]
+    fn test_refill_is_capped() {
+        let limiter = RateLimiter::new(2);
+        let later = Instant::now() + Duration::from_hours(1);
+
+        // An idle hour still only allows a burst of the configured size
+        assert!(limiter.try_acquire_at(later).is_ok());
+        assert!(limiter.try_acquire_at(later).is_ok());
+        assert!(limiter.try_acquire_at(later).is_err());
+    }
+}
@@ -3,8 +3,564 @@ use serde::{Deserialize, Serialize};
 /// Input schema for `otter_create_app` tool
 #[derive(Debug, Deserialize, Serialize, schemars::JsonSchema)]
 pub struct CreateAppInput {
+    // The constraints document the default name policy so clients can check
+    // names locally; the server's configured policy still decides. Surrounding
+    // whitespace is allowed because names are trimmed before validation.
     #[schemars(
-        description = "Application name (alphanumeric, hyphens, underscores, max 255 chars). Must start with alphanumeric character."
+        description = "Application name (alphanumeric, hyphens, underscores, max 255 chars). Must start with alphanumeric character. Omit to generate a random name such as \"plucky-otter-4821\". The pattern uses Unicode classes (\\p{L}, \\p{N}), so check it with a Unicode-aware validator, e.g. a JavaScript RegExp with the u flag.",
+        length(min = 1, max = 255),
+        regex(pattern = r"^\s*[\p{L}\p{N}][\p{L}\p{N}_-]*\s*$")
+    )]
+    pub name: Option<String>,
+
+    #[schemars(
+        description = "Optional client-chosen key that makes this call safe to retry. Repeating a create with the same key returns the originally created application instead of failing."
+    )]
+    pub idempotency_key: Option<String>,
+
+    #[schemars(
+        description = "When true, only check whether the name is valid and available; nothing is created. Requires name."
+    )]
+    pub dry_run: Option<bool>,
+
+    #[schemars(
+        description = "Owner of the new application. When the server scopes names by owner, the name only needs to be unique among this owner's applications. Omit for an application nobody owns."
+    )]
+    pub owner: Option<String>,
+}
+
+/// Input schema for `otter_list_apps` tool
+#[derive(Debug, Default, Deserialize, Serialize, schemars::JsonSchema)]
+pub struct ListAppsInput {
+    #[schemars(description = "Maximum number of applications to return. Omit to return all.")]
+    pub limit: Option<i64>,
+
+    #[schemars(description = "Number of applications to skip (default 0)")]
+    pub offset: Option<i64>,
+
+    #[schemars(
+        description = "Keyset pagination cursor: pass \"\" for the first page, then the next_cursor from the previous response. Pages stay consistent while applications are created or deleted. Cannot be combined with offset; limit defaults to 100."
+    )]
+    pub cursor: Option<String>,
+
+    #[schemars(description = "Also list archived applications (default false)")]
+    pub include_archived: Option<bool>,
+}
+
+/// Input schema for `otter_recent_apps` tool
+#[derive(Debug, Deserialize, Serialize, schemars::JsonSchema)]
+pub struct RecentAppsInput {
+    #[schemars(
+        description = "Number of applications to return (default 10, max 100). Newest applications come first."
+    )]
+    pub limit: Option<i64>,
+}
+
+/// Input schema for `otter_find_apps` tool
+#[derive(Debug, Deserialize, Serialize, schemars::JsonSchema)]
+pub struct FindAppsInput {
+    #[schemars(
+        description = "Name pattern: * matches any characters, ? matches exactly one (e.g. \"app-prod-*\"). Case-sensitive; other characters match literally."
+    )]
+    pub pattern: String,
+}
+
+/// Input schema for `otter_filter_apps` tool
+#[derive(Debug, Deserialize, Serialize, schemars::JsonSchema)]
+pub struct FilterAppsInput {
+    #[schemars(description = "Only names at least this many characters long")]
+    pub min_length: Option<usize>,
+    #[schemars(description = "Only names at most this many characters long")]
+    pub max_length: Option<usize>,
+    #[schemars(
+        description = "Name pattern as for otter_find_apps: * matches any characters, ? exactly one"
+    )]
+    pub pattern: Option<String>,
+}
+
+/// Input schema for `otter_query_apps` tool
+#[derive(Debug, Default, Deserialize, Serialize, schemars::JsonSchema)]
+pub struct QueryAppsInput {
+    #[schemars(
+        description = "Only names containing this text (case-sensitive, no wildcards, at most 256 characters)"
+    )]
+    pub name_contains: Option<String>,
+
+    #[schemars(description = "Only applications with this status. Omit for every status.")]
+    pub status: Option<AppStatusInput>,
+
+    #[schemars(description = "Sort by \"name\", \"created_at\" (default), or \"updated_at\"")]
+    pub sort_by: Option<SortFieldInput>,
+
+    #[schemars(description = "Sort direction: \"asc\" or \"desc\" (default)")]
+    pub direction: Option<SortDirectionInput>,
+
+    #[schemars(description = "Page size (default 50, clamped to 1-100)")]
+    pub limit: Option<i64>,
+
+    #[schemars(description = "Number of matching applications to skip (default 0)")]
+    pub offset: Option<i64>,
+}
+
+/// Input schema for `otter_count_matching` tool
+#[derive(Debug, Default, Deserialize, Serialize, schemars::JsonSchema)]
+pub struct CountMatchingInput {
+    #[schemars(
+        description = "Only names containing this text (case-sensitive, no wildcards, at most 256 characters)"
+    )]
+    pub name_contains: Option<String>,
+
+    #[schemars(description = "Only applications with this status. Omit for every status.")]
+    pub status: Option<AppStatusInput>,
+}
+
+/// Input schema for `otter_search` tool
+#[derive(Debug, Deserialize, Serialize, schemars::JsonSchema)]
+pub struct SearchInput {
+    #[schemars(
+        description = "Words that must all appear in the name or description (case-insensitive, at most 256 characters). End a word with * to match by prefix."
+    )]
+    pub query: String,
+}
+
+/// Input schema for `otter_search_all` tool
+#[derive(Debug, Deserialize, Serialize, schemars::JsonSchema)]
+pub struct SearchAllInput {
+    #[schemars(
+        description = "Text to look for in names, descriptions, and metadata values (case-insensitive, no wildcards, at most 256 characters)"
+    )]
+    pub query: String,
+}
+
+/// Application status accepted as a filter
+#[derive(Debug, Clone, Copy, Deserialize, Serialize, schemars::JsonSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum AppStatusInput {
+    Active,
+    Archived,
+}
+
+impl From<AppStatusInput> for ottershipper_db::AppStatus {
+    fn from(status: AppStatusInput) -> Self {
+        match status {
+            AppStatusInput::Active => Self::Active,
+            AppStatusInput::Archived => Self::Archived,
+        }
+    }
+}
+
+/// Column accepted by `otter_query_apps` for sorting
+#[derive(Debug, Clone, Copy, Deserialize, Serialize, schemars::JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum SortFieldInput {
+    Name,
+    CreatedAt,
+    UpdatedAt,
+}
+
+impl From<SortFieldInput> for ottershipper_db::SortField {
+    fn from(field: SortFieldInput) -> Self {
+        match field {
+            SortFieldInput::Name => Self::Name,
+            SortFieldInput::CreatedAt => Self::CreatedAt,
+            SortFieldInput::UpdatedAt => Self::UpdatedAt,
+        }
+    }
+}
+
+/// Sort direction accepted by `otter_query_apps`
+#[derive(Debug, Clone, Copy, Deserialize, Serialize, schemars::JsonSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum SortDirectionInput {
+    Asc,
+    Desc,
+}
+
+impl From<SortDirectionInput> for ottershipper_db::SortDirection {
+    fn from(direction: SortDirectionInput) -> Self {
+        match direction {
+            SortDirectionInput::Asc => Self::Asc,
+            SortDirectionInput::Desc => Self::Desc,
+        }
+    }
+}
+
+/// Input schema for `otter_rename_app` tool
+#[derive(Debug, Deserialize, Serialize, schemars::JsonSchema)]
+pub struct RenameAppInput {
+    #[schemars(
+        description = "ID of the application to rename. Provide either id or current_name."
+    )]
+    pub id: Option<String>,
+
+    #[schemars(
+        description = "Current name of the application to rename. Provide either id or current_name."
+    )]
+    pub current_name: Option<String>,
+
+    #[schemars(description = "New name for the application")]
+    pub new_name: String,
+
+    #[schemars(
+        description = "Owner whose application to rename by name. Only matters when the server scopes names by owner; omit for applications nobody owns."
+    )]
+    pub owner: Option<String>,
+}
+
+/// Input schema for `otter_rename_prefix` tool
+#[derive(Debug, Deserialize, Serialize, schemars::JsonSchema)]
+pub struct RenamePrefixInput {
+    #[schemars(
+        description = "Prefix to replace, e.g. \"svc-\". Every application whose name starts with it is renamed. Case-sensitive; must not be empty."
+    )]
+    pub old_prefix: String,
+
+    #[schemars(description = "Prefix to put in its place, e.g. \"team-a-\". May be empty.")]
+    pub new_prefix: String,
+
+    #[schemars(
+        description = "Owner whose applications to rename. Only matters when the server scopes names by owner: then only this owner's applications are renamed, or only those nobody owns if omitted."
+    )]
+    pub owner: Option<String>,
+}
+
+/// A point in time given as an ISO-8601 (RFC 3339) string or epoch milliseconds
+#[derive(Debug, Clone, Deserialize, Serialize, schemars::JsonSchema)]
+#[serde(untagged)]
+pub enum TimestampInput {
+    /// Milliseconds since the Unix epoch
+    EpochMillis(i64),
+    /// ISO-8601 / RFC 3339 timestamp, e.g. "2024-01-31T12:00:00Z", or epoch
+    /// milliseconds as a string, e.g. "1706702400000"
+    Iso8601(String),
+}
+
+impl TimestampInput {
+    /// Convert to epoch milliseconds
+    pub fn to_epoch_millis(&self) -> Result<i64, String> {
+        match self {
+            Self::EpochMillis(ms) => Ok(*ms),
+            Self::Iso8601(s) => {
+                if let Ok(ms) = s.trim().parse::<i64>() {
+                    return Ok(ms);
+                }
+                chrono::DateTime::parse_from_rfc3339(s)
+                    .map(|dt| dt.timestamp_millis())
+                    .map_err(|e| format!("invalid ISO-8601 timestamp '{s}': {e}"))
+            }
+        }
+    }
+}
+
+/// Input schema for `otter_apps_created_between` tool
+#[derive(Debug, Deserialize, Serialize, schemars::JsonSchema)]
+pub struct CreatedBetweenInput {
+    #[schemars(
+        description = "Start of the window (inclusive), as an ISO-8601 timestamp or epoch milliseconds"
+    )]
+    pub start: TimestampInput,
+
+    #[schemars(
+        description = "End of the window (inclusive), as an ISO-8601 timestamp or epoch milliseconds"
+    )]
+    pub end: TimestampInput,
+}
+
+/// Input schema for `otter_apply_app` tool
+#[derive(Debug, Deserialize, Serialize, schemars::JsonSchema)]
+pub struct ApplyAppInput {
+    #[schemars(
+        description = "Name of the application to create or update. Must follow the server's name rules."
     )]
     pub name: String,
+
+    #[schemars(description = "Description to set. Omitting it clears any existing description.")]
+    pub description: Option<String>,
+}
+
+/// Input schema for `otter_get_app` tool
+#[derive(Debug, Deserialize, Serialize, schemars::JsonSchema)]
+pub struct GetAppInput {
+    #[schemars(description = "ID of the application to look up. Provide either id or name.")]
+    pub id: Option<String>,
+
+    #[schemars(description = "Name of the application to look up. Provide either id or name.")]
+    pub name: Option<String>,
+
+    #[schemars(
+        description = "Owner whose application to look up by name. Only matters when the server scopes names by owner; omit for applications nobody owns."
+    )]
+    pub owner: Option<String>,
+}
+
+/// Input schema for `otter_describe_app` tool
+#[derive(Debug, Deserialize, Serialize, schemars::JsonSchema)]
+pub struct DescribeAppInput {
+    #[schemars(description = "ID of the application to describe. Provide either id or name.")]
+    pub id: Option<String>,
+
+    #[schemars(description = "Name of the application to describe. Provide either id or name.")]
+    pub name: Option<String>,
+
+    #[schemars(description = "Number of recent audit entries to include (default 10)")]
+    pub audit_limit: Option<usize>,
+
+    #[schemars(
+        description = "Owner whose application to describe by name. Only matters when the server scopes names by owner; omit for applications nobody owns."
+    )]
+    pub owner: Option<String>,
+}
+
+/// Input schema for `otter_archive_app` and `otter_unarchive_app` tools
+#[derive(Debug, Deserialize, Serialize, schemars::JsonSchema)]
+pub struct ArchiveAppInput {
+    #[schemars(description = "ID of the application. Provide either id or name.")]
+    pub id: Option<String>,
+
+    #[schemars(description = "Name of the application. Provide either id or name.")]
+    pub name: Option<String>,
+
+    #[schemars(
+        description = "Owner whose application to find by name. Only matters when the server scopes names by owner; omit for applications nobody owns."
+    )]
+    pub owner: Option<String>,
+}
+
+/// Input schema for `otter_touch_app` tool
+#[derive(Debug, Deserialize, Serialize, schemars::JsonSchema)]
+pub struct TouchAppInput {
+    #[schemars(description = "ID of the application. Provide either id or name.")]
+    pub id: Option<String>,
+
+    #[schemars(description = "Name of the application. Provide either id or name.")]
+    pub name: Option<String>,
+
+    #[schemars(
+        description = "Owner whose application to touch by name. Only matters when the server scopes names by owner; omit for applications nobody owns."
+    )]
+    pub owner: Option<String>,
+}
+
+/// Input schema for `otter_clone_app` tool
+#[derive(Debug, Deserialize, Serialize, schemars::JsonSchema)]
+pub struct CloneAppInput {
+    #[schemars(description = "ID of the application to copy")]
+    pub source_id: String,
+
+    #[schemars(description = "Name for the new application")]
+    pub name: String,
+}
+
+/// Input schema for `otter_validate_name` tool
+#[derive(Debug, Deserialize, Serialize, schemars::JsonSchema)]
+pub struct ValidateNameInput {
+    #[schemars(description = "Application name to check against the server's naming rules")]
+    pub name: String,
+}
+
+/// Input schema for `otter_delete_app` tool
+#[derive(Debug, Deserialize, Serialize, schemars::JsonSchema)]
+pub struct DeleteAppInput {
+    #[schemars(
+        description = "ID of the application to delete. Provide either id or name, or both if the server requires confirmation."
+    )]
+    pub id: Option<String>,
+
+    #[schemars(
+        description = "Name of the application to delete. Provide either id or name, or both if the server requires confirmation."
+    )]
+    pub name: Option<String>,
+
+    #[schemars(
+        description = "Owner whose application to delete by name. Only matters when the server scopes names by owner; omit for applications nobody owns."
+    )]
+    pub owner: Option<String>,
+}
+
+/// Input schema for `otter_delete_apps` tool
+#[derive(Debug, Deserialize, Serialize, schemars::JsonSchema)]
+pub struct DeleteAppsInput {
+    #[schemars(
+        description = "IDs of the applications to delete. Unknown IDs are ignored; an empty list deletes nothing."
+    )]
+    pub ids: Vec<String>,
+
+    #[schemars(
+        description = "Names of the applications, in the same order as ids. Required when the server requires confirmation; if given, any mismatch deletes nothing."
+    )]
+    pub names: Option<Vec<String>>,
+}
+
+/// Input schema for `otter_reset` tool
+#[derive(Debug, Deserialize, Serialize, schemars::JsonSchema)]
+pub struct ResetInput {
+    #[schemars(description = "Must be exactly \"DELETE ALL\"; anything else deletes nothing")]
+    pub confirm: String,
+}
+
+/// Input schema for `otter_audit_log` tool
+#[derive(Debug, Deserialize, Serialize, schemars::JsonSchema)]
+pub struct AuditLogInput {
+    #[schemars(
+        description = "Only return the trail of the application with this ID (oldest first). Omit to return the most recent entries across all applications (newest first)."
+    )]
+    pub app_id: Option<String>,
+
+    #[schemars(
+        description = "Number of recent entries to return when app_id is omitted (default 10, max 100)"
+    )]
+    pub limit: Option<i64>,
+}
+
+/// Input schema for `otter_checkpoint` tool
+#[derive(Debug, Deserialize, Serialize, schemars::JsonSchema)]
+pub struct CheckpointInput {
+    #[schemars(
+        description = "Checkpoint mode: \"passive\" (default, never blocks), \"full\", \"restart\", or \"truncate\" (also shrinks the WAL file to zero bytes)"
+    )]
+    pub mode: Option<CheckpointModeInput>,
+}
+
+/// WAL checkpoint mode accepted by `otter_checkpoint`
+#[derive(Debug, Clone, Copy, Deserialize, Serialize, schemars::JsonSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum CheckpointModeInput {
+    Passive,
+    Full,
+    Restart,
+    Truncate,
+}
+
+impl From<CheckpointModeInput> for ottershipper_db::CheckpointMode {
+    fn from(mode: CheckpointModeInput) -> Self {
+        match mode {
+            CheckpointModeInput::Passive => Self::Passive,
+            CheckpointModeInput::Full => Self::Full,
+            CheckpointModeInput::Restart => Self::Restart,
+            CheckpointModeInput::Truncate => Self::Truncate,
+        }
+    }
+}
+
+/// Input schema for `otter_set_metadata` tool
+#[derive(Debug, Deserialize, Serialize, schemars::JsonSchema)]
+pub struct SetMetadataInput {
+    #[schemars(description = "Application ID")]
+    pub id: String,
+
+    #[schemars(
+        description = "Metadata key, e.g. \"owner\" or \"repo_url\": up to 64 alphanumeric characters, hyphens, and underscores, starting with an alphanumeric character"
+    )]
+    pub key: String,
+
+    #[schemars(
+        description = "Value to store (up to 4096 characters); replaces any existing value"
+    )]
+    pub value: String,
+}
+
+/// Input schema for `otter_get_metadata` tool
+#[derive(Debug, Deserialize, Serialize, schemars::JsonSchema)]
+pub struct GetMetadataInput {
+    #[schemars(description = "Application ID")]
+    pub id: String,
+
+    #[schemars(description = "Key to read. Omit to list all metadata of the application.")]
+    pub key: Option<String>,
+}
+
+/// Input schema for `otter_find_by_meta` tool
+#[derive(Debug, Deserialize, Serialize, schemars::JsonSchema)]
+pub struct FindByMetaInput {
+    #[schemars(description = "Metadata key to match, e.g. \"repo\"")]
+    pub key: String,
+
+    #[schemars(description = "Value the key must have exactly (case-sensitive)")]
+    pub value: String,
+}
+
+/// Input schema for `otter_delete_metadata` tool
+#[derive(Debug, Deserialize, Serialize, schemars::JsonSchema)]
+pub struct DeleteMetadataInput {
+    #[schemars(description = "Application ID")]
+    pub id: String,
+
+    #[schemars(description = "Key to remove")]
+    pub key: String,
+}
+
+/// Body of every successful tool result
+///
+/// `success` is always `true`; the other fields depend on the tool.
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize, schemars::JsonSchema)]
+pub struct SuccessEnvelope {
+    /// Always `true`
+    pub success: bool,
+
+    /// Tool-specific fields
+    #[serde(flatten)]
+    pub fields: serde_json::Map<String, serde_json::Value>,
+}
+
+/// `data` attached to every tool error
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize, schemars::JsonSchema)]
+pub struct ErrorEnvelope {
+    /// Always `false`
+    pub success: bool,
+
+    /// Machine-readable cause of the error
+    pub reason: ErrorReason,
+
+    /// Human-readable description, the same as the error message
+    pub message: String,
+
+    /// Seconds to wait before retrying, for `rate_limited` errors
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub retry_after_secs: Option<u64>,
+
+    /// Milliseconds to wait before retrying, for `busy` errors
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub retry_after_ms: Option<u64>,
+
+    /// The application already using the name, for `duplicate_name` errors
+    /// (omitted if it couldn't be loaded)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub existing: Option<ExistingApplication>,
+}
+
+/// The application a create collided with
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize, schemars::JsonSchema)]
+pub struct ExistingApplication {
+    /// Application ID
+    pub id: String,
+    /// Application name
+    pub name: String,
+    /// Creation time as an RFC 3339 UTC timestamp
+    pub created_at: String,
+}
+
+/// Why a tool call failed
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize, Serialize, schemars::JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum ErrorReason {
+    /// The application (or other target) doesn't exist
+    NotFound,
+    /// An argument was missing, malformed, or out of range
+    InvalidParams,
+    /// The application name breaks the naming rules
+    InvalidName,
+    /// Another application already has this name
+    DuplicateName,
+    /// The configured application limit was reached
+    LimitExceeded,
+    /// Too many tool calls; retry after `retry_after_secs`
+    RateLimited,
+    /// The database is overloaded; back off and retry after `retry_after_ms`
+    Busy,
+    /// The disk holding the database is full; writes fail until space is freed
+    StorageFull,
+    /// The request can't be served in the server's current state
+    InvalidRequest,
+    /// Unexpected server-side failure
+    Internal,
 }
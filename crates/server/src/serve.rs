@@ -0,0 +1,263 @@
+use crate::McpServer;
+use rmcp::service::{RunningService, ServerInitializeError};
+use rmcp::transport::IntoTransport;
+use rmcp::{RoleServer, ServiceExt};
+use std::io;
+use std::time::Duration;
+use tokio_util::sync::CancellationToken;
+
+/// Error from the MCP handshake on a transport
+pub type ServeError = ServerInitializeError<io::Error>;
+
+/// Handshake attempts on one client before giving up
+const MAX_SERVE_ATTEMPTS: u32 = 3;
+
+/// Delay before the first retry; doubled on each further attempt
+const INITIAL_BACKOFF: Duration = Duration::from_millis(100);
+
+/// Whether a failed handshake is worth another attempt
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Retry {
+    /// A brief I/O hiccup; the same client may still succeed
+    Transient,
+    /// The client went away or spoke the wrong protocol; retrying can't help
+    Permanent,
+}
+
+/// Classify a handshake failure
+///
+/// Only interrupted, would-block and timed-out transport I/O is transient.
+/// Closed connections, cancellation, and every protocol error (unexpected
+/// messages, unsupported protocol versions) are permanent.
+#[must_use]
+pub fn classify_serve_error(error: &ServeError) -> Retry {
+    match error {
+        ServerInitializeError::TransportError { error, .. }
+            if matches!(
+                error.kind(),
+                io::ErrorKind::Interrupted | io::ErrorKind::WouldBlock | io::ErrorKind::TimedOut
+            ) =>
+        {
+            Retry::Transient
+        }
+        _ => Retry::Permanent,
+    }
+}
+
+/// Serve `server` on `transport`, retrying transient handshake failures
+///
+/// A failed handshake consumes its transport, so each retry serves the one
+/// `reopen` returns; a transport that can't be reopened, like an SSE session,
+/// returns `None` and the failure is final. Retries back off exponentially,
+/// stop after a few attempts, and end early when `ct` is cancelled. `classify`
+/// decides which failures are retried, normally [`classify_serve_error`].
+pub async fn serve_with_retry<T, A>(
+    server: McpServer,
+    mut transport: T,
+    mut reopen: impl FnMut() -> Option<T>,
+    ct: CancellationToken,
+    classify: fn(&ServeError) -> Retry,
+) -> Result<RunningService<RoleServer, McpServer>, ServeError>
+where
+    T: IntoTransport<RoleServer, io::Error, A>,
+{
+    let mut backoff = INITIAL_BACKOFF;
+    let mut attempt = 1;
+    loop {
+        let error = match server
+            .clone()
+            .serve_with_ct(transport, ct.child_token())
+            .await
+        {
+            Ok(service) => return Ok(service),
+            Err(error) => error,
+        };
+        if attempt >= MAX_SERVE_ATTEMPTS || classify(&error) == Retry::Permanent {
+            return Err(error);
+        }
+        let Some(next) = reopen() else {
+            return Err(error);
+        };
+
+        tracing::warn!(
+            attempt,
+            "MCP handshake failed, retrying in {backoff:?}: {error}"
+        );
+        tokio::select! {
+            () = tokio::time::sleep(backoff) => {}
+            () = ct.cancelled() => return Err(ServerInitializeError::Cancelled),
+        }
+        transport = next;
+        backoff *= 2;
+        attempt += 1;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use futures::{SinkExt, StreamExt};
+    use ottershipper_core::ApplicationService;
+    use ottershipper_db::Database;
+    use rmcp::model::{ClientJsonRpcMessage, ProtocolVersion, ServerJsonRpcMessage};
+    use serde_json::json;
+    use std::pin::Pin;
+
+    type TestSink = Pin<Box<dyn futures::Sink<ServerJsonRpcMessage, Error = io::Error> + Send>>;
+    type TestStream = Pin<Box<dyn futures::Stream<Item = ClientJsonRpcMessage> + Send>>;
+
+    fn transport_error(kind: io::ErrorKind) -> ServeError {
+        ServerInitializeError::TransportError {
+            error: io::Error::from(kind),
+            context: "sending initialize response".into(),
+        }
+    }
+
+    fn message(value: serde_json::Value) -> ClientJsonRpcMessage {
+        serde_json::from_value(value).unwrap()
+    }
+
+    fn initialize() -> ClientJsonRpcMessage {
+        message(json!({
+            "jsonrpc": "2.0",
+            "id": 1,
+            "method": "initialize",
+            "params": {
+                "protocolVersion": "2025-03-26",
+                "capabilities": {},
+                "clientInfo": { "name": "test", "version": "1.0" },
+            },
+        }))
+    }
+
+    /// A client sending `messages` and then staying connected, whose replies
+    /// fail with `send_error` if given
+    fn client(
+        messages: Vec<ClientJsonRpcMessage>,
+        send_error: Option<io::ErrorKind>,
+    ) -> (TestSink, TestStream) {
+        let sink: TestSink = match send_error {
+            Some(kind) => Box::pin(futures::sink::unfold((), move |(), _| async move {
+                Err(io::Error::from(kind))
+            })),
+            None => Box::pin(futures::sink::drain().sink_map_err(|never| match never {})),
+        };
+        let stream = futures::stream::iter(messages).chain(futures::stream::pending());
+        (sink, Box::pin(stream))
+    }
+
+    async fn server() -> McpServer {
+        let db = Database::new_in_memory().await.unwrap();
+        db.migrate().await.unwrap();
+        McpServer::new(ApplicationService::new(db))
+    }
+
+    #[test]
+    fn test_classify_serve_error() {
+        for kind in [
+            io::ErrorKind::Interrupted,
+            io::ErrorKind::WouldBlock,
+            io::ErrorKind::TimedOut,
+        ] {
+            assert_eq!(
+                classify_serve_error(&transport_error(kind)),
+                Retry::Transient,
+                "{kind:?}"
+            );
+        }
+
+        let permanent = [
+            transport_error(io::ErrorKind::BrokenPipe),
+            transport_error(io::ErrorKind::ConnectionReset),
+            ServerInitializeError::ConnectionClosed("initialized request".to_string()),
+            ServerInitializeError::ExpectedInitializeRequest(None),
+            ServerInitializeError::ExpectedInitializedNotification(None),
+            ServerInitializeError::UnsupportedProtocolVersion(ProtocolVersion::default()),
+            ServerInitializeError::Cancelled,
+        ];
+        for error in &permanent {
+            assert_eq!(classify_serve_error(error), Retry::Permanent, "{error}");
+        }
+    }
+
+    #[tokio::test]
+    async fn test_serve_with_retry_retries_transient_failures() {
+        let initialized = message(json!({
+            "jsonrpc": "2.0",
+            "method": "notifications/initialized",
+        }));
+        let mut reopened = 0;
+
+        let service = serve_with_retry(
+            server().await,
+            client(vec![initialize()], Some(io::ErrorKind::Interrupted)),
+            || {
+                reopened += 1;
+                Some(client(vec![initialize(), initialized.clone()], None))
+            },
+            CancellationToken::new(),
+            classify_serve_error,
+        )
+        .await
+        .expect("the retry should succeed");
+
+        assert_eq!(reopened, 1);
+        service.cancel().await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_serve_with_retry_gives_up() {
+        // Permanent failures aren't retried
+        let mut reopened = 0;
+        let ping = message(json!({ "jsonrpc": "2.0", "id": 1, "method": "ping" }));
+        let result = serve_with_retry(
+            server().await,
+            client(vec![ping], None),
+            || {
+                reopened += 1;
+                Some(client(vec![initialize()], None))
+            },
+            CancellationToken::new(),
+            classify_serve_error,
+        )
+        .await;
+        assert!(matches!(
+            result,
+            Err(ServerInitializeError::ExpectedInitializeRequest(_))
+        ));
+        assert_eq!(reopened, 0);
+
+        // Transient failures stop after the last attempt
+        let mut reopened = 0;
+        let result = serve_with_retry(
+            server().await,
+            client(vec![initialize()], Some(io::ErrorKind::TimedOut)),
+            || {
+                reopened += 1;
+                Some(client(vec![initialize()], Some(io::ErrorKind::TimedOut)))
+            },
+            CancellationToken::new(),
+            classify_serve_error,
+        )
+        .await;
+        assert!(matches!(
+            result,
+            Err(ServerInitializeError::TransportError { .. })
+        ));
+        assert_eq!(reopened, MAX_SERVE_ATTEMPTS - 1);
+
+        // A transport that can't be reopened is served once
+        let result = serve_with_retry(
+            server().await,
+            client(vec![initialize()], Some(io::ErrorKind::Interrupted)),
+            || None,
+            CancellationToken::new(),
+            classify_serve_error,
+        )
+        .await;
+        assert!(matches!(
+            result,
+            Err(ServerInitializeError::TransportError { .. })
+        ));
+    }
+}
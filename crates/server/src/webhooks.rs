@@ -0,0 +1,133 @@
+use crate::config::WebhookConfig;
+use hmac::{Hmac, Mac};
+use ottershipper_core::AppEvent;
+use serde_json::{json, Value};
+use sha2::Sha256;
+use std::fmt::Write;
+use std::time::Duration;
+use tokio::sync::broadcast::{self, error::RecvError};
+use tokio::task::JoinHandle;
+
+/// Header carrying the payload signature, `sha256=<hex HMAC-SHA256 of the body>`
+pub const SIGNATURE_HEADER: &str = "X-Otter-Signature";
+
+/// Delay before the first retry; doubled on each further attempt
+const INITIAL_BACKOFF: Duration = Duration::from_millis(500);
+
+/// How long a single delivery attempt may take
+const REQUEST_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Delivers application change events to a configured URL
+///
+/// Deliveries run on their own task, in event order, so MCP requests never wait
+/// on the receiving endpoint.
+pub struct WebhookNotifier {
+    url: String,
+    secret: Option<String>,
+    max_retries: u32,
+    client: reqwest::Client,
+}
+
+impl WebhookNotifier {
+    /// Build a notifier from `config`, or `None` if no URL is configured
+    #[must_use]
+    pub fn from_config(config: &WebhookConfig) -> Option<Self> {
+        let url = config.url.clone()?;
+        let client = reqwest::Client::builder()
+            .timeout(REQUEST_TIMEOUT)
+            .build()
+            .unwrap_or_default();
+
+        Some(Self {
+            url,
+            secret: config.secret.clone(),
+            max_retries: config.max_retries,
+            client,
+        })
+    }
+
+    /// Deliver every event received on `events` until the channel closes
+    #[must_use]
+    pub fn spawn(self, mut events: broadcast::Receiver<AppEvent>) -> JoinHandle<()> {
+        tokio::spawn(async move {
+            loop {
+                match events.recv().await {
+                    Ok(event) => self.deliver(&event).await,
+                    Err(RecvError::Lagged(missed)) => {
+                        tracing::warn!(missed, "Webhook delivery fell behind, events dropped");
+                    }
+                    Err(RecvError::Closed) => break,
+                }
+            }
+        })
+    }
+
+    async fn deliver(&self, event: &AppEvent) {
+        let body = payload(event).to_string();
+
+        let mut backoff = INITIAL_BACKOFF;
+        for attempt in 0..=self.max_retries {
+            if attempt > 0 {
+                tokio::time::sleep(backoff).await;
+                backoff *= 2;
+            }
+
+            let mut request = self
+                .client
+                .post(&self.url)
+                .header(reqwest::header::CONTENT_TYPE, "application/json")
+                .body(body.clone());
+            if let Some(secret) = &self.secret {
+                request = request.header(SIGNATURE_HEADER, sign(secret, body.as_bytes()));
+            }
+
+            match request
+                .send()
+                .await
+                .and_then(reqwest::Response::error_for_status)
+            {
+                Ok(_) => return,
+                Err(e) => tracing::debug!(attempt, "Webhook delivery failed: {}", e),
+            }
+        }
+
+        tracing::error!(
+            url = %self.url,
+            attempts = self.max_retries + 1,
+            "Giving up on webhook delivery"
+        );
+    }
+}
+
+/// JSON body sent for `event`
+fn payload(event: &AppEvent) -> Value {
+    let (name, application) = match event {
+        AppEvent::Created(app) => ("created", json!(app)),
+        AppEvent::Renamed { id, old, new } => (
+            "renamed",
+            json!({ "id": id, "name": new, "previous_name": old }),
+        ),
+        AppEvent::Deleted(id) => ("deleted", json!({ "id": id })),
+    };
+
+    json!({
+        "event": name,
+        "application": application,
+        "timestamp": chrono::Utc::now().timestamp_millis(),
+    })
+}
+
+/// Signature header value for `body`
+fn sign(secret: &str, body: &[u8]) -> String {
+    // HMAC accepts keys of any length, so this can't fail
+    let mut mac = Hmac::<Sha256>::new_from_slice(secret.as_bytes()).expect("any key length");
+    mac.update(body);
+
+    mac.finalize()
+        .into_bytes()
+        .iter()
+        .fold(String::from("sha256="), |mut out, b| {
+            let _ = write!(out, "{b:02x}");
+            out
+        })
+}
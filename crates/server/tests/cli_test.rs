@@ -0,0 +1,112 @@
+use std::process::Command;
+use tempfile::tempdir;
+
+fn ottershipper() -> Command {
+    let mut command = Command::new(env!("CARGO_BIN_EXE_ottershipper"));
+    command.env_remove("OTTERSHIPPER_DEFAULT_TRANSPORT");
+    command
+}
+
+/// Test that --check-config accepts a valid file and prints the effective config
+#[test]
+fn test_check_config_valid() -> Result<(), Box<dyn std::error::Error>> {
+    let temp_dir = tempdir()?;
+    let config_path = temp_dir.path().join("ottershipper.toml");
+    std::fs::write(&config_path, "[server]\nport = 4000\n")?;
+
+    let output = ottershipper()
+        .arg("--check-config")
+        .arg(&config_path)
+        .output()?;
+    assert!(output.status.success());
+
+    // Defaults are filled in alongside the configured values
+    let stdout = String::from_utf8(output.stdout)?;
+    assert!(stdout.contains("port = 4000"), "{stdout}");
+    assert!(stdout.contains("transport = \"stdio\""), "{stdout}");
+
+    Ok(())
+}
+
+/// Test that `OTTERSHIPPER_DEFAULT_TRANSPORT` sets the transport when nothing else does
+#[test]
+fn test_default_transport_env() -> Result<(), Box<dyn std::error::Error>> {
+    let temp_dir = tempdir()?;
+    let config_path = temp_dir.path().join("ottershipper.toml");
+    std::fs::write(&config_path, "[server]\nport = 4000\n")?;
+
+    let output = ottershipper()
+        .env("OTTERSHIPPER_DEFAULT_TRANSPORT", "http")
+        .arg("--check-config")
+        .arg(&config_path)
+        .output()?;
+    assert!(output.status.success());
+
+    let stdout = String::from_utf8(output.stdout)?;
+    assert!(stdout.contains("transport = \"http\""), "{stdout}");
+
+    Ok(())
+}
+
+/// Test that --check-config validates the --transport override, wherever it's given
+#[test]
+fn test_check_config_with_transport() -> Result<(), Box<dyn std::error::Error>> {
+    let temp_dir = tempdir()?;
+    let config_path = temp_dir.path().join("ottershipper.toml");
+    std::fs::write(&config_path, "[server]\nport = 4000\n")?;
+    let config_path = config_path.to_str().unwrap();
+
+    for args in [
+        ["--transport", "http", "--check-config", config_path],
+        ["--check-config", config_path, "--transport", "http"],
+    ] {
+        let output = ottershipper().args(args).output()?;
+        assert!(output.status.success(), "{args:?}");
+        let stdout = String::from_utf8(output.stdout)?;
+        assert!(
+            stdout.contains("transport = \"http\""),
+            "{args:?}: {stdout}"
+        );
+    }
+
+    let output = ottershipper()
+        .args([
+            "--check-config",
+            config_path,
+            "--transport",
+            "smoke-signals",
+        ])
+        .output()?;
+    assert!(!output.status.success());
+    let stderr = String::from_utf8(output.stderr)?;
+    assert!(stderr.contains("smoke-signals"), "{stderr}");
+
+    Ok(())
+}
+
+/// Test that --check-config exits nonzero for an invalid or missing file
+#[test]
+fn test_check_config_invalid() -> Result<(), Box<dyn std::error::Error>> {
+    let temp_dir = tempdir()?;
+    let config_path = temp_dir.path().join("ottershipper.toml");
+    std::fs::write(&config_path, "[server]\ntransport = \"smoke-signals\"\n")?;
+
+    let output = ottershipper()
+        .arg("--check-config")
+        .arg(&config_path)
+        .output()?;
+    assert!(!output.status.success());
+    let stderr = String::from_utf8(output.stderr)?;
+    assert!(stderr.contains("smoke-signals"), "{stderr}");
+
+    // Nothing was created next to the config
+    assert!(!temp_dir.path().join("ottershipper.db").exists());
+
+    let output = ottershipper()
+        .arg("--check-config")
+        .arg(temp_dir.path().join("missing.toml"))
+        .output()?;
+    assert!(!output.status.success());
+
+    Ok(())
+}
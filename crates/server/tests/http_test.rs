@@ -0,0 +1,328 @@
+use ottershipper_core::ApplicationService;
+use ottershipper_db::Database;
+use ottershipper_server::{
+    export_routes, run_sse, serve_sse, CorsConfig, McpServer, ServerConfig, Transport, EXPORT_PATH,
+    SCHEMA_VERSION_HEADER,
+};
+use rmcp::model::{CallToolRequestParam, CallToolResult};
+use rmcp::transport::SseClientTransport;
+use rmcp::{ClientHandler, ServiceExt};
+use tempfile::tempdir;
+use tokio::net::TcpListener;
+use tokio_util::sync::CancellationToken;
+
+/// Test that oversized POST bodies are rejected before reaching the MCP session
+#[tokio::test]
+async fn test_http_rejects_oversized_request() -> Result<(), Box<dyn std::error::Error>> {
+    let listener = TcpListener::bind("127.0.0.1:0").await?;
+    let addr = listener.local_addr()?;
+    let sse_server = serve_sse(listener, 1024, &CorsConfig::default(), axum::Router::new()).await?;
+
+    let client = reqwest::Client::new();
+    let url = format!("http://{addr}/message?sessionId=unknown");
+
+    let oversized = format!(
+        r#"{{"jsonrpc":"2.0","method":"ping","id":1,"padding":"{}"}}"#,
+        "x".repeat(4096)
+    );
+    let response = client
+        .post(&url)
+        .header("content-type", "application/json")
+        .body(oversized)
+        .send()
+        .await?;
+    assert_eq!(response.status(), reqwest::StatusCode::PAYLOAD_TOO_LARGE);
+
+    // Small bodies get past the limit (and fail only because the session doesn't exist)
+    let response = client
+        .post(&url)
+        .header("content-type", "application/json")
+        .body(r#"{"jsonrpc":"2.0","method":"ping","id":1}"#)
+        .send()
+        .await?;
+    assert_eq!(response.status(), reqwest::StatusCode::NOT_FOUND);
+
+    sse_server.cancel();
+
+    Ok(())
+}
+
+/// Test that CORS headers are sent only for allowed origins
+#[tokio::test]
+async fn test_http_cors_allowed_origin() -> Result<(), Box<dyn std::error::Error>> {
+    let cors = CorsConfig {
+        allowed_origins: vec!["https://dashboard.example.com".to_string()],
+    };
+    let listener = TcpListener::bind("127.0.0.1:0").await?;
+    let addr = listener.local_addr()?;
+    let sse_server = serve_sse(listener, 1024 * 1024, &cors, axum::Router::new()).await?;
+
+    let client = reqwest::Client::new();
+    let url = format!("http://{addr}/message?sessionId=unknown");
+
+    // Preflight from an allowed origin
+    let response = client
+        .request(reqwest::Method::OPTIONS, &url)
+        .header("origin", "https://dashboard.example.com")
+        .header("access-control-request-method", "POST")
+        .header("access-control-request-headers", "content-type")
+        .send()
+        .await?;
+    assert!(response.status().is_success());
+    assert_eq!(
+        response.headers()["access-control-allow-origin"],
+        "https://dashboard.example.com"
+    );
+
+    // Actual request from an allowed origin
+    let response = client
+        .post(&url)
+        .header("origin", "https://dashboard.example.com")
+        .header("content-type", "application/json")
+        .body(r#"{"jsonrpc":"2.0","method":"ping","id":1}"#)
+        .send()
+        .await?;
+    assert_eq!(
+        response.headers()["access-control-allow-origin"],
+        "https://dashboard.example.com"
+    );
+
+    // Other origins get no CORS headers
+    let response = client
+        .post(&url)
+        .header("origin", "https://evil.example.com")
+        .header("content-type", "application/json")
+        .body(r#"{"jsonrpc":"2.0","method":"ping","id":1}"#)
+        .send()
+        .await?;
+    assert!(response
+        .headers()
+        .get("access-control-allow-origin")
+        .is_none());
+
+    sse_server.cancel();
+
+    Ok(())
+}
+
+#[derive(Clone)]
+struct TestClient;
+
+impl ClientHandler for TestClient {}
+
+/// Parse a tool call's JSON response
+fn response_json(result: &CallToolResult) -> serde_json::Value {
+    serde_json::from_str(&result.content[0].as_text().unwrap().text).unwrap()
+}
+
+/// Test that stdio and HTTP can be served together over one MCP server and database
+#[tokio::test]
+async fn test_stdio_and_http_share_one_server() -> Result<(), Box<dyn std::error::Error>> {
+    let temp_dir = tempdir()?;
+    let db = Database::new(temp_dir.path().join("test.db")).await?;
+    db.migrate().await?;
+    let mcp_server = McpServer::new(ApplicationService::new(db.clone()));
+    let shutdown = CancellationToken::new();
+
+    // HTTP transport
+    let listener = TcpListener::bind("127.0.0.1:0").await?;
+    let addr = listener.local_addr()?;
+    let sse_server = serve_sse(
+        listener,
+        1024 * 1024,
+        &CorsConfig::default(),
+        export_routes(db.clone()),
+    )
+    .await?;
+    let http_handle = tokio::spawn(run_sse(mcp_server.clone(), sse_server, shutdown.clone()));
+    let http_client = TestClient
+        .serve(SseClientTransport::start(format!("http://{addr}/sse")).await?)
+        .await?;
+
+    // In-process pipe standing in for stdio
+    let (server_io, client_io) = tokio::io::duplex(4096);
+    let stdio_handle = tokio::spawn({
+        let server = mcp_server.clone();
+        let ct = shutdown.child_token();
+        async move {
+            let service = server.serve_with_ct(server_io, ct).await?;
+            service.waiting().await?;
+            anyhow::Ok(())
+        }
+    });
+    let stdio_client = TestClient.serve(client_io).await?;
+
+    // Each transport sees the other's applications
+    for (client, name) in [(&http_client, "via-http"), (&stdio_client, "via-stdio")] {
+        client
+            .call_tool(CallToolRequestParam {
+                name: "otter_create_app".into(),
+                arguments: serde_json::json!({ "name": name }).as_object().cloned(),
+            })
+            .await?;
+    }
+    for client in [&http_client, &stdio_client] {
+        let result = client
+            .call_tool(CallToolRequestParam {
+                name: "otter_list_apps".into(),
+                arguments: None,
+            })
+            .await?;
+        let response = response_json(&result);
+        assert_eq!(response["count"], 2);
+    }
+
+    // Cancelling shutdown stops both transports
+    shutdown.cancel();
+    http_handle.await?;
+    stdio_handle.await??;
+
+    Ok(())
+}
+
+/// Test that the NDJSON export streams one line per application
+#[tokio::test]
+async fn test_http_export_ndjson() -> Result<(), Box<dyn std::error::Error>> {
+    let temp_dir = tempdir()?;
+    let db = Database::new(temp_dir.path().join("test.db")).await?;
+    db.migrate().await?;
+    let count = 250;
+    for i in 0..count {
+        db.applications().create(&format!("app-{i:03}")).await?;
+    }
+
+    let listener = TcpListener::bind("127.0.0.1:0").await?;
+    let addr = listener.local_addr()?;
+    let sse_server = serve_sse(
+        listener,
+        1024 * 1024,
+        &CorsConfig::default(),
+        export_routes(db.clone()),
+    )
+    .await?;
+
+    let response = reqwest::get(format!("http://{addr}{EXPORT_PATH}")).await?;
+    assert_eq!(response.status(), reqwest::StatusCode::OK);
+    assert_eq!(
+        response.headers()[reqwest::header::CONTENT_TYPE],
+        "application/x-ndjson"
+    );
+    assert_eq!(
+        response.headers()[SCHEMA_VERSION_HEADER.as_str()],
+        Database::latest_schema_version()
+    );
+
+    let body = response.text().await?;
+    assert!(body.ends_with('\n'));
+    let lines: Vec<serde_json::Value> = body
+        .lines()
+        .map(serde_json::from_str)
+        .collect::<Result<_, _>>()?;
+    assert_eq!(lines.len(), count);
+    let mut names: Vec<_> = lines
+        .iter()
+        .map(|app| app["name"].as_str().unwrap().to_string())
+        .collect();
+    names.sort();
+    names.dedup();
+    assert_eq!(names.len(), count);
+
+    // An empty catalog is an empty body, not an error
+    let empty = Database::new(temp_dir.path().join("empty.db")).await?;
+    empty.migrate().await?;
+    let listener = TcpListener::bind("127.0.0.1:0").await?;
+    let empty_addr = listener.local_addr()?;
+    let empty_server = serve_sse(
+        listener,
+        1024 * 1024,
+        &CorsConfig::default(),
+        export_routes(empty),
+    )
+    .await?;
+    let response = reqwest::get(format!("http://{empty_addr}{EXPORT_PATH}")).await?;
+    assert_eq!(response.status(), reqwest::StatusCode::OK);
+    assert!(response.text().await?.is_empty());
+
+    sse_server.cancel();
+    empty_server.cancel();
+
+    Ok(())
+}
+
+/// Test that the export answers a matching `If-None-Match` with 304 until
+/// an application changes
+#[tokio::test]
+async fn test_http_export_etag() -> Result<(), Box<dyn std::error::Error>> {
+    let temp_dir = tempdir()?;
+    let db = Database::new(temp_dir.path().join("test.db")).await?;
+    db.migrate().await?;
+    let app = db.applications().create("api").await?;
+
+    let listener = TcpListener::bind("127.0.0.1:0").await?;
+    let addr = listener.local_addr()?;
+    let sse_server = serve_sse(
+        listener,
+        1024 * 1024,
+        &CorsConfig::default(),
+        export_routes(db.clone()),
+    )
+    .await?;
+    let client = reqwest::Client::new();
+    let url = format!("http://{addr}{EXPORT_PATH}");
+    let get = |etag: Option<&str>| {
+        let mut request = client.get(&url);
+        if let Some(etag) = etag {
+            request = request.header(reqwest::header::IF_NONE_MATCH, etag);
+        }
+        request.send()
+    };
+
+    let response = get(None).await?;
+    assert_eq!(response.status(), reqwest::StatusCode::OK);
+    let etag = response.headers()[reqwest::header::ETAG]
+        .to_str()?
+        .to_string();
+    assert!(etag.starts_with("W/\""), "weak ETag expected: {etag}");
+
+    let response = get(Some(&etag)).await?;
+    assert_eq!(response.status(), reqwest::StatusCode::NOT_MODIFIED);
+    assert_eq!(response.headers()[reqwest::header::ETAG], etag.as_str());
+    assert!(response.text().await?.is_empty());
+    // Any tag in the list matches, weak or strong
+    let strong = etag.trim_start_matches("W/");
+    let response = get(Some(&format!("\"other\", {strong}"))).await?;
+    assert_eq!(response.status(), reqwest::StatusCode::NOT_MODIFIED);
+
+    db.applications().rename(&app.id, "api-v2").await?;
+    let response = get(Some(&etag)).await?;
+    assert_eq!(response.status(), reqwest::StatusCode::OK);
+    assert_ne!(response.headers()[reqwest::header::ETAG], etag.as_str());
+    assert!(response.text().await?.contains("api-v2"));
+
+    sse_server.cancel();
+
+    Ok(())
+}
+
+#[test]
+fn test_parse_transports() {
+    let parse = |transport: &str| {
+        ServerConfig {
+            transport: transport.to_string(),
+            ..Default::default()
+        }
+        .transports()
+    };
+
+    assert_eq!(parse("stdio").unwrap(), vec![Transport::Stdio]);
+    assert_eq!(parse("http").unwrap(), vec![Transport::Http]);
+    assert_eq!(
+        parse("stdio+http").unwrap(),
+        vec![Transport::Stdio, Transport::Http]
+    );
+    assert_eq!(
+        parse("http, stdio, http").unwrap(),
+        vec![Transport::Http, Transport::Stdio]
+    );
+    assert!(parse("carrier-pigeon").is_err());
+}
@@ -1,10 +1,14 @@
 use ottershipper_core::ApplicationService;
-use ottershipper_db::Database;
-use ottershipper_server::McpServer;
-use rmcp::model::CallToolRequestParam;
+use ottershipper_db::{AppStatus, ApplicationView, AuditAction, Database, DatabaseConfig};
+use ottershipper_server::{
+    ErrorEnvelope, ErrorReason, ExistingApplication, LimitsConfig, McpServer, ToolsConfig, HELP_URI,
+};
+use rmcp::model::{
+    CallToolRequestParam, CallToolResult, ReadResourceRequestParam, ResourceContents,
+};
 use rmcp::service::RunningService;
-use rmcp::{ClientHandler, RoleClient, ServiceExt};
-use tempfile::tempdir;
+use rmcp::{ClientHandler, RoleClient, ServerHandler, ServiceExt};
+use tempfile::{tempdir, TempDir};
 
 /// Test client handler
 #[derive(Clone)]
@@ -18,6 +22,22 @@ async fn setup_mcp_test() -> Result<
         Database,
         RunningService<RoleClient, TestClient>,
         tokio::task::JoinHandle<anyhow::Result<()>>,
+        TempDir,
+    ),
+    Box<dyn std::error::Error>,
+> {
+    setup_mcp_test_with(McpServer::new).await
+}
+
+/// Setup test environment, building the MCP server with `build_server`
+async fn setup_mcp_test_with(
+    build_server: impl FnOnce(ApplicationService) -> McpServer,
+) -> Result<
+    (
+        Database,
+        RunningService<RoleClient, TestClient>,
+        tokio::task::JoinHandle<anyhow::Result<()>>,
+        TempDir,
     ),
     Box<dyn std::error::Error>,
 > {
@@ -28,7 +48,7 @@ async fn setup_mcp_test() -> Result<
     db.migrate().await?;
 
     let service = ApplicationService::new(db.clone());
-    let mcp_server = McpServer::new(service);
+    let mcp_server = build_server(service);
 
     // Create duplex channel for server-client communication
     let (server_transport, client_transport) = tokio::io::duplex(4096);
@@ -43,14 +63,14 @@ async fn setup_mcp_test() -> Result<
     // Start client (automatically initializes)
     let client = TestClient.serve(client_transport).await?;
 
-    Ok((db, client, server_handle))
+    Ok((db, client, server_handle, temp_dir))
 }
 
 /// Test end-to-end MCP tool call: create application
 /// This tests the full stack: MCP protocol → service layer → database
 #[tokio::test]
 async fn test_mcp_create_app_e2e() -> Result<(), Box<dyn std::error::Error>> {
-    let (db, client, server_handle) = setup_mcp_test().await?;
+    let (db, client, server_handle, _temp_dir) = setup_mcp_test().await?;
 
     // Call otter_create_app tool
     let result = client
@@ -83,11 +103,446 @@ async fn test_mcp_create_app_e2e() -> Result<(), Box<dyn std::error::Error>> {
     Ok(())
 }
 
+/// Test that retrying a create with the same idempotency key is deduplicated
+#[tokio::test]
+async fn test_mcp_create_app_idempotency_key() -> Result<(), Box<dyn std::error::Error>> {
+    let (db, client, server_handle, _temp_dir) = setup_mcp_test().await?;
+
+    let mut ids = Vec::new();
+    for (name, key) in [
+        ("retried", "key-a"),
+        ("retried", "key-a"),
+        ("fresh", "key-b"),
+    ] {
+        let result = client
+            .call_tool(CallToolRequestParam {
+                name: "otter_create_app".into(),
+                arguments: serde_json::json!({ "name": name, "idempotency_key": key })
+                    .as_object()
+                    .cloned(),
+            })
+            .await?;
+        let response: serde_json::Value =
+            serde_json::from_str(&result.content[0].as_text().unwrap().text)?;
+        assert_eq!(response["success"], true);
+        ids.push((
+            response["application"]["id"].as_str().unwrap().to_string(),
+            response["created"].as_bool().unwrap(),
+        ));
+    }
+
+    // Same key returned the same app, different key created a new one
+    assert_eq!(ids[0].0, ids[1].0);
+    assert!(ids[0].1);
+    assert!(!ids[1].1);
+    assert_ne!(ids[0].0, ids[2].0);
+    assert!(ids[2].1);
+    assert_eq!(db.applications().list().await?.len(), 2);
+
+    client.cancel().await?;
+    server_handle.await??;
+
+    Ok(())
+}
+
+/// Test that a dry-run create reports availability without inserting
+#[tokio::test]
+async fn test_mcp_create_app_dry_run() -> Result<(), Box<dyn std::error::Error>> {
+    let (db, client, server_handle, _temp_dir) = setup_mcp_test().await?;
+
+    db.applications().create("taken-name").await?;
+
+    let dry_run = |name: &str| CallToolRequestParam {
+        name: "otter_create_app".into(),
+        arguments: serde_json::json!({ "name": name, "dry_run": true })
+            .as_object()
+            .cloned(),
+    };
+
+    // Taken name
+    let result = client.call_tool(dry_run("taken-name")).await?;
+    let response: serde_json::Value =
+        serde_json::from_str(&result.content[0].as_text().unwrap().text)?;
+    assert_eq!(response["valid"], true);
+    assert_eq!(response["would_create"], false);
+    assert!(response["reason"]
+        .as_str()
+        .unwrap()
+        .contains("already exists"));
+
+    // Free name
+    let result = client.call_tool(dry_run("free-name")).await?;
+    let response: serde_json::Value =
+        serde_json::from_str(&result.content[0].as_text().unwrap().text)?;
+    assert_eq!(response["would_create"], true);
+    assert!(response["reason"].is_null());
+
+    // Invalid name
+    let result = client.call_tool(dry_run("bad name")).await?;
+    let response: serde_json::Value =
+        serde_json::from_str(&result.content[0].as_text().unwrap().text)?;
+    assert_eq!(response["valid"], false);
+    assert_eq!(response["would_create"], false);
+
+    // Nothing was created
+    assert_eq!(db.applications().count().await?, 1);
+
+    client.cancel().await?;
+    server_handle.await??;
+
+    Ok(())
+}
+
+/// Test creating an application without a name generates one
+#[tokio::test]
+async fn test_mcp_create_app_autoname() -> Result<(), Box<dyn std::error::Error>> {
+    let (db, client, server_handle, _temp_dir) = setup_mcp_test().await?;
+
+    let result = client
+        .call_tool(CallToolRequestParam {
+            name: "otter_create_app".into(),
+            arguments: serde_json::json!({}).as_object().cloned(),
+        })
+        .await?;
+
+    let response: serde_json::Value =
+        serde_json::from_str(&result.content[0].as_text().unwrap().text)?;
+    assert_eq!(response["success"], true);
+    let name = response["application"]["name"].as_str().unwrap();
+    assert!(ottershipper_db::validate_app_name(name).is_ok());
+
+    let apps = db.applications().list().await?;
+    assert_eq!(apps.len(), 1);
+    assert_eq!(apps[0].name, name);
+
+    client.cancel().await?;
+    server_handle.await??;
+
+    Ok(())
+}
+
+/// Test declaratively creating and then updating an application
+#[tokio::test]
+async fn test_mcp_apply_app() -> Result<(), Box<dyn std::error::Error>> {
+    let (db, client, server_handle, _temp_dir) = setup_mcp_test().await?;
+
+    let mut ids = Vec::new();
+    for (description, expect_created) in [("v1", true), ("v2", false)] {
+        let result = client
+            .call_tool(CallToolRequestParam {
+                name: "otter_apply_app".into(),
+                arguments: serde_json::json!({
+                    "name": "declared-app",
+                    "description": description
+                })
+                .as_object()
+                .cloned(),
+            })
+            .await?;
+        let response: serde_json::Value =
+            serde_json::from_str(&result.content[0].as_text().unwrap().text)?;
+        assert_eq!(response["success"], true);
+        assert_eq!(response["created"], expect_created);
+        assert_eq!(response["application"]["description"], description);
+        ids.push(response["application"]["id"].as_str().unwrap().to_string());
+    }
+    assert_eq!(ids[0], ids[1]);
+
+    let app = db.applications().get_by_name_or_err("declared-app").await?;
+    assert_eq!(app.description.as_deref(), Some("v2"));
+
+    client.cancel().await?;
+    server_handle.await??;
+
+    Ok(())
+}
+
+/// Test looking up a single application by ID or name
+#[tokio::test]
+async fn test_mcp_get_app() -> Result<(), Box<dyn std::error::Error>> {
+    let (db, client, server_handle, _temp_dir) = setup_mcp_test().await?;
+
+    let app = db.applications().create("lookup-app").await?;
+
+    for arguments in [
+        serde_json::json!({ "id": app.id }),
+        serde_json::json!({ "name": "lookup-app" }),
+    ] {
+        let result = client
+            .call_tool(CallToolRequestParam {
+                name: "otter_get_app".into(),
+                arguments: arguments.as_object().cloned(),
+            })
+            .await?;
+        let response: serde_json::Value =
+            serde_json::from_str(&result.content[0].as_text().unwrap().text)?;
+        assert_eq!(response["success"], true);
+        assert_eq!(response["application"]["id"], app.id.as_str());
+        assert_eq!(response["application"]["name"], "lookup-app");
+    }
+
+    let result = client
+        .call_tool(CallToolRequestParam {
+            name: "otter_get_app".into(),
+            arguments: serde_json::json!({ "name": "missing-app" })
+                .as_object()
+                .cloned(),
+        })
+        .await;
+    let err = result.unwrap_err().to_string();
+    assert!(err.contains("missing-app"), "unexpected error: {err}");
+
+    client.cancel().await?;
+    server_handle.await??;
+
+    Ok(())
+}
+
+/// Test creating and getting applications for an owner through MCP
+#[tokio::test]
+async fn test_mcp_owner() -> Result<(), Box<dyn std::error::Error>> {
+    let (db, client, server_handle, _temp_dir) = setup_mcp_test().await?;
+    assert!(db.scope_names_by_owner().await?);
+
+    let call = |name: &'static str, arguments: serde_json::Value| {
+        client.call_tool(CallToolRequestParam {
+            name: name.into(),
+            arguments: arguments.as_object().cloned(),
+        })
+    };
+
+    let mut ids = Vec::new();
+    for owner in ["team-a", "team-b"] {
+        let result = call(
+            "otter_create_app",
+            serde_json::json!({ "name": "api", "owner": owner }),
+        )
+        .await?;
+        let response: serde_json::Value =
+            serde_json::from_str(&result.content[0].as_text().unwrap().text)?;
+        assert_eq!(response["application"]["owner"], owner);
+        ids.push(response["application"]["id"].clone());
+    }
+
+    let result = call(
+        "otter_get_app",
+        serde_json::json!({ "name": "api", "owner": "team-b" }),
+    )
+    .await?;
+    let response: serde_json::Value =
+        serde_json::from_str(&result.content[0].as_text().unwrap().text)?;
+    assert_eq!(response["application"]["id"], ids[1]);
+
+    let err = call("otter_get_app", serde_json::json!({ "name": "api" }))
+        .await
+        .unwrap_err()
+        .to_string();
+    assert!(
+        err.contains("No application found"),
+        "unexpected error: {err}"
+    );
+
+    // Every tool finding an application by name looks among the owner's
+    let response = |result: CallToolResult| -> serde_json::Value {
+        serde_json::from_str(&result.content[0].as_text().unwrap().text).unwrap()
+    };
+    for (tool, owner, id) in [
+        ("otter_describe_app", "team-a", &ids[0]),
+        ("otter_touch_app", "team-b", &ids[1]),
+        ("otter_archive_app", "team-a", &ids[0]),
+        ("otter_unarchive_app", "team-a", &ids[0]),
+    ] {
+        let result = call(tool, serde_json::json!({ "name": "api", "owner": owner })).await?;
+        assert_eq!(response(result)["application"]["id"], *id, "{tool}");
+    }
+
+    let result = call(
+        "otter_rename_app",
+        serde_json::json!({ "current_name": "api", "new_name": "api-v2", "owner": "team-a" }),
+    )
+    .await?;
+    assert_eq!(response(result)["application"]["id"], ids[0]);
+
+    // Both owners have a name starting with "api"; only team-b's is renamed
+    let result = call(
+        "otter_rename_prefix",
+        serde_json::json!({ "old_prefix": "api", "new_prefix": "svc-api", "owner": "team-b" }),
+    )
+    .await?;
+    assert_eq!(
+        response(result)["renamed"],
+        serde_json::json!([{ "old": "api", "new": "svc-api" }])
+    );
+
+    let result = call(
+        "otter_delete_app",
+        serde_json::json!({ "name": "svc-api", "owner": "team-b" }),
+    )
+    .await?;
+    assert_eq!(response(result)["deleted"], true);
+    assert!(db
+        .applications()
+        .get(ids[1].as_str().unwrap())
+        .await?
+        .is_none());
+    let kept = db.applications().get(ids[0].as_str().unwrap()).await?;
+    assert_eq!(kept.map(|app| app.name).as_deref(), Some("api-v2"));
+
+    client.cancel().await?;
+    server_handle.await??;
+
+    Ok(())
+}
+
+/// Test cloning an application through MCP
+#[tokio::test]
+async fn test_mcp_clone_app() -> Result<(), Box<dyn std::error::Error>> {
+    let (db, client, server_handle, _temp_dir) = setup_mcp_test().await?;
+    let source = db
+        .applications()
+        .create_with_description("template", Some("base"))
+        .await?;
+
+    let clone = |source_id: &str, name: &str| {
+        client.call_tool(CallToolRequestParam {
+            name: "otter_clone_app".into(),
+            arguments: serde_json::json!({ "source_id": source_id, "name": name })
+                .as_object()
+                .cloned(),
+        })
+    };
+
+    let result = clone(&source.id, "copy").await?;
+    let response: serde_json::Value =
+        serde_json::from_str(&result.content[0].as_text().unwrap().text)?;
+    assert_eq!(response["application"]["name"], "copy");
+    assert_eq!(response["application"]["description"], "base");
+    assert_ne!(response["application"]["id"], source.id.as_str());
+
+    // Archived templates clone into active applications
+    db.applications()
+        .set_status(&source.id, AppStatus::Archived)
+        .await?;
+    let result = clone(&source.id, "from-archived").await?;
+    let response: serde_json::Value =
+        serde_json::from_str(&result.content[0].as_text().unwrap().text)?;
+    assert_eq!(response["application"]["status"], "active");
+
+    let err = clone("missing", "orphan").await.unwrap_err().to_string();
+    assert!(err.contains("missing"), "unexpected error: {err}");
+
+    let err = clone(&source.id, "copy").await.unwrap_err().to_string();
+    assert!(err.contains("already exists"), "unexpected error: {err}");
+    assert_eq!(db.applications().count().await?, 3);
+
+    client.cancel().await?;
+    server_handle.await??;
+
+    Ok(())
+}
+
+/// Test cursor pagination through the list tool
+#[tokio::test]
+async fn test_mcp_list_apps_cursor() -> Result<(), Box<dyn std::error::Error>> {
+    let (db, client, server_handle, _temp_dir) = setup_mcp_test().await?;
+
+    for i in 0..5 {
+        db.applications().create(&format!("paged-{i}")).await?;
+    }
+
+    let mut names = Vec::new();
+    let mut cursor = String::new();
+    loop {
+        let result = client
+            .call_tool(CallToolRequestParam {
+                name: "otter_list_apps".into(),
+                arguments: serde_json::json!({ "cursor": cursor, "limit": 2 })
+                    .as_object()
+                    .cloned(),
+            })
+            .await?;
+        let response: serde_json::Value =
+            serde_json::from_str(&result.content[0].as_text().unwrap().text)?;
+        for app in response["applications"].as_array().unwrap() {
+            names.push(app["name"].as_str().unwrap().to_string());
+        }
+        match response["next_cursor"].as_str() {
+            Some(next) => cursor = next.to_string(),
+            None => break,
+        }
+    }
+    names.sort();
+    assert_eq!(
+        names,
+        ["paged-0", "paged-1", "paged-2", "paged-3", "paged-4"]
+    );
+
+    // Malformed cursors and cursor+offset are rejected
+    for arguments in [
+        serde_json::json!({ "cursor": "not-a-cursor" }),
+        serde_json::json!({ "cursor": "", "offset": 1 }),
+    ] {
+        let result = client
+            .call_tool(CallToolRequestParam {
+                name: "otter_list_apps".into(),
+                arguments: arguments.as_object().cloned(),
+            })
+            .await;
+        assert!(result.is_err());
+    }
+
+    client.cancel().await?;
+    server_handle.await??;
+
+    Ok(())
+}
+
+/// Test that every tool reports an application in exactly the same shape
+#[tokio::test]
+async fn test_mcp_application_json_is_consistent() -> Result<(), Box<dyn std::error::Error>> {
+    let (_db, client, server_handle, _temp_dir) = setup_mcp_test().await?;
+
+    let call = |name: &'static str, arguments: serde_json::Value| {
+        client.call_tool(CallToolRequestParam {
+            name: name.into(),
+            arguments: arguments.as_object().cloned(),
+        })
+    };
+    let parse = |result: CallToolResult| -> Result<serde_json::Value, serde_json::Error> {
+        serde_json::from_str(&result.content[0].as_text().unwrap().text)
+    };
+
+    let created = parse(
+        call(
+            "otter_create_app",
+            serde_json::json!({ "name": "same-shape" }),
+        )
+        .await?,
+    )?;
+    let fetched = parse(call("otter_get_app", serde_json::json!({ "name": "same-shape" })).await?)?;
+    let listed = parse(call("otter_list_apps", serde_json::json!({})).await?)?;
+
+    let created_json = serde_json::to_string(&created["application"])?;
+    assert_eq!(
+        created_json,
+        serde_json::to_string(&fetched["application"])?
+    );
+    assert_eq!(
+        created_json,
+        serde_json::to_string(&listed["applications"][0])?
+    );
+
+    client.cancel().await?;
+    server_handle.await??;
+
+    Ok(())
+}
+
 /// Test end-to-end MCP tool call: list applications
 /// This tests the full stack: MCP protocol → service layer → database
 #[tokio::test]
 async fn test_mcp_list_apps_e2e() -> Result<(), Box<dyn std::error::Error>> {
-    let (_db, client, server_handle) = setup_mcp_test().await?;
+    let (_db, client, server_handle, _temp_dir) = setup_mcp_test().await?;
 
     // Create test applications via MCP tool (better end-to-end testing)
     for name in ["app-one", "app-two", "app-three"] {
@@ -135,9 +590,66 @@ async fn test_mcp_list_apps_e2e() -> Result<(), Box<dyn std::error::Error>> {
     for app in apps {
         assert!(app["id"].is_string());
         assert!(app["name"].is_string());
-        assert!(app["created_at"].is_number());
+        let created_at = app["created_at"].as_str().unwrap();
+        assert!(chrono::DateTime::parse_from_rfc3339(created_at).is_ok());
+    }
+
+    client.cancel().await?;
+    server_handle.await??;
+
+    Ok(())
+}
+
+/// Test paginating through applications reports correct `has_more` flags
+#[tokio::test]
+async fn test_mcp_list_apps_pagination() -> Result<(), Box<dyn std::error::Error>> {
+    let (db, client, server_handle, _temp_dir) = setup_mcp_test().await?;
+
+    for i in 0..5 {
+        db.applications().create(&format!("page-app-{i}")).await?;
     }
 
+    let mut seen = Vec::new();
+    for (offset, expected_len, expected_more) in [(0, 2, true), (2, 2, true), (4, 1, false)] {
+        let result = client
+            .call_tool(CallToolRequestParam {
+                name: "otter_list_apps".into(),
+                arguments: serde_json::json!({ "limit": 2, "offset": offset })
+                    .as_object()
+                    .cloned(),
+            })
+            .await?;
+        let response: serde_json::Value =
+            serde_json::from_str(&result.content[0].as_text().unwrap().text)?;
+
+        assert_eq!(response["total"], 5);
+        assert_eq!(response["limit"], 2);
+        assert_eq!(response["offset"], offset);
+        assert_eq!(response["has_more"], expected_more);
+
+        let apps = response["applications"].as_array().unwrap();
+        assert_eq!(apps.len(), expected_len);
+        seen.extend(apps.iter().map(|a| a["id"].as_str().unwrap().to_string()));
+    }
+
+    // Every application appears exactly once across pages
+    seen.sort();
+    seen.dedup();
+    assert_eq!(seen.len(), 5);
+
+    // Without a limit everything is returned on one page
+    let result = client
+        .call_tool(CallToolRequestParam {
+            name: "otter_list_apps".into(),
+            arguments: None,
+        })
+        .await?;
+    let response: serde_json::Value =
+        serde_json::from_str(&result.content[0].as_text().unwrap().text)?;
+    assert_eq!(response["count"], 5);
+    assert_eq!(response["has_more"], false);
+    assert!(response["limit"].is_null());
+
     client.cancel().await?;
     server_handle.await??;
 
@@ -147,7 +659,7 @@ async fn test_mcp_list_apps_e2e() -> Result<(), Box<dyn std::error::Error>> {
 /// Test listing applications when no apps exist
 #[tokio::test]
 async fn test_mcp_list_apps_empty() -> Result<(), Box<dyn std::error::Error>> {
-    let (_db, client, server_handle) = setup_mcp_test().await?;
+    let (_db, client, server_handle, _temp_dir) = setup_mcp_test().await?;
 
     // Call otter_list_apps tool on empty database
     let result = client
@@ -174,3 +686,2027 @@ async fn test_mcp_list_apps_empty() -> Result<(), Box<dyn std::error::Error>> {
 
     Ok(())
 }
+
+/// Test listing the most recent applications with a limit
+#[tokio::test]
+async fn test_mcp_recent_apps() -> Result<(), Box<dyn std::error::Error>> {
+    let (_db, client, server_handle, _temp_dir) = setup_mcp_test().await?;
+
+    for name in ["app-one", "app-two", "app-three"] {
+        client
+            .call_tool(CallToolRequestParam {
+                name: "otter_create_app".into(),
+                arguments: serde_json::json!({ "name": name }).as_object().cloned(),
+            })
+            .await?;
+        tokio::time::sleep(std::time::Duration::from_millis(5)).await;
+    }
+
+    // Call otter_recent_apps with an explicit limit
+    let result = client
+        .call_tool(CallToolRequestParam {
+            name: "otter_recent_apps".into(),
+            arguments: serde_json::json!({ "limit": 2 }).as_object().cloned(),
+        })
+        .await?;
+
+    let response_text = result.content[0].as_text().unwrap();
+    let response: serde_json::Value = serde_json::from_str(&response_text.text)?;
+    assert_eq!(response["success"], true);
+    assert_eq!(response["count"], 2);
+
+    let apps = response["applications"].as_array().unwrap();
+    assert_eq!(apps[0]["name"], "app-three");
+    assert_eq!(apps[1]["name"], "app-two");
+
+    // Default limit covers all three
+    let result = client
+        .call_tool(CallToolRequestParam {
+            name: "otter_recent_apps".into(),
+            arguments: None,
+        })
+        .await?;
+    let response: serde_json::Value =
+        serde_json::from_str(&result.content[0].as_text().unwrap().text)?;
+    assert_eq!(response["count"], 3);
+
+    client.cancel().await?;
+    server_handle.await??;
+
+    Ok(())
+}
+
+/// Test time-window listing with ISO-8601 and epoch inputs
+#[tokio::test]
+async fn test_mcp_apps_created_between() -> Result<(), Box<dyn std::error::Error>> {
+    let (db, client, server_handle, _temp_dir) = setup_mcp_test().await?;
+
+    let app = db.applications().create("windowed").await?;
+
+    // Epoch millis bounds matching the creation instant exactly
+    let result = client
+        .call_tool(CallToolRequestParam {
+            name: "otter_apps_created_between".into(),
+            arguments: serde_json::json!({ "start": app.created_at, "end": app.created_at })
+                .as_object()
+                .cloned(),
+        })
+        .await?;
+    let response: serde_json::Value =
+        serde_json::from_str(&result.content[0].as_text().unwrap().text)?;
+    assert_eq!(response["count"], 1);
+    assert_eq!(response["applications"][0]["name"], "windowed");
+
+    // Epoch millis passed as strings are accepted too
+    let result = client
+        .call_tool(CallToolRequestParam {
+            name: "otter_apps_created_between".into(),
+            arguments: serde_json::json!({
+                "start": "1706702400000",
+                "end": app.created_at.to_string()
+            })
+            .as_object()
+            .cloned(),
+        })
+        .await?;
+    let response: serde_json::Value =
+        serde_json::from_str(&result.content[0].as_text().unwrap().text)?;
+    assert_eq!(response["count"], 1);
+    assert_eq!(response["start"], 1_706_702_400_000_i64);
+
+    // ISO-8601 bounds well in the past match nothing
+    let result = client
+        .call_tool(CallToolRequestParam {
+            name: "otter_apps_created_between".into(),
+            arguments: serde_json::json!({
+                "start": "2000-01-01T00:00:00Z",
+                "end": "2000-12-31T23:59:59Z"
+            })
+            .as_object()
+            .cloned(),
+        })
+        .await?;
+    let response: serde_json::Value =
+        serde_json::from_str(&result.content[0].as_text().unwrap().text)?;
+    assert_eq!(response["count"], 0);
+    assert_eq!(response["start"], 946_684_800_000_i64);
+
+    // Inverted range is an error
+    let result = client
+        .call_tool(CallToolRequestParam {
+            name: "otter_apps_created_between".into(),
+            arguments: serde_json::json!({
+                "start": "2001-01-01T00:00:00Z",
+                "end": "2000-01-01T00:00:00Z"
+            })
+            .as_object()
+            .cloned(),
+        })
+        .await;
+    assert!(result.is_err());
+
+    client.cancel().await?;
+    server_handle.await??;
+
+    Ok(())
+}
+
+/// Test bucketing of applications into the recent-activity windows
+#[tokio::test]
+async fn test_mcp_recent_activity() -> Result<(), Box<dyn std::error::Error>> {
+    const HOUR_MS: i64 = 60 * 60 * 1000;
+    let (db, client, server_handle, _temp_dir) = setup_mcp_test().await?;
+
+    let now = chrono::Utc::now().timestamp_millis();
+    for (name, age_ms) in [
+        ("made-today", 2 * HOUR_MS),
+        ("made-this-week", 3 * 24 * HOUR_MS),
+        ("made-this-month", 20 * 24 * HOUR_MS),
+        ("made-long-ago", 60 * 24 * HOUR_MS),
+    ] {
+        let app = db.applications().create(name).await?;
+        db.applications()
+            .set_created_at(&app.id, now - age_ms)
+            .await?;
+    }
+
+    let result = client
+        .call_tool(CallToolRequestParam {
+            name: "otter_recent_activity".into(),
+            arguments: None,
+        })
+        .await?;
+    let response: serde_json::Value =
+        serde_json::from_str(&result.content[0].as_text().unwrap().text)?;
+    assert_eq!(response["success"], true);
+
+    let names = |window: &str| -> Vec<String> {
+        response[window]["apps"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|app| app["name"].as_str().unwrap().to_string())
+            .collect()
+    };
+    assert_eq!(response["last_24h"]["count"], 1);
+    assert_eq!(names("last_24h"), ["made-today"]);
+    assert_eq!(response["last_7d"]["count"], 2);
+    assert_eq!(names("last_7d"), ["made-today", "made-this-week"]);
+    assert_eq!(response["last_30d"]["count"], 3);
+    assert_eq!(
+        names("last_30d"),
+        ["made-today", "made-this-week", "made-this-month"]
+    );
+
+    client.cancel().await?;
+    server_handle.await??;
+
+    Ok(())
+}
+
+/// Test deleting a single application by name and by ID
+#[tokio::test]
+async fn test_mcp_delete_app() -> Result<(), Box<dyn std::error::Error>> {
+    let (db, client, server_handle, _temp_dir) = setup_mcp_test().await?;
+
+    let by_id = db.applications().create("delete-by-id").await?;
+    db.applications().create("delete-by-name").await?;
+
+    for args in [
+        serde_json::json!({ "name": "delete-by-name" }),
+        serde_json::json!({ "id": by_id.id }),
+    ] {
+        let result = client
+            .call_tool(CallToolRequestParam {
+                name: "otter_delete_app".into(),
+                arguments: args.as_object().cloned(),
+            })
+            .await?;
+        let response: serde_json::Value =
+            serde_json::from_str(&result.content[0].as_text().unwrap().text)?;
+        assert_eq!(response["deleted"], true);
+    }
+    assert!(db.applications().list().await?.is_empty());
+
+    // Deleting a missing name succeeds but reports nothing deleted
+    let result = client
+        .call_tool(CallToolRequestParam {
+            name: "otter_delete_app".into(),
+            arguments: serde_json::json!({ "name": "delete-by-name" })
+                .as_object()
+                .cloned(),
+        })
+        .await?;
+    let response: serde_json::Value =
+        serde_json::from_str(&result.content[0].as_text().unwrap().text)?;
+    assert_eq!(response["deleted"], false);
+
+    // Neither id nor name is rejected
+    let result = client
+        .call_tool(CallToolRequestParam {
+            name: "otter_delete_app".into(),
+            arguments: serde_json::json!({}).as_object().cloned(),
+        })
+        .await;
+    assert!(result.is_err());
+
+    client.cancel().await?;
+    server_handle.await??;
+
+    Ok(())
+}
+
+/// Test that deletes need the matching name when confirmation is required
+#[tokio::test]
+async fn test_mcp_delete_app_requires_confirmation() -> Result<(), Box<dyn std::error::Error>> {
+    let tools = ToolsConfig {
+        require_confirmation: true,
+        ..ToolsConfig::default()
+    };
+    let (db, client, server_handle, _temp_dir) =
+        setup_mcp_test_with(|service| McpServer::new(service).with_tools(&tools)).await?;
+    let app = db.applications().create("precious").await?;
+
+    let delete = |args: serde_json::Value| {
+        client.call_tool(CallToolRequestParam {
+            name: "otter_delete_app".into(),
+            arguments: args.as_object().cloned(),
+        })
+    };
+    let invalid_params = |result: Result<CallToolResult, rmcp::ServiceError>| match result {
+        Err(rmcp::ServiceError::McpError(err)) => {
+            err.code == rmcp::model::ErrorCode::INVALID_PARAMS
+        }
+        _ => false,
+    };
+
+    // The ID alone is not enough
+    assert!(invalid_params(
+        delete(serde_json::json!({ "id": app.id })).await
+    ));
+
+    // A mismatched name is refused and nothing is deleted
+    assert!(invalid_params(
+        delete(serde_json::json!({ "id": app.id, "name": "other" })).await
+    ));
+    assert!(db.applications().get(&app.id).await?.is_some());
+
+    // The matching name deletes
+    let result = delete(serde_json::json!({ "id": app.id, "name": "precious" })).await?;
+    let response: serde_json::Value =
+        serde_json::from_str(&result.content[0].as_text().unwrap().text)?;
+    assert_eq!(response["deleted"], true);
+    assert!(db.applications().get(&app.id).await?.is_none());
+
+    // Batch deletes need a name per ID, and one mismatch deletes nothing
+    let first = db.applications().create("first").await?;
+    let second = db.applications().create("second").await?;
+    let delete_many = |args: serde_json::Value| {
+        client.call_tool(CallToolRequestParam {
+            name: "otter_delete_apps".into(),
+            arguments: args.as_object().cloned(),
+        })
+    };
+    for args in [
+        serde_json::json!({ "ids": [first.id, second.id] }),
+        serde_json::json!({ "ids": [first.id, second.id], "names": ["first"] }),
+        serde_json::json!({ "ids": [first.id, second.id], "names": ["first", "other"] }),
+    ] {
+        assert!(invalid_params(delete_many(args).await));
+    }
+    assert_eq!(db.applications().count().await?, 2);
+
+    // A rename between looking the name up and deleting is caught
+    db.applications().rename(&second.id, "renamed").await?;
+    assert!(invalid_params(
+        delete(serde_json::json!({ "id": second.id, "name": "second" })).await
+    ));
+    assert!(db.applications().get(&second.id).await?.is_some());
+
+    let result = delete_many(serde_json::json!({
+        "ids": [first.id, second.id],
+        "names": ["first", "renamed"]
+    }))
+    .await?;
+    let response: serde_json::Value =
+        serde_json::from_str(&result.content[0].as_text().unwrap().text)?;
+    assert_eq!(response["deleted"], 2);
+    assert_eq!(db.applications().count().await?, 0);
+
+    client.cancel().await?;
+    server_handle.await??;
+
+    // Off by default: the ID alone deletes
+    let (db, client, server_handle, _temp_dir) = setup_mcp_test().await?;
+    let app = db.applications().create("casual").await?;
+    let result = client
+        .call_tool(CallToolRequestParam {
+            name: "otter_delete_app".into(),
+            arguments: serde_json::json!({ "id": app.id }).as_object().cloned(),
+        })
+        .await?;
+    let response: serde_json::Value =
+        serde_json::from_str(&result.content[0].as_text().unwrap().text)?;
+    assert_eq!(response["deleted"], true);
+
+    client.cancel().await?;
+    server_handle.await??;
+
+    Ok(())
+}
+
+/// Test that archived applications leave the default listing until unarchived
+#[tokio::test]
+async fn test_mcp_archive_app() -> Result<(), Box<dyn std::error::Error>> {
+    let (db, client, server_handle, _temp_dir) = setup_mcp_test().await?;
+    let app = db.applications().create("shelved").await?;
+    db.applications().create("in-use").await?;
+
+    let call = |name: &'static str, args: serde_json::Value| {
+        client.call_tool(CallToolRequestParam {
+            name: name.into(),
+            arguments: args.as_object().cloned(),
+        })
+    };
+    let listed = |result: CallToolResult| -> Result<Vec<String>, serde_json::Error> {
+        let response: serde_json::Value =
+            serde_json::from_str(&result.content[0].as_text().unwrap().text)?;
+        Ok(response["applications"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|app| app["name"].as_str().unwrap().to_string())
+            .collect())
+    };
+
+    let result = call(
+        "otter_archive_app",
+        serde_json::json!({ "name": "shelved" }),
+    )
+    .await?;
+    let response: serde_json::Value =
+        serde_json::from_str(&result.content[0].as_text().unwrap().text)?;
+    assert_eq!(response["application"]["status"], "archived");
+
+    // Gone from the default listing, back with include_archived
+    let names = listed(call("otter_list_apps", serde_json::json!({})).await?)?;
+    assert_eq!(names, ["in-use"]);
+    let names = listed(
+        call(
+            "otter_list_apps",
+            serde_json::json!({ "include_archived": true }),
+        )
+        .await?,
+    )?;
+    assert_eq!(names.len(), 2);
+    let names = listed(call("otter_list_apps", serde_json::json!({ "cursor": "" })).await?)?;
+    assert_eq!(names, ["in-use"]);
+
+    // Still gettable by ID
+    let result = call("otter_get_app", serde_json::json!({ "id": app.id })).await?;
+    let response: serde_json::Value =
+        serde_json::from_str(&result.content[0].as_text().unwrap().text)?;
+    assert_eq!(response["application"]["name"], "shelved");
+
+    // Back in the default listing after unarchiving
+    call("otter_unarchive_app", serde_json::json!({ "id": app.id })).await?;
+    let names = listed(call("otter_list_apps", serde_json::json!({})).await?)?;
+    assert_eq!(names.len(), 2);
+
+    assert!(call(
+        "otter_archive_app",
+        serde_json::json!({ "name": "missing" })
+    )
+    .await
+    .is_err());
+
+    client.cancel().await?;
+    server_handle.await??;
+
+    Ok(())
+}
+
+/// Test counting applications by status
+#[tokio::test]
+async fn test_mcp_status_summary() -> Result<(), Box<dyn std::error::Error>> {
+    let (db, client, server_handle, _temp_dir) = setup_mcp_test().await?;
+
+    let summary = || async {
+        let result = client
+            .call_tool(CallToolRequestParam {
+                name: "otter_status_summary".into(),
+                arguments: None,
+            })
+            .await?;
+        let response: serde_json::Value =
+            serde_json::from_str(&result.content[0].as_text().unwrap().text)?;
+        Ok::<_, Box<dyn std::error::Error>>(response)
+    };
+
+    assert_eq!(
+        summary().await?,
+        serde_json::json!({ "success": true, "active": 0, "archived": 0 })
+    );
+
+    let archived = db.applications().create("old").await?;
+    db.applications().create("new-a").await?;
+    db.applications().create("new-b").await?;
+    db.applications()
+        .set_status(&archived.id, AppStatus::Archived)
+        .await?;
+    assert_eq!(
+        summary().await?,
+        serde_json::json!({ "success": true, "active": 2, "archived": 1 })
+    );
+
+    client.cancel().await?;
+    server_handle.await??;
+
+    Ok(())
+}
+
+/// Test fetching an application with its metadata and audit trail in one call
+#[tokio::test]
+async fn test_mcp_describe_app() -> Result<(), Box<dyn std::error::Error>> {
+    let (db, client, server_handle, _temp_dir) = setup_mcp_test().await?;
+    let app = db.applications().create("detailed").await?;
+    db.applications()
+        .set_meta(&app.id, "owner", "payments")
+        .await?;
+    db.applications().set_meta(&app.id, "tier", "1").await?;
+    db.applications().rename(&app.id, "detailed-api").await?;
+
+    let describe = |arguments: serde_json::Value| {
+        client.call_tool(CallToolRequestParam {
+            name: "otter_describe_app".into(),
+            arguments: arguments.as_object().cloned(),
+        })
+    };
+
+    let result = describe(serde_json::json!({ "name": "detailed-api" })).await?;
+    let response: serde_json::Value =
+        serde_json::from_str(&result.content[0].as_text().unwrap().text)?;
+    assert_eq!(response["success"], true);
+    assert_eq!(response["application"]["id"], app.id);
+    assert_eq!(response["application"]["name"], "detailed-api");
+    assert_eq!(
+        response["metadata"],
+        serde_json::json!({ "owner": "payments", "tier": "1" })
+    );
+    // There are no tags in this server, so no tags section
+    assert!(response.get("tags").is_none());
+
+    // Newest first: the rename, two metadata updates, then the create
+    let audit = response["recent_audit"].as_array().unwrap();
+    assert_eq!(audit.len(), 4);
+    assert_eq!(audit[0]["action"], "update");
+    assert_eq!(audit[0]["app_name"], "detailed-api");
+    assert_eq!(audit[3]["action"], "create");
+
+    let result = describe(serde_json::json!({ "id": app.id, "audit_limit": 1 })).await?;
+    let response: serde_json::Value =
+        serde_json::from_str(&result.content[0].as_text().unwrap().text)?;
+    assert_eq!(response["recent_audit"].as_array().unwrap().len(), 1);
+
+    let err = describe(serde_json::json!({ "name": "detailed" }))
+        .await
+        .unwrap_err();
+    let rmcp::ServiceError::McpError(err) = err else {
+        panic!("expected a tool error, got {err}");
+    };
+    assert_eq!(err.code, rmcp::model::ErrorCode::RESOURCE_NOT_FOUND);
+
+    client.cancel().await?;
+    server_handle.await??;
+
+    Ok(())
+}
+
+/// Test touching an application moves only its `updated_at`
+#[tokio::test]
+async fn test_mcp_touch_app() -> Result<(), Box<dyn std::error::Error>> {
+    let (db, client, server_handle, _temp_dir) = setup_mcp_test().await?;
+    let app = db.applications().create("pinged").await?;
+    db.applications().set_created_at(&app.id, 0).await?;
+
+    let touch = |arguments: serde_json::Value| {
+        client.call_tool(CallToolRequestParam {
+            name: "otter_touch_app".into(),
+            arguments: arguments.as_object().cloned(),
+        })
+    };
+
+    let result = touch(serde_json::json!({ "name": "pinged" })).await?;
+    let response: serde_json::Value =
+        serde_json::from_str(&result.content[0].as_text().unwrap().text)?;
+    assert_eq!(response["application"]["id"], app.id);
+    assert_eq!(
+        response["application"]["created_at"],
+        "1970-01-01T00:00:00.000Z"
+    );
+    assert_ne!(
+        response["application"]["updated_at"],
+        response["application"]["created_at"]
+    );
+
+    let err = touch(serde_json::json!({ "id": "no-such-id" }))
+        .await
+        .unwrap_err();
+    let rmcp::ServiceError::McpError(err) = err else {
+        panic!("expected a tool error, got {err}");
+    };
+    assert_eq!(err.code, rmcp::model::ErrorCode::RESOURCE_NOT_FOUND);
+
+    client.cancel().await?;
+    server_handle.await??;
+
+    Ok(())
+}
+
+/// Test renaming one application by ID or by its current name
+#[tokio::test]
+async fn test_mcp_rename_app() -> Result<(), Box<dyn std::error::Error>> {
+    let (db, client, server_handle, _temp_dir) = setup_mcp_test().await?;
+    let app = db.applications().create("billing").await?;
+
+    let rename = |arguments: serde_json::Value| {
+        client.call_tool(CallToolRequestParam {
+            name: "otter_rename_app".into(),
+            arguments: arguments.as_object().cloned(),
+        })
+    };
+
+    let result = rename(serde_json::json!({
+        "current_name": "billing",
+        "new_name": "billing-api"
+    }))
+    .await?;
+    let response: serde_json::Value =
+        serde_json::from_str(&result.content[0].as_text().unwrap().text)?;
+    assert_eq!(response["application"]["id"], app.id);
+    assert_eq!(response["application"]["name"], "billing-api");
+    assert!(db.applications().get_by_name("billing").await?.is_none());
+
+    // The old name no longer resolves
+    let err = rename(serde_json::json!({ "current_name": "billing", "new_name": "x-api" }))
+        .await
+        .unwrap_err();
+    let rmcp::ServiceError::McpError(err) = err else {
+        panic!("expected a tool error, got {err}");
+    };
+    assert_eq!(err.code, rmcp::model::ErrorCode::RESOURCE_NOT_FOUND);
+    assert_eq!(err.data.unwrap()["reason"], "not_found");
+
+    // The ID path still works
+    let result = rename(serde_json::json!({ "id": app.id, "new_name": "payments" })).await?;
+    let response: serde_json::Value =
+        serde_json::from_str(&result.content[0].as_text().unwrap().text)?;
+    assert_eq!(response["application"]["name"], "payments");
+
+    let err = rename(serde_json::json!({ "new_name": "nothing" }))
+        .await
+        .unwrap_err();
+    let rmcp::ServiceError::McpError(err) = err else {
+        panic!("expected a tool error, got {err}");
+    };
+    assert_eq!(err.code, rmcp::model::ErrorCode::INVALID_PARAMS);
+
+    client.cancel().await?;
+    server_handle.await??;
+
+    Ok(())
+}
+
+/// Test bulk renaming by prefix
+#[tokio::test]
+async fn test_mcp_rename_prefix() -> Result<(), Box<dyn std::error::Error>> {
+    let (db, client, server_handle, _temp_dir) = setup_mcp_test().await?;
+    db.applications().create("svc-api").await?;
+    db.applications().create("svc-web").await?;
+
+    let rename = |new_prefix: &str| {
+        client.call_tool(CallToolRequestParam {
+            name: "otter_rename_prefix".into(),
+            arguments: serde_json::json!({ "old_prefix": "svc-", "new_prefix": new_prefix })
+                .as_object()
+                .cloned(),
+        })
+    };
+
+    let result = rename("team-a-").await?;
+    let response: serde_json::Value =
+        serde_json::from_str(&result.content[0].as_text().unwrap().text)?;
+    assert_eq!(response["count"], 2);
+    assert_eq!(
+        response["renamed"][0],
+        serde_json::json!({ "old": "svc-api", "new": "team-a-api" })
+    );
+    assert!(db.applications().get_by_name("team-a-web").await?.is_some());
+
+    // A collision renames nothing
+    db.applications().create("svc-x").await?;
+    db.applications().create("svc-y").await?;
+    db.applications().create("new-y").await?;
+    let err = rename("new-").await.unwrap_err();
+    let rmcp::ServiceError::McpError(err) = err else {
+        panic!("expected a tool error, got {err}");
+    };
+    assert_eq!(err.code, rmcp::model::ErrorCode::INVALID_PARAMS);
+    assert_eq!(err.data.unwrap()["reason"], "duplicate_name");
+    assert!(db.applications().get_by_name("svc-x").await?.is_some());
+
+    client.cancel().await?;
+    server_handle.await??;
+
+    Ok(())
+}
+
+/// Test bulk deletion by IDs
+#[tokio::test]
+async fn test_mcp_delete_apps() -> Result<(), Box<dyn std::error::Error>> {
+    let (db, client, server_handle, _temp_dir) = setup_mcp_test().await?;
+
+    let a = db.applications().create("doomed-a").await?;
+    let b = db.applications().create("doomed-b").await?;
+    db.applications().create("survivor").await?;
+
+    let result = client
+        .call_tool(CallToolRequestParam {
+            name: "otter_delete_apps".into(),
+            arguments: serde_json::json!({ "ids": [a.id, b.id, "not-a-real-id"] })
+                .as_object()
+                .cloned(),
+        })
+        .await?;
+    let response: serde_json::Value =
+        serde_json::from_str(&result.content[0].as_text().unwrap().text)?;
+    assert_eq!(response["success"], true);
+    assert_eq!(response["requested"], 3);
+    assert_eq!(response["deleted"], 2);
+
+    let apps = db.applications().list().await?;
+    assert_eq!(apps.len(), 1);
+    assert_eq!(apps[0].name, "survivor");
+
+    client.cancel().await?;
+    server_handle.await??;
+
+    Ok(())
+}
+
+/// Test the admin WAL checkpoint tool
+#[tokio::test]
+async fn test_mcp_checkpoint() -> Result<(), Box<dyn std::error::Error>> {
+    let (db, client, server_handle, _temp_dir) = setup_mcp_test().await?;
+
+    db.applications().create("checkpointed").await?;
+
+    let result = client
+        .call_tool(CallToolRequestParam {
+            name: "otter_checkpoint".into(),
+            arguments: serde_json::json!({ "mode": "truncate" })
+                .as_object()
+                .cloned(),
+        })
+        .await?;
+    let response: serde_json::Value =
+        serde_json::from_str(&result.content[0].as_text().unwrap().text)?;
+    assert_eq!(response["success"], true);
+    assert_eq!(response["wal_enabled"], true);
+    assert_eq!(response["mode"], "truncate");
+    assert_eq!(response["busy"], false);
+    assert!(response["log_frames"].as_i64().unwrap() >= 0);
+
+    client.cancel().await?;
+    server_handle.await??;
+
+    Ok(())
+}
+
+/// Test that creates and deletes through MCP show up in the audit log
+#[tokio::test]
+async fn test_mcp_audit_log() -> Result<(), Box<dyn std::error::Error>> {
+    let (db, client, server_handle, _temp_dir) = setup_mcp_test().await?;
+
+    let app = db.applications().create("audited").await?;
+    db.applications().delete(&app.id).await?;
+    db.applications().create("other").await?;
+
+    // One application's trail, oldest first
+    let result = client
+        .call_tool(CallToolRequestParam {
+            name: "otter_audit_log".into(),
+            arguments: serde_json::json!({ "app_id": app.id }).as_object().cloned(),
+        })
+        .await?;
+    let response: serde_json::Value =
+        serde_json::from_str(&result.content[0].as_text().unwrap().text)?;
+    assert_eq!(response["success"], true);
+    assert_eq!(response["count"], 2);
+    assert_eq!(response["entries"][0]["action"], "create");
+    assert_eq!(response["entries"][1]["action"], "delete");
+    assert_eq!(response["entries"][1]["app_name"], "audited");
+
+    // Recent entries across all applications, newest first
+    let result = client
+        .call_tool(CallToolRequestParam {
+            name: "otter_audit_log".into(),
+            arguments: serde_json::json!({ "limit": 2 }).as_object().cloned(),
+        })
+        .await?;
+    let response: serde_json::Value =
+        serde_json::from_str(&result.content[0].as_text().unwrap().text)?;
+    assert_eq!(response["count"], 2);
+    assert_eq!(response["entries"][0]["app_name"], "other");
+
+    client.cancel().await?;
+    server_handle.await??;
+
+    Ok(())
+}
+
+/// Test that the version advertised during initialization is the crate version
+#[tokio::test]
+async fn test_mcp_advertised_version() -> Result<(), Box<dyn std::error::Error>> {
+    let (_db, client, server_handle, _temp_dir) = setup_mcp_test().await?;
+
+    let info = client
+        .peer_info()
+        .expect("server info after initialization");
+    assert_eq!(info.server_info.name, "ottershipper");
+    assert_eq!(info.server_info.version, env!("CARGO_PKG_VERSION"));
+
+    client.cancel().await?;
+    server_handle.await??;
+
+    Ok(())
+}
+
+/// Test that clients receive usage instructions on initialization
+#[tokio::test]
+async fn test_mcp_instructions() -> Result<(), Box<dyn std::error::Error>> {
+    let (_db, client, server_handle, _temp_dir) = setup_mcp_test().await?;
+
+    let info = client
+        .peer_info()
+        .expect("server info after initialization");
+    let instructions = info.instructions.clone().expect("default instructions");
+    // Every tool is listed, so clients learn about new ones
+    for tool in client.list_all_tools().await? {
+        assert!(
+            instructions.contains(tool.name.as_ref()),
+            "instructions should mention {}",
+            tool.name
+        );
+    }
+    assert!(instructions.contains("dry_run"));
+    assert!(instructions.contains("reserved name"));
+
+    client.cancel().await?;
+    server_handle.await??;
+
+    // Operators can replace the default guidance
+    let (_db, client, server_handle, _temp_dir) = setup_mcp_test_with(|service| {
+        McpServer::new(service).with_instructions("Only create apps for team Otter.")
+    })
+    .await?;
+    let info = client
+        .peer_info()
+        .expect("server info after initialization");
+    assert_eq!(
+        info.instructions.as_deref(),
+        Some("Only create apps for team Otter.")
+    );
+
+    client.cancel().await?;
+    server_handle.await??;
+
+    Ok(())
+}
+
+/// Test loading instructions from a file, falling back to the default
+#[tokio::test]
+async fn test_instructions_file() -> Result<(), Box<dyn std::error::Error>> {
+    let temp_dir = tempdir()?;
+    let db = Database::new(temp_dir.path().join("test.db")).await?;
+    let service = ApplicationService::new(db);
+
+    let path = temp_dir.path().join("instructions.md");
+    std::fs::write(
+        &path,
+        "Ask before deleting anything, ünless told otherwise.",
+    )?;
+    let info = McpServer::new(service.clone())
+        .with_instructions_file(&path)
+        .get_info();
+    assert_eq!(
+        info.instructions.as_deref(),
+        Some("Ask before deleting anything, ünless told otherwise.")
+    );
+
+    let default = McpServer::new(service.clone()).get_info().instructions;
+    assert!(default.is_some());
+
+    // Missing and non-UTF-8 files leave the default in place
+    let missing = McpServer::new(service.clone())
+        .with_instructions_file(&temp_dir.path().join("missing.md"))
+        .get_info();
+    assert_eq!(missing.instructions, default);
+
+    let binary = temp_dir.path().join("binary.md");
+    std::fs::write(&binary, [0xff, 0xfe, 0x00])?;
+    let invalid = McpServer::new(service)
+        .with_instructions_file(&binary)
+        .get_info();
+    assert_eq!(invalid.instructions, default);
+
+    Ok(())
+}
+
+/// Test that the help resource documents every tool
+#[tokio::test]
+async fn test_mcp_help_resource() -> Result<(), Box<dyn std::error::Error>> {
+    let (_db, client, server_handle, _temp_dir) = setup_mcp_test().await?;
+
+    let info = client
+        .peer_info()
+        .expect("server info after initialization");
+    assert!(info.capabilities.resources.is_some());
+
+    let resources = client.list_all_resources().await?;
+    assert_eq!(resources.len(), 1);
+    assert_eq!(resources[0].uri, HELP_URI);
+
+    let read = |uri: &str| {
+        client.read_resource(ReadResourceRequestParam {
+            uri: uri.to_string(),
+        })
+    };
+    let result = read(HELP_URI).await?;
+    let ResourceContents::TextResourceContents {
+        text, mime_type, ..
+    } = &result.contents[0]
+    else {
+        panic!("help should be text");
+    };
+    assert_eq!(mime_type.as_deref(), Some("text/markdown"));
+    for tool in client.list_all_tools().await? {
+        assert!(
+            text.contains(tool.name.as_ref()),
+            "help should mention {}",
+            tool.name
+        );
+    }
+    // The server's own name rules are filled in
+    assert!(text.contains("reserved name"));
+    assert!(!text.contains("{name_rules}"));
+
+    assert!(read("otter://nope").await.is_err());
+
+    client.cancel().await?;
+    server_handle.await??;
+
+    // Operators can replace the built-in help
+    let (_db, client, server_handle, _temp_dir) =
+        setup_mcp_test_with(|service| McpServer::new(service).with_help("# Ask team Otter"))
+            .await?;
+    let result = client
+        .read_resource(ReadResourceRequestParam {
+            uri: HELP_URI.to_string(),
+        })
+        .await?;
+    let ResourceContents::TextResourceContents { text, .. } = &result.contents[0] else {
+        panic!("help should be text");
+    };
+    assert_eq!(text, "# Ask team Otter");
+
+    client.cancel().await?;
+    server_handle.await??;
+
+    Ok(())
+}
+
+/// Test that ping responds without needing any applications
+#[tokio::test]
+async fn test_mcp_ping() -> Result<(), Box<dyn std::error::Error>> {
+    let (db, client, server_handle, _temp_dir) = setup_mcp_test().await?;
+    assert_eq!(db.applications().count().await?, 0);
+
+    let before = chrono::Utc::now().timestamp_millis();
+    let result = client
+        .call_tool(CallToolRequestParam {
+            name: "otter_ping".into(),
+            arguments: None,
+        })
+        .await?;
+    let response: serde_json::Value =
+        serde_json::from_str(&result.content[0].as_text().unwrap().text)?;
+    assert_eq!(response["pong"], true);
+    let server_time = response["server_time"].as_i64().unwrap();
+    assert!(server_time >= before);
+    assert!(server_time <= chrono::Utc::now().timestamp_millis());
+
+    client.cancel().await?;
+    server_handle.await??;
+
+    Ok(())
+}
+
+/// Test that tool descriptors match what clients are offered over MCP
+#[tokio::test]
+async fn test_tool_descriptors() -> Result<(), Box<dyn std::error::Error>> {
+    let temp_dir = tempdir()?;
+    let db = Database::new(temp_dir.path().join("test.db")).await?;
+    let server = McpServer::new(ApplicationService::new(db));
+
+    let descriptors = server.tool_descriptors();
+    let create = descriptors
+        .iter()
+        .find(|tool| tool.name == "otter_create_app")
+        .expect("otter_create_app is registered");
+    assert!(create.description.as_deref().unwrap().contains("Create"));
+    let name_schema = &create.input_schema["properties"]["name"];
+    assert!(name_schema["description"].is_string());
+    let name_type = &name_schema["type"];
+    assert!(
+        name_type == "string"
+            || name_type
+                .as_array()
+                .is_some_and(|t| t.contains(&"string".into())),
+        "unexpected name schema: {name_schema}"
+    );
+
+    // Every tool documents the shared response envelopes
+    assert!(create.output_schema["properties"]["success"].is_object());
+    let error_properties = &create.error_schema["properties"];
+    for field in ["success", "reason", "message"] {
+        assert!(error_properties[field].is_object(), "missing {field}");
+    }
+
+    // Disabled tools are left out, as in tools/list
+    let tools = ToolsConfig {
+        enabled: None,
+        disabled: vec!["otter_create_app".to_string()],
+        ..ToolsConfig::default()
+    };
+    let restricted = server.with_tools(&tools).tool_descriptors();
+    assert_eq!(restricted.len(), descriptors.len() - 1);
+    assert!(!restricted
+        .iter()
+        .any(|tool| tool.name == "otter_create_app"));
+
+    Ok(())
+}
+
+/// Test that the create schema documents the default name rules
+#[tokio::test]
+async fn test_create_app_name_schema_constraints() -> Result<(), Box<dyn std::error::Error>> {
+    let temp_dir = tempdir()?;
+    let db = Database::new(temp_dir.path().join("test.db")).await?;
+    let server = McpServer::new(ApplicationService::new(db));
+
+    let descriptors = server.tool_descriptors();
+    let create = descriptors
+        .iter()
+        .find(|tool| tool.name == "otter_create_app")
+        .expect("otter_create_app is registered");
+    let name_schema = &create.input_schema["properties"]["name"];
+
+    let policy = ottershipper_db::NamePolicy::default();
+    assert_eq!(name_schema["maxLength"], policy.max_len);
+    assert_eq!(name_schema["minLength"], policy.min_len);
+    let pattern = name_schema["pattern"].as_str().unwrap();
+    assert_eq!(pattern, r"^\s*[\p{L}\p{N}][\p{L}\p{N}_-]*\s*$");
+    assert!(name_schema["description"]
+        .as_str()
+        .unwrap()
+        .contains("Unicode-aware"));
+
+    // The pattern accepts exactly the names the server does
+    let regex = regex_automata::meta::Regex::new(pattern)?;
+    for (name, valid) in [
+        ("my-app", true),
+        ("app_2", true),
+        ("Ünïcödé-名前", true),
+        ("  padded  ", true),
+        ("-leading-hyphen", false),
+        ("_leading-underscore", false),
+        ("has space", false),
+        ("dotted.name", false),
+        ("", false),
+    ] {
+        assert_eq!(regex.is_match(name), valid, "pattern on {name:?}");
+        assert_eq!(
+            policy.validate(&policy.normalize(name)).is_ok(),
+            valid,
+            "server on {name:?}"
+        );
+    }
+
+    Ok(())
+}
+
+/// Test that names are validated and normalized without creating anything
+#[tokio::test]
+async fn test_mcp_validate_name() -> Result<(), Box<dyn std::error::Error>> {
+    let (db, client, server_handle, _temp_dir) = setup_mcp_test().await?;
+
+    let validate = |name: &'static str| {
+        client.call_tool(CallToolRequestParam {
+            name: "otter_validate_name".into(),
+            arguments: serde_json::json!({ "name": name }).as_object().cloned(),
+        })
+    };
+
+    let result = validate("my-app").await?;
+    let response: serde_json::Value =
+        serde_json::from_str(&result.content[0].as_text().unwrap().text)?;
+    assert_eq!(response["valid"], true);
+    assert!(response["reason"].is_null());
+    assert_eq!(response["normalized"], "my-app");
+
+    let result = validate("-bad name").await?;
+    let response: serde_json::Value =
+        serde_json::from_str(&result.content[0].as_text().unwrap().text)?;
+    assert_eq!(response["valid"], false);
+    assert!(response["reason"]
+        .as_str()
+        .unwrap()
+        .contains("must start with alphanumeric"));
+
+    let result = validate("  padded-app\n").await?;
+    let response: serde_json::Value =
+        serde_json::from_str(&result.content[0].as_text().unwrap().text)?;
+    assert_eq!(response["valid"], true);
+    assert_eq!(response["normalized"], "padded-app");
+
+    // Nothing was created
+    assert_eq!(db.applications().count().await?, 0);
+
+    client.cancel().await?;
+    server_handle.await??;
+
+    Ok(())
+}
+
+/// Test that applications can be found by name pattern
+#[tokio::test]
+async fn test_mcp_find_apps() -> Result<(), Box<dyn std::error::Error>> {
+    let (db, client, server_handle, _temp_dir) = setup_mcp_test().await?;
+
+    for name in ["app-prod-api", "app-prod-web", "app-staging-web"] {
+        db.applications().create(name).await?;
+    }
+
+    let result = client
+        .call_tool(CallToolRequestParam {
+            name: "otter_find_apps".into(),
+            arguments: serde_json::json!({ "pattern": "app-prod-*" })
+                .as_object()
+                .cloned(),
+        })
+        .await?;
+    let response: serde_json::Value =
+        serde_json::from_str(&result.content[0].as_text().unwrap().text)?;
+    assert_eq!(response["success"], true);
+    assert_eq!(response["count"], 2);
+    assert_eq!(response["pattern"], "app-prod-*");
+    assert_eq!(response["applications"][0]["name"], "app-prod-api");
+    assert_eq!(response["applications"][1]["name"], "app-prod-web");
+
+    client.cancel().await?;
+    server_handle.await??;
+
+    Ok(())
+}
+
+/// Test filtering applications by name length and glob pattern
+#[tokio::test]
+async fn test_mcp_filter_apps() -> Result<(), Box<dyn std::error::Error>> {
+    let (db, client, server_handle, _temp_dir) = setup_mcp_test().await?;
+
+    for name in ["x", "yz", "app-prod-api", "app-prod-web", "app-staging-web"] {
+        db.applications().create(name).await?;
+    }
+
+    let call = |arguments: serde_json::Value| {
+        client.call_tool(CallToolRequestParam {
+            name: "otter_filter_apps".into(),
+            arguments: arguments.as_object().cloned(),
+        })
+    };
+
+    let result = call(serde_json::json!({ "max_length": 2 })).await?;
+    let response: serde_json::Value =
+        serde_json::from_str(&result.content[0].as_text().unwrap().text)?;
+    assert_eq!(response["count"], 2);
+    assert_eq!(response["applications"][0]["name"], "x");
+    assert_eq!(response["applications"][1]["name"], "yz");
+
+    let result = call(serde_json::json!({ "pattern": "app-*-web", "min_length": 13 })).await?;
+    let response: serde_json::Value =
+        serde_json::from_str(&result.content[0].as_text().unwrap().text)?;
+    assert_eq!(response["count"], 1);
+    assert_eq!(response["applications"][0]["name"], "app-staging-web");
+    assert_eq!(response["pattern"], "app-*-web");
+
+    let err = call(serde_json::json!({ "min_length": 5, "max_length": 1 }))
+        .await
+        .unwrap_err();
+    let rmcp::ServiceError::McpError(err) = err else {
+        panic!("expected an MCP error, got {err:?}");
+    };
+    assert_eq!(err.code, rmcp::model::ErrorCode::INVALID_PARAMS);
+
+    client.cancel().await?;
+    server_handle.await??;
+
+    Ok(())
+}
+
+/// Test combining filters, sorting, and pagination in `otter_query_apps`
+#[tokio::test]
+async fn test_mcp_query_apps() -> Result<(), Box<dyn std::error::Error>> {
+    let (db, client, server_handle, _temp_dir) = setup_mcp_test().await?;
+
+    let names = ["prod-api", "prod-web", "staging-api", "prod-db", "dev-api"];
+    for (i, name) in (1_000..).zip(names) {
+        let app = db.applications().create(name).await?;
+        db.applications().set_created_at(&app.id, i).await?;
+    }
+    let db_app = db.applications().get_by_name_or_err("prod-db").await?;
+    db.applications()
+        .set_status(&db_app.id, AppStatus::Archived)
+        .await?;
+
+    let call = |arguments: serde_json::Value| {
+        client.call_tool(CallToolRequestParam {
+            name: "otter_query_apps".into(),
+            arguments: arguments.as_object().cloned(),
+        })
+    };
+    let names_of = |response: &serde_json::Value| -> Vec<String> {
+        response["items"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|app| app["name"].as_str().unwrap().to_string())
+            .collect()
+    };
+
+    // Defaults: every status, newest first
+    let result = call(serde_json::json!({})).await?;
+    let response: serde_json::Value =
+        serde_json::from_str(&result.content[0].as_text().unwrap().text)?;
+    assert_eq!(
+        names_of(&response),
+        ["dev-api", "prod-db", "staging-api", "prod-web", "prod-api"]
+    );
+    assert_eq!(response["total"], 5);
+    assert_eq!(response["limit"], 50);
+    assert_eq!(response["offset"], 0);
+
+    // Filter + sort + page: the total counts every match, not just the page
+    let result = call(serde_json::json!({
+        "name_contains": "prod-",
+        "sort_by": "name",
+        "direction": "asc",
+        "limit": 2
+    }))
+    .await?;
+    let response: serde_json::Value =
+        serde_json::from_str(&result.content[0].as_text().unwrap().text)?;
+    assert_eq!(names_of(&response), ["prod-api", "prod-db"]);
+    assert_eq!(response["total"], 3);
+
+    let result = call(serde_json::json!({
+        "name_contains": "prod-",
+        "sort_by": "name",
+        "direction": "asc",
+        "limit": 2,
+        "offset": 2
+    }))
+    .await?;
+    let response: serde_json::Value =
+        serde_json::from_str(&result.content[0].as_text().unwrap().text)?;
+    assert_eq!(names_of(&response), ["prod-web"]);
+    assert_eq!(response["offset"], 2);
+
+    // Status filter combined with a substring and created_at ascending
+    let result = call(serde_json::json!({
+        "name_contains": "prod",
+        "status": "active",
+        "sort_by": "created_at",
+        "direction": "asc"
+    }))
+    .await?;
+    let response: serde_json::Value =
+        serde_json::from_str(&result.content[0].as_text().unwrap().text)?;
+    assert_eq!(names_of(&response), ["prod-api", "prod-web"]);
+    assert_eq!(response["total"], 2);
+
+    let result = call(serde_json::json!({ "status": "archived" })).await?;
+    let response: serde_json::Value =
+        serde_json::from_str(&result.content[0].as_text().unwrap().text)?;
+    assert_eq!(names_of(&response), ["prod-db"]);
+
+    // Wildcard characters match literally
+    let result = call(serde_json::json!({ "name_contains": "%" })).await?;
+    let response: serde_json::Value =
+        serde_json::from_str(&result.content[0].as_text().unwrap().text)?;
+    assert_eq!(response["total"], 0);
+
+    // Out-of-range paging is clamped rather than rejected
+    let result = call(serde_json::json!({ "limit": 1_000, "offset": -5 })).await?;
+    let response: serde_json::Value =
+        serde_json::from_str(&result.content[0].as_text().unwrap().text)?;
+    assert_eq!(response["limit"], 100);
+    assert_eq!(response["offset"], 0);
+    let result = call(serde_json::json!({ "limit": 0 })).await?;
+    let response: serde_json::Value =
+        serde_json::from_str(&result.content[0].as_text().unwrap().text)?;
+    assert_eq!(response["limit"], 1);
+    assert_eq!(response["items"].as_array().unwrap().len(), 1);
+
+    // Unknown sort columns and overlong search text are rejected
+    for arguments in [
+        serde_json::json!({ "sort_by": "id; DROP TABLE applications" }),
+        serde_json::json!({ "name_contains": "x".repeat(257) }),
+    ] {
+        let err = call(arguments).await.unwrap_err();
+        let rmcp::ServiceError::McpError(err) = err else {
+            panic!("expected an MCP error, got {err:?}");
+        };
+        assert_eq!(err.code, rmcp::model::ErrorCode::INVALID_PARAMS);
+    }
+
+    client.cancel().await?;
+    server_handle.await??;
+
+    Ok(())
+}
+
+/// Test the configured default and maximum page sizes
+#[tokio::test]
+async fn test_mcp_page_sizes() -> Result<(), Box<dyn std::error::Error>> {
+    let (db, client, server_handle, _temp_dir) =
+        setup_mcp_test_with(|service| McpServer::new(service).with_page_sizes(Some(2), Some(3)))
+            .await?;
+    for i in 0..5 {
+        db.applications().create(&format!("paged-{i}")).await?;
+    }
+
+    let call = |name: &'static str, arguments: serde_json::Value| {
+        client.call_tool(CallToolRequestParam {
+            name: name.into(),
+            arguments: arguments.as_object().cloned(),
+        })
+    };
+    let parse = |result: rmcp::model::CallToolResult| -> serde_json::Value {
+        serde_json::from_str(&result.content[0].as_text().unwrap().text).unwrap()
+    };
+
+    // Omitting limit applies the default page size
+    for (tool, arguments, items) in [
+        ("otter_list_apps", serde_json::json!({}), "applications"),
+        (
+            "otter_list_apps",
+            serde_json::json!({ "cursor": "" }),
+            "applications",
+        ),
+        ("otter_query_apps", serde_json::json!({}), "items"),
+    ] {
+        let response = parse(call(tool, arguments).await?);
+        assert_eq!(response[items].as_array().unwrap().len(), 2, "{tool}");
+        assert_eq!(response["limit"], 2, "{tool}");
+        assert_eq!(response["limit_clamped"], false, "{tool}");
+    }
+
+    // Asking for more than the maximum is clamped and flagged
+    for (tool, arguments, items) in [
+        (
+            "otter_list_apps",
+            serde_json::json!({ "limit": 50 }),
+            "applications",
+        ),
+        (
+            "otter_list_apps",
+            serde_json::json!({ "limit": -1 }),
+            "applications",
+        ),
+        (
+            "otter_list_apps",
+            serde_json::json!({ "cursor": "", "limit": 50 }),
+            "applications",
+        ),
+        (
+            "otter_query_apps",
+            serde_json::json!({ "limit": 50 }),
+            "items",
+        ),
+    ] {
+        let response = parse(call(tool, arguments).await?);
+        assert_eq!(response[items].as_array().unwrap().len(), 3, "{tool}");
+        assert_eq!(response["limit"], 3, "{tool}");
+        assert_eq!(response["limit_clamped"], true, "{tool}");
+    }
+
+    // Limits within the maximum are used as given
+    let response = parse(call("otter_list_apps", serde_json::json!({ "limit": 3 })).await?);
+    assert_eq!(response["applications"].as_array().unwrap().len(), 3);
+    assert_eq!(response["limit_clamped"], false);
+
+    client.cancel().await?;
+    server_handle.await??;
+
+    // Without page sizes configured, omitting limit still lists everything
+    let (db, client, server_handle, _temp_dir) = setup_mcp_test().await?;
+    for i in 0..5 {
+        db.applications().create(&format!("paged-{i}")).await?;
+    }
+    let response = parse(
+        client
+            .call_tool(CallToolRequestParam {
+                name: "otter_list_apps".into(),
+                arguments: None,
+            })
+            .await?,
+    );
+    assert_eq!(response["applications"].as_array().unwrap().len(), 5);
+    assert_eq!(response["limit"], serde_json::Value::Null);
+    assert_eq!(response["limit_clamped"], false);
+
+    client.cancel().await?;
+    server_handle.await??;
+
+    Ok(())
+}
+
+/// Test that `otter_reset` is hidden unless the config allows it
+#[tokio::test]
+async fn test_mcp_reset_hidden_by_default() -> Result<(), Box<dyn std::error::Error>> {
+    let (db, client, server_handle, _temp_dir) = setup_mcp_test().await?;
+    db.applications().create("keep").await?;
+
+    let listed = client.list_all_tools().await?;
+    assert!(!listed.iter().any(|tool| tool.name == "otter_reset"));
+    let result = client
+        .call_tool(CallToolRequestParam {
+            name: "otter_reset".into(),
+            arguments: serde_json::json!({ "confirm": "DELETE ALL" })
+                .as_object()
+                .cloned(),
+        })
+        .await;
+    assert!(result.is_err(), "hidden tool should not be callable");
+    assert_eq!(db.applications().count().await?, 1);
+
+    client.cancel().await?;
+    server_handle.await??;
+
+    // Listing it in `enabled` isn't enough either
+    let tools = ToolsConfig {
+        enabled: Some(vec!["otter_reset".to_string()]),
+        ..ToolsConfig::default()
+    };
+    let server = McpServer::new(ApplicationService::new(db.clone())).with_tools(&tools);
+    assert!(server.tool_descriptors().is_empty());
+
+    Ok(())
+}
+
+/// Test that an allowed `otter_reset` refuses without the exact confirmation
+/// and deletes everything with it
+#[tokio::test]
+async fn test_mcp_reset() -> Result<(), Box<dyn std::error::Error>> {
+    let tools = ToolsConfig {
+        allow_reset: true,
+        ..ToolsConfig::default()
+    };
+    let (db, client, server_handle, _temp_dir) =
+        setup_mcp_test_with(|service| McpServer::new(service).with_tools(&tools)).await?;
+    let listed = client.list_all_tools().await?;
+    assert!(listed.iter().any(|tool| tool.name == "otter_reset"));
+    let mut ids = Vec::new();
+    for i in 0..3 {
+        let app = db.applications().create(&format!("reset-{i}")).await?;
+        db.applications().set_meta(&app.id, "owner", "dev").await?;
+        ids.push(app.id);
+    }
+
+    let reset = |arguments: serde_json::Value| {
+        client.call_tool(CallToolRequestParam {
+            name: "otter_reset".into(),
+            arguments: arguments.as_object().cloned(),
+        })
+    };
+
+    for arguments in [
+        serde_json::json!({}),
+        serde_json::json!({ "confirm": "delete all" }),
+        serde_json::json!({ "confirm": "DELETE ALL " }),
+        serde_json::json!({ "confirm": "yes" }),
+    ] {
+        let err = reset(arguments.clone()).await.unwrap_err();
+        let rmcp::ServiceError::McpError(err) = err else {
+            panic!("expected an MCP error for {arguments}, got {err:?}");
+        };
+        assert_eq!(err.code, rmcp::model::ErrorCode::INVALID_PARAMS);
+    }
+    assert_eq!(
+        db.applications().count().await?,
+        3,
+        "refusals delete nothing"
+    );
+
+    let result = reset(serde_json::json!({ "confirm": "DELETE ALL" })).await?;
+    let response: serde_json::Value =
+        serde_json::from_str(&result.content[0].as_text().unwrap().text)?;
+    assert_eq!(response["deleted"], 3);
+    assert_eq!(db.applications().count().await?, 0);
+    for id in &ids {
+        assert!(db.applications().list_meta(id).await?.is_empty());
+        let trail = db.applications().audit_trail(id).await?;
+        assert_eq!(
+            trail.last().map(|entry| entry.action),
+            Some(AuditAction::Delete)
+        );
+    }
+
+    // Resetting an empty catalog is not an error
+    let result = reset(serde_json::json!({ "confirm": "DELETE ALL" })).await?;
+    let response: serde_json::Value =
+        serde_json::from_str(&result.content[0].as_text().unwrap().text)?;
+    assert_eq!(response["deleted"], 0);
+
+    client.cancel().await?;
+    server_handle.await??;
+
+    Ok(())
+}
+
+/// Test finding applications by a metadata value
+#[tokio::test]
+async fn test_mcp_find_by_meta() -> Result<(), Box<dyn std::error::Error>> {
+    let (db, client, server_handle, _temp_dir) = setup_mcp_test().await?;
+    let api = db.applications().create("api").await?;
+    let web = db.applications().create("web").await?;
+    db.applications()
+        .set_meta(&api.id, "repo", "github.com/acme/api")
+        .await?;
+    db.applications()
+        .set_meta(&web.id, "repo", "github.com/acme/web")
+        .await?;
+
+    let result = client
+        .call_tool(CallToolRequestParam {
+            name: "otter_find_by_meta".into(),
+            arguments: serde_json::json!({ "key": "repo", "value": "github.com/acme/web" })
+                .as_object()
+                .cloned(),
+        })
+        .await?;
+    let response: serde_json::Value =
+        serde_json::from_str(&result.content[0].as_text().unwrap().text)?;
+    assert_eq!(response["success"], true);
+    assert_eq!(response["count"], 1);
+    assert_eq!(response["applications"][0]["id"], web.id.as_str());
+    assert_eq!(response["key"], "repo");
+
+    client.cancel().await?;
+    server_handle.await??;
+
+    Ok(())
+}
+
+/// Test that `otter_count_matching` agrees with the equivalent `otter_query_apps` listing
+#[tokio::test]
+async fn test_mcp_count_matching() -> Result<(), Box<dyn std::error::Error>> {
+    let (db, client, server_handle, _temp_dir) = setup_mcp_test().await?;
+    for name in ["prod-api", "prod-web", "staging-api", "prod-db", "dev-api"] {
+        db.applications().create(name).await?;
+    }
+    let db_app = db.applications().get_by_name_or_err("prod-db").await?;
+    db.applications()
+        .set_status(&db_app.id, AppStatus::Archived)
+        .await?;
+
+    let call = |name: &'static str, arguments: serde_json::Value| {
+        client.call_tool(CallToolRequestParam {
+            name: name.into(),
+            arguments: arguments.as_object().cloned(),
+        })
+    };
+    let parse = |result: CallToolResult| -> serde_json::Value {
+        serde_json::from_str(&result.content[0].as_text().unwrap().text).unwrap()
+    };
+
+    for (filters, expected) in [
+        (serde_json::json!({}), 5),
+        (serde_json::json!({ "name_contains": "prod-" }), 3),
+        (
+            serde_json::json!({ "name_contains": "api", "status": "active" }),
+            3,
+        ),
+        (serde_json::json!({ "status": "archived" }), 1),
+        (serde_json::json!({ "name_contains": "%" }), 0),
+    ] {
+        let counted = parse(call("otter_count_matching", filters.clone()).await?);
+        assert_eq!(counted["success"], true);
+        assert_eq!(counted["count"], expected, "{filters}");
+        assert!(counted.get("items").is_none());
+
+        let listed = parse(call("otter_query_apps", filters.clone()).await?);
+        assert_eq!(
+            counted["count"],
+            listed["items"].as_array().unwrap().len(),
+            "{filters}"
+        );
+    }
+
+    let err = call(
+        "otter_count_matching",
+        serde_json::json!({ "name_contains": "x".repeat(257) }),
+    )
+    .await
+    .unwrap_err();
+    let rmcp::ServiceError::McpError(err) = err else {
+        panic!("expected an MCP error, got {err:?}");
+    };
+    assert_eq!(err.code, rmcp::model::ErrorCode::INVALID_PARAMS);
+
+    client.cancel().await?;
+    server_handle.await??;
+
+    Ok(())
+}
+
+/// Test `otter_search` on the full-text index and on the `LIKE` fallback
+#[tokio::test]
+async fn test_mcp_search() -> Result<(), Box<dyn std::error::Error>> {
+    let (db, client, server_handle, _temp_dir) = setup_mcp_test().await?;
+    let web = db
+        .applications()
+        .create_with_description("web", Some("Storefront for payments"))
+        .await?;
+    let api = db.applications().create("payments-api").await?;
+
+    let search = |query: &str| {
+        client.call_tool(CallToolRequestParam {
+            name: "otter_search".into(),
+            arguments: serde_json::json!({ "query": query }).as_object().cloned(),
+        })
+    };
+    let ids_of = |result: CallToolResult| -> Vec<String> {
+        let response: serde_json::Value =
+            serde_json::from_str(&result.content[0].as_text().unwrap().text).unwrap();
+        response["applications"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|app| app["id"].as_str().unwrap().to_string())
+            .collect()
+    };
+
+    // Without the index, words match as substrings ordered by name
+    assert_eq!(
+        ids_of(search("payment").await?),
+        [api.id.clone(), web.id.clone()]
+    );
+
+    // With it, whole words match and name matches rank first
+    assert!(db.enable_full_text_search().await?);
+    assert_eq!(
+        ids_of(search("PAYMENTS").await?),
+        [api.id.clone(), web.id.clone()]
+    );
+    assert!(ids_of(search("payment").await?).is_empty());
+    assert_eq!(ids_of(search("pay*").await?).len(), 2);
+    assert_eq!(ids_of(search("storefront payments").await?), [web.id]);
+
+    // Later writes are indexed too
+    let billing = db.applications().create("billing").await?;
+    assert_eq!(ids_of(search("billing").await?), [billing.id]);
+
+    let err = search("   ").await.unwrap_err();
+    let rmcp::ServiceError::McpError(err) = err else {
+        panic!("expected an MCP error, got {err:?}");
+    };
+    assert_eq!(err.code, rmcp::model::ErrorCode::INVALID_PARAMS);
+
+    client.cancel().await?;
+    server_handle.await??;
+
+    Ok(())
+}
+
+/// Test searching names, descriptions, and metadata in one call
+#[tokio::test]
+async fn test_mcp_search_all() -> Result<(), Box<dyn std::error::Error>> {
+    let (db, client, server_handle, _temp_dir) = setup_mcp_test().await?;
+    let api = db.applications().create("payments-api").await?;
+    let web = db
+        .applications()
+        .create_with_description("web", Some("Payments storefront"))
+        .await?;
+    db.applications()
+        .set_meta(&api.id, "team", "payments")
+        .await?;
+
+    let call = |arguments: serde_json::Value| {
+        client.call_tool(CallToolRequestParam {
+            name: "otter_search_all".into(),
+            arguments: arguments.as_object().cloned(),
+        })
+    };
+
+    let result = call(serde_json::json!({ "query": "payments" })).await?;
+    let response: serde_json::Value =
+        serde_json::from_str(&result.content[0].as_text().unwrap().text)?;
+    assert_eq!(response["success"], true);
+    assert_eq!(response["count"], 2);
+    assert_eq!(response["applications"][0]["id"], api.id.as_str());
+    assert_eq!(response["applications"][1]["id"], web.id.as_str());
+
+    let err = call(serde_json::json!({ "query": " " })).await.unwrap_err();
+    assert!(err.to_string().contains("cannot be empty"), "{err}");
+
+    client.cancel().await?;
+    server_handle.await??;
+
+    Ok(())
+}
+
+/// Test setting, reading, and deleting application metadata
+#[tokio::test]
+async fn test_mcp_metadata() -> Result<(), Box<dyn std::error::Error>> {
+    let (db, client, server_handle, _temp_dir) = setup_mcp_test().await?;
+    let app = db.applications().create("with-meta").await?;
+
+    let call = |name: &'static str, arguments: serde_json::Value| {
+        client.call_tool(CallToolRequestParam {
+            name: name.into(),
+            arguments: arguments.as_object().cloned(),
+        })
+    };
+    let parse = |result: CallToolResult| -> serde_json::Value {
+        serde_json::from_str(&result.content[0].as_text().unwrap().text).unwrap()
+    };
+
+    for (key, value) in [
+        ("owner", "team@example.com"),
+        ("repo_url", "https://example.com/repo"),
+    ] {
+        let result = call(
+            "otter_set_metadata",
+            serde_json::json!({ "id": app.id, "key": key, "value": value }),
+        )
+        .await?;
+        assert_eq!(parse(result)["success"], true);
+    }
+
+    let response = parse(
+        call(
+            "otter_get_metadata",
+            serde_json::json!({ "id": app.id, "key": "owner" }),
+        )
+        .await?,
+    );
+    assert_eq!(response["value"], "team@example.com");
+
+    let response = parse(call("otter_get_metadata", serde_json::json!({ "id": app.id })).await?);
+    assert_eq!(
+        response["metadata"],
+        serde_json::json!({ "owner": "team@example.com", "repo_url": "https://example.com/repo" })
+    );
+
+    let response = parse(
+        call(
+            "otter_delete_metadata",
+            serde_json::json!({ "id": app.id, "key": "owner" }),
+        )
+        .await?,
+    );
+    assert_eq!(response["deleted"], true);
+
+    // Bad keys and unknown applications are rejected
+    let err = call(
+        "otter_set_metadata",
+        serde_json::json!({ "id": app.id, "key": "not a key", "value": "x" }),
+    )
+    .await
+    .unwrap_err()
+    .to_string();
+    assert!(err.contains("metadata key"), "unexpected error: {err}");
+    let err = call("otter_get_metadata", serde_json::json!({ "id": "missing" }))
+        .await
+        .unwrap_err()
+        .to_string();
+    assert!(
+        err.contains("No application found"),
+        "unexpected error: {err}"
+    );
+
+    client.cancel().await?;
+    server_handle.await??;
+
+    Ok(())
+}
+
+/// Test that a duplicate create fails with the documented error envelope
+#[tokio::test]
+async fn test_mcp_duplicate_create_error_envelope() -> Result<(), Box<dyn std::error::Error>> {
+    let (db, client, server_handle, _temp_dir) = setup_mcp_test().await?;
+    let taken = db.applications().create("taken").await?;
+
+    let err = client
+        .call_tool(CallToolRequestParam {
+            name: "otter_create_app".into(),
+            arguments: serde_json::json!({ "name": "taken" }).as_object().cloned(),
+        })
+        .await
+        .unwrap_err();
+    let rmcp::ServiceError::McpError(err) = err else {
+        panic!("expected a tool error, got {err}");
+    };
+
+    assert_eq!(err.code, rmcp::model::ErrorCode::INVALID_PARAMS);
+    let envelope: ErrorEnvelope = serde_json::from_value(err.data.expect("error data"))?;
+    assert_eq!(
+        envelope,
+        ErrorEnvelope {
+            success: false,
+            reason: ErrorReason::DuplicateName,
+            message: "Failed to create application: Name 'taken' already exists".to_string(),
+            retry_after_secs: None,
+            retry_after_ms: None,
+            existing: Some(ExistingApplication {
+                id: taken.id.clone(),
+                name: "taken".to_string(),
+                created_at: ApplicationView::from(&taken).created_at,
+            }),
+        }
+    );
+
+    client.cancel().await?;
+    server_handle.await??;
+
+    Ok(())
+}
+
+/// Test that a duplicate create reports the existing application, so agents can recover in one call
+#[tokio::test]
+async fn test_mcp_duplicate_create_returns_existing() -> Result<(), Box<dyn std::error::Error>> {
+    let (_db, client, server_handle, _temp_dir) = setup_mcp_test().await?;
+
+    let create = || CallToolRequestParam {
+        name: "otter_create_app".into(),
+        arguments: serde_json::json!({ "name": "original" })
+            .as_object()
+            .cloned(),
+    };
+
+    let result = client.call_tool(create()).await?;
+    let response: serde_json::Value =
+        serde_json::from_str(&result.content[0].as_text().unwrap().text)?;
+    let original_id = response["application"]["id"].as_str().unwrap().to_string();
+
+    let err = client.call_tool(create()).await.unwrap_err();
+    let rmcp::ServiceError::McpError(err) = err else {
+        panic!("expected a tool error, got {err}");
+    };
+    let data = err.data.expect("error data");
+    assert_eq!(data["success"], false);
+    assert_eq!(data["reason"], "duplicate_name");
+    assert_eq!(data["existing"]["id"], original_id.as_str());
+    assert_eq!(data["existing"]["name"], "original");
+    assert!(data["existing"]["created_at"].is_string());
+
+    client.cancel().await?;
+    server_handle.await??;
+
+    Ok(())
+}
+
+/// Test that an exhausted connection pool is reported as busy with a retry hint
+#[tokio::test]
+async fn test_mcp_busy_when_pool_exhausted() -> Result<(), Box<dyn std::error::Error>> {
+    let temp_dir = tempdir()?;
+    let config = DatabaseConfig {
+        max_connections: 1,
+        acquire_timeout: std::time::Duration::from_millis(100),
+        ..Default::default()
+    };
+    let db = Database::new_with_config(temp_dir.path().join("busy.db"), config).await?;
+    db.migrate().await?;
+
+    let (_db, client, server_handle, _temp_dir) = setup_mcp_test_with({
+        let db = db.clone();
+        move |_| McpServer::new(ApplicationService::new(db))
+    })
+    .await?;
+    let list = || CallToolRequestParam {
+        name: "otter_list_apps".into(),
+        arguments: None,
+    };
+
+    // Hold the only connection so the call can't get one in time
+    let tx = db.begin().await?;
+    let err = client.call_tool(list()).await.unwrap_err();
+    let rmcp::ServiceError::McpError(err) = err else {
+        panic!("expected a tool error, got {err}");
+    };
+    let envelope: ErrorEnvelope = serde_json::from_value(err.data.expect("error data"))?;
+    assert_eq!(envelope.reason, ErrorReason::Busy);
+    assert!(envelope.retry_after_ms.is_some_and(|ms| ms > 0));
+
+    // Once the connection is free again the same call succeeds, retrying as
+    // advised if a loaded test machine makes it miss the short timeout
+    tx.rollback().await?;
+    let mut attempts = 0;
+    while let Err(err) = client.call_tool(list()).await {
+        attempts += 1;
+        assert!(
+            attempts < 10,
+            "still failing after {attempts} retries: {err}"
+        );
+        tokio::time::sleep(std::time::Duration::from_millis(
+            envelope.retry_after_ms.unwrap_or_default(),
+        ))
+        .await;
+    }
+
+    client.cancel().await?;
+    server_handle.await??;
+
+    Ok(())
+}
+
+/// Test that server info reports version, transport, and database state
+#[tokio::test]
+async fn test_mcp_server_info() -> Result<(), Box<dyn std::error::Error>> {
+    let (db, client, server_handle, _temp_dir) =
+        setup_mcp_test_with(|service| McpServer::new(service).with_transport("stdio")).await?;
+
+    db.applications().create("counted").await?;
+
+    let result = client
+        .call_tool(CallToolRequestParam {
+            name: "otter_server_info".into(),
+            arguments: None,
+        })
+        .await?;
+    let response: serde_json::Value =
+        serde_json::from_str(&result.content[0].as_text().unwrap().text)?;
+    assert_eq!(response["success"], true);
+    assert_eq!(response["name"], "ottershipper");
+    assert_eq!(response["version"], env!("CARGO_PKG_VERSION"));
+    assert!(!response["protocol_version"].as_str().unwrap().is_empty());
+    assert_eq!(response["transport"], "stdio");
+    assert!(response["migration_count"].as_i64().unwrap() > 0);
+    assert_eq!(
+        response["schema_version"],
+        ottershipper_db::Database::latest_schema_version()
+    );
+    assert_eq!(
+        response["schema_version"],
+        response["latest_schema_version"]
+    );
+    assert_eq!(response["app_count"], 1);
+    assert!(response["tools"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .any(|tool| tool == "otter_server_info"));
+
+    client.cancel().await?;
+    server_handle.await??;
+
+    Ok(())
+}
+
+/// Test that diagnostics report disk usage, pool, and migrations
+#[tokio::test]
+async fn test_mcp_diagnostics() -> Result<(), Box<dyn std::error::Error>> {
+    let (db, client, server_handle, _temp_dir) = setup_mcp_test().await?;
+
+    db.applications().create("diagnosed").await?;
+
+    let result = client
+        .call_tool(CallToolRequestParam {
+            name: "otter_diagnostics".into(),
+            arguments: None,
+        })
+        .await?;
+    let response: serde_json::Value =
+        serde_json::from_str(&result.content[0].as_text().unwrap().text)?;
+    assert_eq!(response["success"], true);
+    assert!(response["disk_usage"]["main_bytes"].as_u64().unwrap() > 0);
+    assert!(response["disk_usage"]["path"].is_string());
+    assert!(response["pool"]["max_connections"].as_u64().unwrap() > 0);
+    assert!(!response["migrations"].as_array().unwrap().is_empty());
+    assert_eq!(response["pending_migrations"], 0);
+
+    client.cancel().await?;
+    server_handle.await??;
+
+    Ok(())
+}
+
+/// Test that disabled tools are neither listed nor callable
+#[tokio::test]
+async fn test_mcp_disabled_tools() -> Result<(), Box<dyn std::error::Error>> {
+    let tools = ToolsConfig {
+        enabled: None,
+        disabled: vec!["otter_create_app".to_string()],
+        ..ToolsConfig::default()
+    };
+    let (db, client, server_handle, _temp_dir) =
+        setup_mcp_test_with(|service| McpServer::new(service).with_tools(&tools)).await?;
+
+    let listed = client.list_all_tools().await?;
+    assert!(listed.iter().any(|tool| tool.name == "otter_list_apps"));
+    assert!(!listed.iter().any(|tool| tool.name == "otter_create_app"));
+
+    // Listing still works
+    db.applications().create("existing-app").await?;
+    let result = client
+        .call_tool(CallToolRequestParam {
+            name: "otter_list_apps".into(),
+            arguments: None,
+        })
+        .await?;
+    let response: serde_json::Value =
+        serde_json::from_str(&result.content[0].as_text().unwrap().text)?;
+    assert_eq!(response["count"], 1);
+
+    // Creating is rejected as an unknown tool
+    let result = client
+        .call_tool(CallToolRequestParam {
+            name: "otter_create_app".into(),
+            arguments: serde_json::json!({ "name": "new-app" })
+                .as_object()
+                .cloned(),
+        })
+        .await;
+    let err = result.unwrap_err().to_string();
+    assert!(err.contains("tool not found"), "unexpected error: {err}");
+    assert_eq!(db.applications().count().await?, 1);
+
+    client.cancel().await?;
+    server_handle.await??;
+
+    Ok(())
+}
+
+/// Test that tool calls beyond the configured rate are rejected
+#[tokio::test]
+async fn test_mcp_tool_rate_limit() -> Result<(), Box<dyn std::error::Error>> {
+    let limits = LimitsConfig {
+        tool_calls_per_minute: Some(2),
+    };
+    let (db, client, server_handle, _temp_dir) =
+        setup_mcp_test_with(|service| McpServer::new(service).with_limits(&limits)).await?;
+
+    let create = |name: &'static str| {
+        client.call_tool(CallToolRequestParam {
+            name: "otter_create_app".into(),
+            arguments: serde_json::json!({ "name": name }).as_object().cloned(),
+        })
+    };
+
+    create("first").await?;
+    create("second").await?;
+
+    // The third call within the minute is rejected without creating anything
+    let err = create("third").await.unwrap_err().to_string();
+    assert!(err.contains("Rate limited"), "unexpected error: {err}");
+    assert!(err.contains("retry after 30s"), "unexpected error: {err}");
+    assert_eq!(db.applications().count().await?, 2);
+
+    client.cancel().await?;
+    server_handle.await??;
+
+    Ok(())
+}
+
+/// Test that an allow-list exposes only the listed tools
+#[tokio::test]
+async fn test_mcp_enabled_tools_allow_list() -> Result<(), Box<dyn std::error::Error>> {
+    let tools = ToolsConfig {
+        enabled: Some(vec!["otter_list_apps".to_string()]),
+        disabled: Vec::new(),
+        ..ToolsConfig::default()
+    };
+    let (_db, client, server_handle, _temp_dir) =
+        setup_mcp_test_with(|service| McpServer::new(service).with_tools(&tools)).await?;
+
+    let listed = client.list_all_tools().await?;
+    let names: Vec<_> = listed.iter().map(|tool| tool.name.to_string()).collect();
+    assert_eq!(names, vec!["otter_list_apps"]);
+
+    client.cancel().await?;
+    server_handle.await??;
+
+    Ok(())
+}
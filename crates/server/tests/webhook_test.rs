@@ -0,0 +1,152 @@
+use axum::extract::State;
+use axum::http::{HeaderMap, StatusCode};
+use axum::routing::post;
+use axum::Router;
+use hmac::{Hmac, Mac};
+use ottershipper_core::ApplicationService;
+use ottershipper_db::Database;
+use ottershipper_server::{WebhookConfig, WebhookNotifier, SIGNATURE_HEADER};
+use sha2::Sha256;
+use std::fmt::Write;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
+use tempfile::{tempdir, TempDir};
+use tokio::net::TcpListener;
+use tokio::sync::mpsc;
+
+/// A request received by the mock endpoint
+struct Received {
+    headers: HeaderMap,
+    body: String,
+}
+
+#[derive(Clone)]
+struct MockState {
+    tx: mpsc::UnboundedSender<Received>,
+    /// Requests to reject with a 500 before accepting
+    failures: Arc<AtomicUsize>,
+}
+
+/// Start a mock webhook endpoint that fails the first `failures` requests
+async fn start_mock_endpoint(
+    failures: usize,
+) -> Result<(String, mpsc::UnboundedReceiver<Received>), Box<dyn std::error::Error>> {
+    let (tx, rx) = mpsc::unbounded_channel();
+    let state = MockState {
+        tx,
+        failures: Arc::new(AtomicUsize::new(failures)),
+    };
+
+    let app = Router::new()
+        .route(
+            "/hook",
+            post(
+                |State(state): State<MockState>, headers: HeaderMap, body: String| async move {
+                    let _ = state.tx.send(Received { headers, body });
+                    let failing = state
+                        .failures
+                        .fetch_update(Ordering::SeqCst, Ordering::SeqCst, |n| n.checked_sub(1))
+                        .is_ok();
+                    if failing {
+                        StatusCode::INTERNAL_SERVER_ERROR
+                    } else {
+                        StatusCode::NO_CONTENT
+                    }
+                },
+            ),
+        )
+        .with_state(state);
+
+    let listener = TcpListener::bind("127.0.0.1:0").await?;
+    let url = format!("http://{}/hook", listener.local_addr()?);
+    tokio::spawn(async move { axum::serve(listener, app).await });
+
+    Ok((url, rx))
+}
+
+async fn setup_service() -> Result<(ApplicationService, TempDir), Box<dyn std::error::Error>> {
+    let temp_dir = tempdir()?;
+    let db = Database::new(temp_dir.path().join("test.db")).await?;
+    db.migrate().await?;
+    Ok((ApplicationService::new(db), temp_dir))
+}
+
+async fn next_request(
+    rx: &mut mpsc::UnboundedReceiver<Received>,
+) -> Result<Received, Box<dyn std::error::Error>> {
+    tokio::time::timeout(Duration::from_secs(10), rx.recv())
+        .await?
+        .ok_or_else(|| "mock endpoint closed".into())
+}
+
+/// Test that a create is posted with the expected payload and signature
+#[tokio::test]
+async fn test_webhook_delivers_signed_create() -> Result<(), Box<dyn std::error::Error>> {
+    let (url, mut rx) = start_mock_endpoint(0).await?;
+    let (service, _temp_dir) = setup_service().await?;
+
+    let config = WebhookConfig {
+        url: Some(url),
+        secret: Some("s3cret".to_string()),
+        ..WebhookConfig::default()
+    };
+    let _task = WebhookNotifier::from_config(&config)
+        .expect("url is configured")
+        .spawn(service.subscribe());
+
+    let app = service.create_app("hooked".to_string()).await?;
+    let received = next_request(&mut rx).await?;
+
+    let payload: serde_json::Value = serde_json::from_str(&received.body)?;
+    assert_eq!(payload["event"], "created");
+    assert_eq!(payload["application"]["id"], app.id.as_str());
+    assert_eq!(payload["application"]["name"], "hooked");
+    assert!(payload["timestamp"].as_i64().is_some());
+
+    let mut mac = Hmac::<Sha256>::new_from_slice(b"s3cret")?;
+    mac.update(received.body.as_bytes());
+    let expected =
+        mac.finalize()
+            .into_bytes()
+            .iter()
+            .fold(String::from("sha256="), |mut out, b| {
+                let _ = write!(out, "{b:02x}");
+                out
+            });
+    assert_eq!(received.headers[SIGNATURE_HEADER].to_str()?, expected);
+
+    Ok(())
+}
+
+/// Test that failed deliveries are retried
+#[tokio::test]
+async fn test_webhook_retries_failed_delivery() -> Result<(), Box<dyn std::error::Error>> {
+    let (url, mut rx) = start_mock_endpoint(1).await?;
+    let (service, _temp_dir) = setup_service().await?;
+
+    let config = WebhookConfig {
+        url: Some(url),
+        ..WebhookConfig::default()
+    };
+    let _task = WebhookNotifier::from_config(&config)
+        .expect("url is configured")
+        .spawn(service.subscribe());
+
+    service.create_app("retried".to_string()).await?;
+
+    let first = next_request(&mut rx).await?;
+    let second = next_request(&mut rx).await?;
+    assert_eq!(first.body, second.body);
+
+    // Unsigned without a secret
+    assert!(second.headers.get(SIGNATURE_HEADER).is_none());
+
+    Ok(())
+}
+
+/// Test that no notifier is built without a URL
+#[test]
+fn test_webhook_disabled_by_default() {
+    assert!(WebhookNotifier::from_config(&WebhookConfig::default()).is_none());
+}